@@ -248,6 +248,7 @@ fn main() {
       posted_before,
       outdir,
     } => {
+      use mws::finances;
       use std::thread::sleep;
       use std::time::Duration;
       let mut page = 1;
@@ -271,38 +272,31 @@ fn main() {
         loop {
           println!("loading page {} ...", page);
 
+          // the raw response is saved, the typed API only supplies the parameters
           let res = if let Some(next_token) = next_token.take() {
             client
               .request_xml_generic(
                 Method::Post,
-                "/Finances",
+                "/Finances/2015-05-01",
                 "2015-05-01",
                 "ListFinancialEventsByNextToken",
-                vec![
-                  (
-                    "NextToken".to_string(),
-                    next_token,
-                  ),
-                ],
+                finances::ListFinancialEventsByNextTokenParameters {
+                  NextToken: next_token,
+                },
               )
               .unwrap()
           } else {
             client
               .request_xml_generic(
                 Method::Post,
-                "/Finances",
+                "/Finances/2015-05-01",
                 "2015-05-01",
                 "ListFinancialEvents",
-                vec![
-                  (
-                    "PostedAfter".to_string(),
-                    get_utc_datetime(posted_after).to_rfc3339(),
-                  ),
-                  (
-                    "PostedBefore".to_string(),
-                    get_utc_datetime(posted_before).to_rfc3339(),
-                  ),
-                ],
+                finances::ListFinancialEventsParameters {
+                  PostedAfter: Some(get_utc_datetime(posted_after)),
+                  PostedBefore: Some(get_utc_datetime(posted_before)),
+                  ..Default::default()
+                },
               )
               .unwrap()
           };
@@ -428,9 +422,10 @@ fn get_client(env: &Env) -> Client {
   let opts = ClientOptions {
     endpoint: region.endpoint.to_string(),
     seller_id: env.seller_id.clone(),
-    mws_auth_token: env.auth_token.clone(),
+    mws_auth_token: env.auth_token.clone().map(Into::into),
     aws_access_key_id: env.access_key_id.clone(),
-    secret_key: env.secret_key.clone(),
+    secret_key: env.secret_key.clone().into(),
+    ..Default::default()
   };
   Client::new(opts).unwrap()
 }
@@ -0,0 +1,337 @@
+//! Local stand-in for Amazon MWS
+//!
+//! `MockServer` runs an HTTP server on `127.0.0.1` in a background thread. It authenticates
+//! requests with `mws::sign::SignatureV2Verifier`, routes them by
+//! path and `Action`, enforces the default throttling quotas of `mws::throttle` and serves
+//! programmable data for the Orders, Reports, Feeds, FBA Inventory and Subscriptions sections.
+//! `GetServiceStatus` is answered for every section.
+//!
+//! ```ignore
+//! let server = MockServer::start();
+//! server.add_order("<AmazonOrderId>111-1111111-1111111</AmazonOrderId>");
+//! let client = server.client();
+//! let res = mws::orders::ListOrders(&client, params)?;
+//! ```
+//!
+//! Lists are split into pages of `set_page_size` items and can be followed with
+//! the `...ByNextToken` operations.
+
+extern crate base64;
+extern crate chrono;
+extern crate crypto;
+extern crate mws;
+extern crate tiny_http;
+extern crate url;
+
+mod request;
+mod response;
+mod sections;
+
+pub use request::MockRequest;
+pub use response::MockResponse;
+pub use sections::FeedSubmission;
+
+use chrono::{DateTime, Utc};
+use mws::client::{Client, ClientOptions};
+use mws::secret::Secret;
+use mws::throttle::{Quota, ThrottleMode, Throttler};
+use sections::{is_section, State};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Credentials accepted by the server
+///
+/// The secret key and the auth token are masked in `Debug` output.
+#[derive(Debug, Clone)]
+pub struct Credentials {
+  pub aws_access_key_id: String,
+  pub secret_key: Secret,
+  pub seller_id: String,
+  pub mws_auth_token: Option<Secret>,
+}
+
+impl Default for Credentials {
+  fn default() -> Credentials {
+    Credentials {
+      aws_access_key_id: "AKIAMOCKACCESSKEYID".to_string(),
+      secret_key: "mock-secret-key".into(),
+      seller_id: "MOCKSELLERID".to_string(),
+      mws_auth_token: None,
+    }
+  }
+}
+
+struct Shared {
+  credentials: Credentials,
+  state: Mutex<State>,
+  throttler: Mutex<Throttler>,
+  overrides: Mutex<HashMap<(String, String), Vec<MockResponse>>>,
+  clock_offset: Mutex<chrono::Duration>,
+}
+
+/// A running stand-in server, stopped when dropped
+pub struct MockServer {
+  endpoint: String,
+  shared: Arc<Shared>,
+  stop: Arc<AtomicBool>,
+  thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+  /// Starts a server which accepts the default credentials.
+  pub fn start() -> MockServer {
+    MockServer::start_with_credentials(Credentials::default())
+  }
+
+  /// Starts a server which accepts `credentials`.
+  pub fn start_with_credentials(credentials: Credentials) -> MockServer {
+    let server = tiny_http::Server::http("127.0.0.1:0").expect("bind mock server");
+    let endpoint = format!("http://{}", server.server_addr());
+    let shared = Arc::new(Shared {
+      credentials: credentials,
+      state: Mutex::new(State::default()),
+      throttler: Mutex::new(Throttler::new(ThrottleMode::Reject)),
+      overrides: Mutex::new(HashMap::new()),
+      clock_offset: Mutex::new(chrono::Duration::zero()),
+    });
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let thread = {
+      let shared = shared.clone();
+      let stop = stop.clone();
+      thread::spawn(move || {
+        while !stop.load(Ordering::SeqCst) {
+          match server.recv_timeout(Duration::from_millis(50)) {
+            Ok(Some(mut req)) => {
+              let mock_req = MockRequest::read(&mut req);
+              let resp = shared.handle(mock_req);
+              let mut http_resp = tiny_http::Response::from_data(resp.body)
+                .with_status_code(resp.status)
+                .with_header(header("x-mws-request-id", &resp.request_id))
+                .with_header(header(
+                  "x-mws-timestamp",
+                  &shared.now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+                ));
+              for (k, v) in resp.headers {
+                http_resp.add_header(header(&k, &v));
+              }
+              req.respond(http_resp).ok();
+            }
+            Ok(None) => {}
+            Err(_) => break,
+          }
+        }
+      })
+    };
+
+    MockServer {
+      endpoint: endpoint,
+      shared: shared,
+      stop: stop,
+      thread: Some(thread),
+    }
+  }
+
+  /// Returns the endpoint of the server, e.g. `http://127.0.0.1:49152`.
+  pub fn endpoint(&self) -> &str {
+    &self.endpoint
+  }
+
+  /// Returns options which point to the server and carry the accepted credentials.
+  pub fn client_options(&self) -> ClientOptions {
+    let credentials = &self.shared.credentials;
+    ClientOptions {
+      endpoint: self.endpoint.clone(),
+      seller_id: credentials.seller_id.clone(),
+      mws_auth_token: credentials.mws_auth_token.clone(),
+      aws_access_key_id: credentials.aws_access_key_id.clone(),
+      secret_key: credentials.secret_key.clone(),
+      ..Default::default()
+    }
+  }
+
+  /// Returns a client connected to the server.
+  ///
+  /// Client-side throttling is disabled so requests reach the server's quotas.
+  pub fn client(&self) -> Client {
+    let mut client = Client::new(self.client_options()).expect("create client");
+    client.set_throttler(None);
+    client
+  }
+
+  /// Sets the maximum number of items returned in one page, 100 by default.
+  pub fn set_page_size(&self, page_size: usize) {
+    self.state().page_size = page_size;
+  }
+
+  /// Adds an order returned by `ListOrders`.
+  ///
+  /// `xml` is the content of the `Order` element, e.g. `<AmazonOrderId>...</AmazonOrderId>...`.
+  pub fn add_order(&self, xml: &str) {
+    self.state().orders.push(xml.to_string());
+  }
+
+  /// Adds an item returned by `ListOrderItems` for `amazon_order_id`.
+  ///
+  /// `xml` is the content of the `OrderItem` element.
+  pub fn add_order_item(&self, amazon_order_id: &str, xml: &str) {
+    self
+      .state()
+      .order_items
+      .entry(amazon_order_id.to_string())
+      .or_insert_with(Vec::new)
+      .push(xml.to_string());
+  }
+
+  /// Adds an item returned by `ListInventorySupply`.
+  ///
+  /// `xml` is the content of the `member` element, e.g. `<SellerSKU>...</SellerSKU>...`.
+  pub fn add_inventory_supply(&self, xml: &str) {
+    self.state().inventory_supply.push(xml.to_string());
+  }
+
+  /// Sets the content of the reports generated by `RequestReport` for `report_type`.
+  ///
+  /// Requests of other report types finish with `_DONE_NO_DATA_`.
+  pub fn set_report_content<C: Into<Vec<u8>>>(&self, report_type: &str, content: C) {
+    self
+      .state()
+      .report_contents
+      .insert(report_type.to_string(), content.into());
+  }
+
+  /// Sets the document returned by `GetFeedSubmissionResult`,
+  /// by default a successful `ProcessingReport`.
+  pub fn set_feed_result<C: Into<Vec<u8>>>(&self, content: C) {
+    self.state().feed_result = Some(content.into());
+  }
+
+  /// Sets the status returned by `GetServiceStatus` of the section at `path`, e.g. `RED`.
+  pub fn set_service_status(&self, path: &str, status: &str) {
+    self
+      .state()
+      .service_status
+      .insert(path.to_string(), status.to_string());
+  }
+
+  /// Returns the feeds received by `SubmitFeed`.
+  pub fn feed_submissions(&self) -> Vec<FeedSubmission> {
+    self.state().feed_submissions.clone()
+  }
+
+  /// Returns the authenticated requests received so far.
+  pub fn requests(&self) -> Vec<MockRequest> {
+    self.state().requests.clone()
+  }
+
+  /// Replaces the quota of an operation.
+  pub fn set_quota(&self, path: &str, action: &str, quota: Quota) {
+    self
+      .shared
+      .throttler
+      .lock()
+      .unwrap()
+      .set_quota(path, action, quota);
+  }
+
+  /// Queues a response for the next authenticated `action` request to `path`,
+  /// e.g. to simulate an error. Queued responses are sent once, in order.
+  pub fn push_response(&self, path: &str, action: &str, resp: MockResponse) {
+    self
+      .shared
+      .overrides
+      .lock()
+      .unwrap()
+      .entry((path.to_string(), action.to_string()))
+      .or_insert_with(Vec::new)
+      .push(resp);
+  }
+
+  /// Shifts the clock of the server, e.g. to test requests from a client with a wrong clock.
+  pub fn set_clock_offset(&self, offset: chrono::Duration) {
+    *self.shared.clock_offset.lock().unwrap() = offset;
+  }
+
+  fn state(&self) -> ::std::sync::MutexGuard<State> {
+    self.shared.state.lock().unwrap()
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    self.stop.store(true, Ordering::SeqCst);
+    if let Some(thread) = self.thread.take() {
+      thread.join().ok();
+    }
+  }
+}
+
+impl Shared {
+  fn now(&self) -> DateTime<Utc> {
+    Utc::now() + *self.clock_offset.lock().unwrap()
+  }
+
+  fn handle(&self, req: MockRequest) -> MockResponse {
+    if !is_section(&req.path) {
+      return MockResponse::error(
+        400,
+        "Sender",
+        "InvalidAddress",
+        &format!("The address {} is not valid for this endpoint.", req.path),
+      );
+    }
+
+    if let Err(resp) = req.authenticate(&self.credentials, self.now()) {
+      return resp;
+    }
+
+    let key = (req.path.clone(), req.action().to_string());
+    let resp = {
+      let mut overrides = self.overrides.lock().unwrap();
+      match overrides.get_mut(&key) {
+        Some(ref mut queue) if !queue.is_empty() => Some(queue.remove(0)),
+        _ => None,
+      }
+    };
+
+    // the server stands in for a single endpoint
+    let throttler = self.throttler.lock().unwrap();
+    let throttled = throttler
+      .try_acquire("", req.seller_id(), &req.path, req.action())
+      .is_err();
+    let quota = throttler.quota(&req.path, req.action()).map(|quota| {
+      (
+        quota.max_request_quota,
+        throttler
+          .remaining("", req.seller_id(), &req.path, req.action())
+          .unwrap_or(0),
+      )
+    });
+    drop(throttler);
+
+    let resp = if throttled {
+      MockResponse::error(503, "Sender", "RequestThrottled", "Request is throttled")
+    } else {
+      let mut state = self.state.lock().unwrap();
+      state.requests.push(req.clone());
+      match resp {
+        Some(resp) => resp,
+        None => state.handle(&req),
+      }
+    };
+
+    match quota {
+      Some((max, remaining)) => resp
+        .with_header("x-mws-quota-max", format!("{}.0", max))
+        .with_header("x-mws-quota-remaining", format!("{}.0", remaining)),
+      None => resp,
+    }
+  }
+}
+
+fn header(name: &str, value: &str) -> tiny_http::Header {
+  tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
@@ -0,0 +1,184 @@
+//! Parsing and authentication of incoming requests
+
+use chrono::{DateTime, Utc};
+use mws::secret::Secret;
+use mws::sign::{Method, SignatureV2Verifier, VerifyError};
+use std::io::Read;
+use tiny_http;
+use url::form_urlencoded;
+use Credentials;
+use MockResponse;
+
+/// A request received by the server
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+  pub method: String,
+  pub host: String,
+  pub path: String,
+  pub content_type: Option<String>,
+  /// Query string and form parameters
+  pub params: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
+
+impl MockRequest {
+  pub(crate) fn read(req: &mut tiny_http::Request) -> MockRequest {
+    let method = req.method().to_string();
+    let host = req
+      .headers()
+      .iter()
+      .find(|h| h.field.equiv("Host"))
+      .map(|h| h.value.as_str().to_lowercase())
+      .unwrap_or_default();
+    let content_type = req
+      .headers()
+      .iter()
+      .find(|h| h.field.equiv("Content-Type"))
+      .map(|h| h.value.as_str().to_string());
+    let is_form = content_type
+      .as_ref()
+      .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+      .unwrap_or(false);
+
+    let (path, query) = {
+      let url = req.url();
+      match url.find('?') {
+        Some(pos) => (url[..pos].to_string(), url[pos + 1..].to_string()),
+        None => (url.to_string(), String::new()),
+      }
+    };
+
+    let mut body = vec![];
+    req.as_reader().read_to_end(&mut body).ok();
+
+    let mut params: Vec<(String, String)> = form_urlencoded::parse(query.as_bytes())
+      .into_owned()
+      .collect();
+    if is_form {
+      params.extend(form_urlencoded::parse(&body).into_owned());
+    }
+
+    MockRequest {
+      method: method,
+      host: host,
+      path: path,
+      content_type: content_type,
+      params: params,
+      body: body,
+    }
+  }
+
+  /// Returns the first value of a parameter.
+  pub fn param(&self, name: &str) -> Option<&str> {
+    self
+      .params
+      .iter()
+      .find(|&&(ref k, _)| k == name)
+      .map(|&(_, ref v)| v.as_str())
+  }
+
+  /// Returns the values of all parameters whose name starts with `prefix` and ends with `suffix`,
+  /// e.g. the members of a list.
+  pub fn params_like(&self, prefix: &str, suffix: &str) -> Vec<&str> {
+    self
+      .params
+      .iter()
+      .filter(|&&(ref k, _)| k.starts_with(prefix) && k.ends_with(suffix))
+      .map(|&(_, ref v)| v.as_str())
+      .collect()
+  }
+
+  /// Returns the values of the list parameter `prefix` ordered by index, e.g. `prefix.1`,
+  /// `prefix.2`, ..., `prefix.10`.
+  pub fn list_param(&self, prefix: &str) -> Vec<&str> {
+    let mut items: Vec<(usize, &str)> = self
+      .params
+      .iter()
+      .filter_map(|&(ref k, ref v)| {
+        if k.starts_with(prefix) {
+          k[prefix.len()..].parse().ok().map(|i| (i, v.as_str()))
+        } else {
+          None
+        }
+      })
+      .collect();
+    items.sort_by_key(|&(i, _)| i);
+    items.into_iter().map(|(_, v)| v).collect()
+  }
+
+  pub fn action(&self) -> &str {
+    self.param("Action").unwrap_or("")
+  }
+
+  pub fn seller_id(&self) -> &str {
+    self
+      .param("SellerId")
+      .or_else(|| self.param("Merchant"))
+      .unwrap_or("")
+  }
+
+  /// Checks the credentials and the Signature V2 of the request, which must be sent at about `now`.
+  pub(crate) fn authenticate(
+    &self,
+    credentials: &Credentials,
+    now: DateTime<Utc>,
+  ) -> Result<(), MockResponse> {
+    match self.param("AWSAccessKeyId") {
+      Some(key) if key != credentials.aws_access_key_id => {
+        return Err(MockResponse::error(
+          401,
+          "Sender",
+          "InvalidAccessKeyId",
+          "The AWS Access Key Id you provided does not exist in our records.",
+        ))
+      }
+      _ => {}
+    }
+
+    let method: Method = self.method.parse().map_err(|_| {
+      MockResponse::error(
+        400,
+        "Sender",
+        "InvalidParameterValue",
+        &format!("Unsupported method {}.", self.method),
+      )
+    })?;
+    SignatureV2Verifier::new(credentials.secret_key.expose())
+      .at(now)
+      .verify(&method, &self.host, &self.path, &self.params)
+      .map_err(|err| match err {
+        VerifyError::MissingParameter(name) => MockResponse::error(
+          400,
+          "Sender",
+          "MissingParameter",
+          &format!("The request must contain the parameter {}.", name),
+        ),
+        VerifyError::SignatureDoesNotMatch { string_to_sign } => MockResponse::error(
+          403,
+          "Sender",
+          "SignatureDoesNotMatch",
+          &format!(
+            "The request signature we calculated does not match the signature you provided. The canonical string for this request should have been '{}'",
+            string_to_sign
+          ),
+        ),
+        err @ VerifyError::TimestampOutOfRange { .. } | err @ VerifyError::Expired { .. } => {
+          MockResponse::error(400, "Sender", "RequestExpired", &err.to_string())
+        }
+        err => MockResponse::error(400, "Sender", "InvalidParameterValue", &err.to_string()),
+      })?;
+
+    if self.seller_id() != credentials.seller_id
+      || self.param("MWSAuthToken") != credentials.mws_auth_token.as_ref().map(Secret::expose)
+    {
+      return Err(MockResponse::error(
+        401,
+        "Sender",
+        "AccessDenied",
+        "Access denied.",
+      ));
+    }
+
+    Ok(())
+  }
+}
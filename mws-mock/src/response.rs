@@ -0,0 +1,87 @@
+//! Response builders
+
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+static NEXT_REQUEST_ID: AtomicUsize = ATOMIC_USIZE_INIT;
+
+const XMLNS: &'static str = "https://mws.amazonservices.com/";
+
+/// A response sent by the server
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+  pub status: u16,
+  pub request_id: String,
+  pub headers: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
+
+impl MockResponse {
+  /// A response with a raw body, e.g. a report.
+  pub fn new<B: Into<Vec<u8>>>(status: u16, body: B) -> MockResponse {
+    MockResponse {
+      status: status,
+      request_id: next_request_id(),
+      headers: vec![],
+      body: body.into(),
+    }
+  }
+
+  /// A `{Action}Response` envelope containing `result` in `{Action}Result`.
+  pub fn xml(action: &str, result: &str) -> MockResponse {
+    let mut resp = MockResponse::new(200, vec![]);
+    resp.body = format!(
+      r#"<?xml version="1.0"?><{action}Response xmlns="{xmlns}"><{action}Result>{result}</{action}Result><ResponseMetadata><RequestId>{request_id}</RequestId></ResponseMetadata></{action}Response>"#,
+      action = action,
+      xmlns = XMLNS,
+      result = result,
+      request_id = resp.request_id
+    )
+    .into_bytes();
+    resp.with_header("Content-Type", "text/xml")
+  }
+
+  /// An `ErrorResponse` document.
+  pub fn error(status: u16, error_type: &str, code: &str, message: &str) -> MockResponse {
+    let mut resp = MockResponse::new(status, vec![]);
+    resp.body = format!(
+      r#"<?xml version="1.0"?><ErrorResponse xmlns="{xmlns}"><Error><Type>{error_type}</Type><Code>{code}</Code><Message>{message}</Message></Error><RequestId>{request_id}</RequestId></ErrorResponse>"#,
+      xmlns = XMLNS,
+      error_type = error_type,
+      code = code,
+      message = escape(message),
+      request_id = resp.request_id
+    )
+    .into_bytes();
+    resp.with_header("Content-Type", "text/xml")
+  }
+
+  pub fn with_header<N: Into<String>, V: Into<String>>(
+    mut self,
+    name: N,
+    value: V,
+  ) -> MockResponse {
+    self.headers.push((name.into(), value.into()));
+    self
+  }
+}
+
+fn next_request_id() -> String {
+  let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::SeqCst) + 1;
+  format!("00000000-0000-0000-0000-{:012}", id)
+}
+
+/// Escapes text for XML element content.
+pub fn escape(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      '\'' => out.push_str("&apos;"),
+      c => out.push(c),
+    }
+  }
+  out
+}
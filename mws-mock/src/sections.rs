@@ -0,0 +1,784 @@
+//! Operation handlers and the data they serve
+
+use chrono::{DateTime, Utc};
+use crypto::digest::Digest;
+use crypto::md5::Md5;
+use mws::service_status::SECTIONS;
+use request::MockRequest;
+use response::{escape, MockResponse};
+use std::collections::HashMap;
+
+/// A feed received by `SubmitFeed`
+#[derive(Debug, Clone)]
+pub struct FeedSubmission {
+  pub feed_submission_id: String,
+  pub feed_type: String,
+  pub marketplace_ids: Vec<String>,
+  pub content_type: Option<String>,
+  pub content: Vec<u8>,
+  pub submitted_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+struct ReportRequest {
+  report_request_id: String,
+  report_type: String,
+  submitted_date: DateTime<Utc>,
+  status: &'static str,
+  generated_report_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Report {
+  report_id: String,
+  report_type: String,
+  report_request_id: String,
+  available_date: DateTime<Utc>,
+  acknowledged: bool,
+  content: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Destination {
+  delivery_channel: String,
+  attributes: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct Subscription {
+  marketplace_id: String,
+  notification_type: String,
+  destination: Destination,
+  is_enabled: bool,
+}
+
+/// Data served by the server
+pub(crate) struct State {
+  pub page_size: usize,
+  pub orders: Vec<String>,
+  pub order_items: HashMap<String, Vec<String>>,
+  pub inventory_supply: Vec<String>,
+  pub report_contents: HashMap<String, Vec<u8>>,
+  pub feed_result: Option<Vec<u8>>,
+  pub feed_submissions: Vec<FeedSubmission>,
+  pub requests: Vec<MockRequest>,
+  /// Status returned by `GetServiceStatus` by path, `GREEN` by default
+  pub service_status: HashMap<String, String>,
+  report_requests: Vec<ReportRequest>,
+  reports: Vec<Report>,
+  destinations: Vec<(String, Destination)>,
+  subscriptions: Vec<Subscription>,
+  /// Remaining pages of lists by NextToken
+  cursors: HashMap<String, Cursor>,
+  next_id: u64,
+}
+
+impl Default for State {
+  fn default() -> State {
+    State {
+      page_size: 100,
+      orders: vec![],
+      order_items: HashMap::new(),
+      inventory_supply: vec![],
+      report_contents: HashMap::new(),
+      feed_result: None,
+      feed_submissions: vec![],
+      requests: vec![],
+      service_status: HashMap::new(),
+      report_requests: vec![],
+      reports: vec![],
+      destinations: vec![],
+      subscriptions: vec![],
+      cursors: HashMap::new(),
+      next_id: 0,
+    }
+  }
+}
+
+/// Remaining items of a list
+struct Cursor {
+  list: &'static str,
+  /// Fields repeated on every page before the items
+  prefix: String,
+  items: Vec<String>,
+}
+
+type HandlerResult = Result<MockResponse, MockResponse>;
+
+impl State {
+  /// Routes a request by path and `Action`.
+  pub fn handle(&mut self, req: &MockRequest) -> MockResponse {
+    let action = req.action().to_string();
+    let res = match (req.path.as_ref(), action.as_ref()) {
+      ("/", "RequestReport") => self.request_report(req),
+      ("/", "GetReportRequestList") => self.get_report_request_list(req),
+      ("/", "GetReportRequestListByNextToken") => self.by_next_token(req, "GetReportRequestList"),
+      ("/", "GetReportList") => self.get_report_list(req),
+      ("/", "GetReportListByNextToken") => self.by_next_token(req, "GetReportList"),
+      ("/", "GetReport") => self.get_report(req),
+      ("/", "SubmitFeed") => self.submit_feed(req),
+      ("/", "GetFeedSubmissionList") => self.get_feed_submission_list(req),
+      ("/", "GetFeedSubmissionListByNextToken") => self.by_next_token(req, "GetFeedSubmissionList"),
+      ("/", "GetFeedSubmissionResult") => self.get_feed_submission_result(req),
+      ("/Orders/2013-09-01", "ListOrders") => self.list_orders(req),
+      ("/Orders/2013-09-01", "ListOrdersByNextToken") => self.by_next_token(req, "ListOrders"),
+      ("/Orders/2013-09-01", "GetOrder") => self.get_order(req),
+      ("/Orders/2013-09-01", "ListOrderItems") => self.list_order_items(req),
+      ("/Orders/2013-09-01", "ListOrderItemsByNextToken") => {
+        self.by_next_token(req, "ListOrderItems")
+      }
+      ("/FulfillmentInventory/2010-10-01", "ListInventorySupply") => {
+        self.list_inventory_supply(req)
+      }
+      ("/FulfillmentInventory/2010-10-01", "ListInventorySupplyByNextToken") => {
+        self.by_next_token(req, "ListInventorySupply")
+      }
+      ("/Subscriptions/2013-07-01", "RegisterDestination") => self.register_destination(req),
+      ("/Subscriptions/2013-07-01", "DeregisterDestination") => self.deregister_destination(req),
+      ("/Subscriptions/2013-07-01", "ListRegisteredDestinations") => {
+        self.list_registered_destinations(req)
+      }
+      ("/Subscriptions/2013-07-01", "SendTestNotificationToDestination") => self
+        .find_destination(req)
+        .map(|_| MockResponse::xml(req.action(), "")),
+      ("/Subscriptions/2013-07-01", "CreateSubscription") => self.create_subscription(req),
+      ("/Subscriptions/2013-07-01", "UpdateSubscription") => self.update_subscription(req),
+      ("/Subscriptions/2013-07-01", "GetSubscription") => self.get_subscription(req),
+      ("/Subscriptions/2013-07-01", "DeleteSubscription") => self.delete_subscription(req),
+      ("/Subscriptions/2013-07-01", "ListSubscriptions") => self.list_subscriptions(req),
+      (path, "GetServiceStatus") if is_section(path) && path != "/" => Ok(MockResponse::xml(
+        "GetServiceStatus",
+        &format!(
+          "<Status>{}</Status><Timestamp>{}</Timestamp>",
+          self
+            .service_status
+            .get(path)
+            .map(String::as_str)
+            .unwrap_or("GREEN"),
+          timestamp(&Utc::now())
+        ),
+      )),
+      (path, _) if is_section(path) => Err(invalid_parameter(&format!(
+        "Action {} is not supported by the mock server.",
+        action
+      ))),
+      _ => Err(MockResponse::error(
+        400,
+        "Sender",
+        "InvalidAddress",
+        &format!("The address {} is not valid for this endpoint.", req.path),
+      )),
+    };
+    match res {
+      Ok(resp) | Err(resp) => resp,
+    }
+  }
+
+  fn next_id(&mut self) -> String {
+    self.next_id += 1;
+    format!("{}", 50000000000 + self.next_id)
+  }
+
+  /// Renders the first page of `items` and stores the rest under a new NextToken.
+  fn paginate(
+    &mut self,
+    action: &str,
+    mut cursor: Cursor,
+    max_count: Option<usize>,
+  ) -> MockResponse {
+    let page_size = max_count
+      .unwrap_or(self.page_size)
+      .min(self.page_size)
+      .max(1);
+    let page = if cursor.items.len() > page_size {
+      let rest = cursor.items.split_off(page_size);
+      let page = ::std::mem::replace(&mut cursor.items, rest);
+      Some(page)
+    } else {
+      None
+    };
+    match page {
+      Some(page) => {
+        let token = format!("{}-{}", cursor.list, self.next_id());
+        let resp = list_response(action, cursor.list, &cursor.prefix, &page, Some(&token));
+        self.cursors.insert(token, cursor);
+        resp
+      }
+      None => list_response(action, cursor.list, &cursor.prefix, &cursor.items, None),
+    }
+  }
+
+  fn by_next_token(&mut self, req: &MockRequest, list: &'static str) -> HandlerResult {
+    let token = required(req, "NextToken")?;
+    match self.cursors.remove(token) {
+      Some(ref cursor) if cursor.list != list => Err(invalid_parameter("Invalid NextToken.")),
+      Some(cursor) => Ok(self.paginate(req.action(), cursor, None)),
+      None => Err(invalid_parameter("Invalid NextToken.")),
+    }
+  }
+
+  fn list_orders(&mut self, req: &MockRequest) -> HandlerResult {
+    if req.params_like("MarketplaceId.", "").is_empty() {
+      return Err(missing_parameter("MarketplaceId"));
+    }
+    let cursor = Cursor {
+      list: "ListOrders",
+      prefix: String::new(),
+      items: self.orders.clone(),
+    };
+    let max_count = parse_max_count(req, "MaxResultsPerPage")?;
+    Ok(self.paginate(req.action(), cursor, max_count))
+  }
+
+  fn get_order(&mut self, req: &MockRequest) -> HandlerResult {
+    let ids = req.list_param("AmazonOrderId.Id.");
+    if ids.is_empty() {
+      return Err(missing_parameter("AmazonOrderId"));
+    }
+    if ids.len() > 50 {
+      return Err(invalid_parameter(
+        "AmazonOrderId list cannot contain more than 50 ids.",
+      ));
+    }
+    let orders: Vec<String> = ids
+      .iter()
+      .filter_map(|id| {
+        let tag = format!("<AmazonOrderId>{}</AmazonOrderId>", escape(id));
+        self
+          .orders
+          .iter()
+          .find(|order| order.contains(&tag))
+          .cloned()
+      })
+      .collect();
+    Ok(list_response(req.action(), "ListOrders", "", &orders, None))
+  }
+
+  fn list_order_items(&mut self, req: &MockRequest) -> HandlerResult {
+    let order_id = required(req, "AmazonOrderId")?.to_string();
+    let items = self
+      .order_items
+      .get(&order_id)
+      .cloned()
+      .ok_or_else(|| invalid_parameter(&format!("Invalid AmazonOrderId: {}", order_id)))?;
+    let cursor = Cursor {
+      list: "ListOrderItems",
+      prefix: format!("<AmazonOrderId>{}</AmazonOrderId>", escape(&order_id)),
+      items: items,
+    };
+    Ok(self.paginate(req.action(), cursor, None))
+  }
+
+  fn list_inventory_supply(&mut self, req: &MockRequest) -> HandlerResult {
+    let prefix = match req.param("MarketplaceId") {
+      Some(id) => format!("<MarketplaceId>{}</MarketplaceId>", escape(id)),
+      None => String::new(),
+    };
+    let cursor = Cursor {
+      list: "ListInventorySupply",
+      prefix: prefix,
+      items: self.inventory_supply.clone(),
+    };
+    Ok(self.paginate(req.action(), cursor, None))
+  }
+
+  fn request_report(&mut self, req: &MockRequest) -> HandlerResult {
+    let report_type = required(req, "ReportType")?.to_string();
+    let now = Utc::now();
+    let report_request_id = self.next_id();
+    let generated_report_id = match self.report_contents.get(&report_type).cloned() {
+      Some(content) => {
+        let report_id = self.next_id();
+        self.reports.push(Report {
+          report_id: report_id.clone(),
+          report_type: report_type.clone(),
+          report_request_id: report_request_id.clone(),
+          available_date: now,
+          acknowledged: false,
+          content: content,
+        });
+        Some(report_id)
+      }
+      None => None,
+    };
+    let info = ReportRequest {
+      report_request_id: report_request_id,
+      report_type: report_type,
+      submitted_date: now,
+      status: if generated_report_id.is_some() {
+        "_DONE_"
+      } else {
+        "_DONE_NO_DATA_"
+      },
+      generated_report_id: generated_report_id,
+    };
+    let xml = format!(
+      "<ReportRequestInfo>{}</ReportRequestInfo>",
+      report_request_info_xml(&ReportRequest {
+        status: "_SUBMITTED_",
+        generated_report_id: None,
+        ..info.clone()
+      })
+    );
+    self.report_requests.push(info);
+    Ok(MockResponse::xml(req.action(), &xml))
+  }
+
+  fn get_report_request_list(&mut self, req: &MockRequest) -> HandlerResult {
+    let ids = req.params_like("ReportRequestIdList.", "");
+    let types = req.params_like("ReportTypeList.", "");
+    let statuses = req.params_like("ReportProcessingStatusList.", "");
+    let items = self
+      .report_requests
+      .iter()
+      .rev()
+      .filter(|r| ids.is_empty() || ids.contains(&r.report_request_id.as_str()))
+      .filter(|r| types.is_empty() || types.contains(&r.report_type.as_str()))
+      .filter(|r| statuses.is_empty() || statuses.contains(&r.status))
+      .map(report_request_info_xml)
+      .collect();
+    let max_count = parse_max_count(req, "MaxCount")?;
+    let cursor = Cursor {
+      list: "GetReportRequestList",
+      prefix: String::new(),
+      items: items,
+    };
+    Ok(self.paginate(req.action(), cursor, max_count))
+  }
+
+  fn get_report_list(&mut self, req: &MockRequest) -> HandlerResult {
+    let ids = req.params_like("ReportRequestIdList.", "");
+    let types = req.params_like("ReportTypeList.", "");
+    let acknowledged = req.param("Acknowledged").map(|v| v == "true");
+    let items = self
+      .reports
+      .iter()
+      .rev()
+      .filter(|r| ids.is_empty() || ids.contains(&r.report_request_id.as_str()))
+      .filter(|r| types.is_empty() || types.contains(&r.report_type.as_str()))
+      .filter(|r| acknowledged.map(|v| r.acknowledged == v).unwrap_or(true))
+      .map(|r| {
+        format!(
+          "<ReportId>{}</ReportId><ReportType>{}</ReportType><ReportRequestId>{}</ReportRequestId><AvailableDate>{}</AvailableDate><Acknowledged>{}</Acknowledged>",
+          r.report_id,
+          escape(&r.report_type),
+          r.report_request_id,
+          timestamp(&r.available_date),
+          r.acknowledged
+        )
+      })
+      .collect();
+    let max_count = parse_max_count(req, "MaxCount")?;
+    let cursor = Cursor {
+      list: "GetReportList",
+      prefix: String::new(),
+      items: items,
+    };
+    Ok(self.paginate(req.action(), cursor, max_count))
+  }
+
+  fn get_report(&mut self, req: &MockRequest) -> HandlerResult {
+    let report_id = required(req, "ReportId")?;
+    let report = self
+      .reports
+      .iter()
+      .find(|r| r.report_id == report_id)
+      .ok_or_else(|| {
+        MockResponse::error(
+          400,
+          "Sender",
+          "InvalidReportId",
+          &format!("Report {} does not exist.", report_id),
+        )
+      })?;
+    Ok(
+      MockResponse::new(200, report.content.clone())
+        .with_header("Content-Type", "text/plain")
+        .with_header("Content-MD5", content_md5(&report.content)),
+    )
+  }
+
+  fn submit_feed(&mut self, req: &MockRequest) -> HandlerResult {
+    let feed_type = required(req, "FeedType")?.to_string();
+    let md5 = req
+      .param("ContentMD5Value")
+      .ok_or_else(|| missing_parameter("ContentMD5Value"))?;
+    if md5 != content_md5(&req.body) {
+      return Err(MockResponse::error(
+        400,
+        "Sender",
+        "ContentMD5DoesNotMatch",
+        "the Content-MD5 HTTP header you passed for your feed did not match the Content-MD5 we calculated for your feed",
+      ));
+    }
+    let submission = FeedSubmission {
+      feed_submission_id: self.next_id(),
+      feed_type: feed_type,
+      marketplace_ids: req
+        .params_like("MarketplaceIdList.", "")
+        .into_iter()
+        .map(ToString::to_string)
+        .collect(),
+      content_type: req.content_type.clone(),
+      content: req.body.clone(),
+      submitted_date: Utc::now(),
+    };
+    let xml = format!(
+      "<FeedSubmissionInfo>{}</FeedSubmissionInfo>",
+      feed_submission_info_xml(&submission, "_SUBMITTED_")
+    );
+    self.feed_submissions.push(submission);
+    Ok(MockResponse::xml(req.action(), &xml))
+  }
+
+  fn get_feed_submission_list(&mut self, req: &MockRequest) -> HandlerResult {
+    let ids = req.params_like("FeedSubmissionIdList.", "");
+    let types = req.params_like("FeedTypeList.", "");
+    let items = self
+      .feed_submissions
+      .iter()
+      .rev()
+      .filter(|f| ids.is_empty() || ids.contains(&f.feed_submission_id.as_str()))
+      .filter(|f| types.is_empty() || types.contains(&f.feed_type.as_str()))
+      .map(|f| feed_submission_info_xml(f, "_DONE_"))
+      .collect();
+    let max_count = parse_max_count(req, "MaxCount")?;
+    let cursor = Cursor {
+      list: "GetFeedSubmissionList",
+      prefix: String::new(),
+      items: items,
+    };
+    Ok(self.paginate(req.action(), cursor, max_count))
+  }
+
+  fn get_feed_submission_result(&mut self, req: &MockRequest) -> HandlerResult {
+    let id = required(req, "FeedSubmissionId")?;
+    let submission = self
+      .feed_submissions
+      .iter()
+      .find(|f| f.feed_submission_id == id)
+      .ok_or_else(|| {
+        MockResponse::error(
+          400,
+          "Sender",
+          "InvalidFeedSubmissionId",
+          &format!("Feed submission {} does not exist.", id),
+        )
+      })?;
+    let content = match self.feed_result {
+      Some(ref content) => content.clone(),
+      None => processing_report_xml(&submission.feed_submission_id).into_bytes(),
+    };
+    Ok(
+      MockResponse::new(200, content.clone())
+        .with_header("Content-Type", "text/xml")
+        .with_header("Content-MD5", content_md5(&content)),
+    )
+  }
+
+  fn find_destination(&self, req: &MockRequest) -> Result<usize, MockResponse> {
+    let marketplace_id = required(req, "MarketplaceId")?;
+    let destination = parse_destination(req, "Destination.")?;
+    self
+      .destinations
+      .iter()
+      .position(|&(ref m, ref d)| m == marketplace_id && *d == destination)
+      .ok_or_else(|| invalid_parameter("The destination is not registered."))
+  }
+
+  fn register_destination(&mut self, req: &MockRequest) -> HandlerResult {
+    if self.find_destination(req).is_ok() {
+      return Err(invalid_parameter("The destination is already registered."));
+    }
+    let marketplace_id = required(req, "MarketplaceId")?.to_string();
+    let destination = parse_destination(req, "Destination.")?;
+    self.destinations.push((marketplace_id, destination));
+    Ok(MockResponse::xml(req.action(), ""))
+  }
+
+  fn deregister_destination(&mut self, req: &MockRequest) -> HandlerResult {
+    let index = self.find_destination(req)?;
+    let (marketplace_id, destination) = self.destinations.remove(index);
+    self
+      .subscriptions
+      .retain(|s| !(s.marketplace_id == marketplace_id && s.destination == destination));
+    Ok(MockResponse::xml(req.action(), ""))
+  }
+
+  fn list_registered_destinations(&mut self, req: &MockRequest) -> HandlerResult {
+    let marketplace_id = required(req, "MarketplaceId")?;
+    let members: String = self
+      .destinations
+      .iter()
+      .filter(|&&(ref m, _)| m == marketplace_id)
+      .map(|&(_, ref d)| format!("<member>{}</member>", destination_xml(d)))
+      .collect();
+    Ok(MockResponse::xml(
+      req.action(),
+      &format!("<DestinationList>{}</DestinationList>", members),
+    ))
+  }
+
+  fn parse_subscription(&self, req: &MockRequest) -> Result<Subscription, MockResponse> {
+    let marketplace_id = required(req, "MarketplaceId")?.to_string();
+    let destination = parse_destination(req, "Subscription.Destination.")?;
+    if !self
+      .destinations
+      .iter()
+      .any(|&(ref m, ref d)| *m == marketplace_id && *d == destination)
+    {
+      return Err(invalid_parameter("The destination is not registered."));
+    }
+    Ok(Subscription {
+      marketplace_id: marketplace_id,
+      notification_type: required(req, "Subscription.NotificationType")?.to_string(),
+      destination: destination,
+      is_enabled: req.param("Subscription.IsEnabled") != Some("false"),
+    })
+  }
+
+  fn subscription_position(&self, s: &Subscription) -> Option<usize> {
+    self.subscriptions.iter().position(|item| {
+      item.marketplace_id == s.marketplace_id
+        && item.notification_type == s.notification_type
+        && item.destination == s.destination
+    })
+  }
+
+  fn create_subscription(&mut self, req: &MockRequest) -> HandlerResult {
+    let subscription = self.parse_subscription(req)?;
+    if self.subscription_position(&subscription).is_some() {
+      return Err(invalid_parameter("The subscription already exists."));
+    }
+    self.subscriptions.push(subscription);
+    Ok(MockResponse::xml(req.action(), ""))
+  }
+
+  fn update_subscription(&mut self, req: &MockRequest) -> HandlerResult {
+    let subscription = self.parse_subscription(req)?;
+    let index = self
+      .subscription_position(&subscription)
+      .ok_or_else(|| invalid_parameter("The subscription does not exist."))?;
+    self.subscriptions[index] = subscription;
+    Ok(MockResponse::xml(req.action(), ""))
+  }
+
+  fn find_subscription(&self, req: &MockRequest) -> Result<usize, MockResponse> {
+    let marketplace_id = required(req, "MarketplaceId")?;
+    let notification_type = required(req, "NotificationType")?;
+    let destination = parse_destination(req, "Destination.")?;
+    self
+      .subscriptions
+      .iter()
+      .position(|s| {
+        s.marketplace_id == marketplace_id
+          && s.notification_type == notification_type
+          && s.destination == destination
+      })
+      .ok_or_else(|| invalid_parameter("The subscription does not exist."))
+  }
+
+  fn get_subscription(&mut self, req: &MockRequest) -> HandlerResult {
+    let index = self.find_subscription(req)?;
+    Ok(MockResponse::xml(
+      req.action(),
+      &format!(
+        "<Subscription>{}</Subscription>",
+        subscription_xml(&self.subscriptions[index])
+      ),
+    ))
+  }
+
+  fn delete_subscription(&mut self, req: &MockRequest) -> HandlerResult {
+    let index = self.find_subscription(req)?;
+    self.subscriptions.remove(index);
+    Ok(MockResponse::xml(req.action(), ""))
+  }
+
+  fn list_subscriptions(&mut self, req: &MockRequest) -> HandlerResult {
+    let marketplace_id = required(req, "MarketplaceId")?;
+    let members: String = self
+      .subscriptions
+      .iter()
+      .filter(|s| s.marketplace_id == marketplace_id)
+      .map(|s| format!("<member>{}</member>", subscription_xml(s)))
+      .collect();
+    Ok(MockResponse::xml(
+      req.action(),
+      &format!("<SubscriptionList>{}</SubscriptionList>", members),
+    ))
+  }
+}
+
+/// Returns `true` if `path` belongs to a section served by the mock server.
+pub(crate) fn is_section(path: &str) -> bool {
+  path == "/"
+    || SECTIONS
+      .iter()
+      .any(|&(_, section_path, _)| section_path == path)
+}
+
+/// Renders a page of a list operation.
+///
+/// Each section wraps its list items differently.
+fn list_response(
+  action: &str,
+  list: &str,
+  prefix: &str,
+  page: &[String],
+  next_token: Option<&str>,
+) -> MockResponse {
+  let (item_tag, wrapper) = match list {
+    "ListOrders" => ("Order", Some("Orders")),
+    "ListOrderItems" => ("OrderItem", Some("OrderItems")),
+    "ListInventorySupply" => ("member", Some("InventorySupplyList")),
+    "GetReportRequestList" => ("ReportRequestInfo", None),
+    "GetReportList" => ("ReportInfo", None),
+    _ => ("FeedSubmissionInfo", None),
+  };
+
+  let mut xml = prefix.to_string();
+  if let Some(wrapper) = wrapper {
+    xml.push_str(&format!("<{}>", wrapper));
+  }
+  for item in page {
+    xml.push_str(&format!(
+      "<{tag}>{item}</{tag}>",
+      tag = item_tag,
+      item = item
+    ));
+  }
+  if let Some(wrapper) = wrapper {
+    xml.push_str(&format!("</{}>", wrapper));
+  }
+  if let Some(token) = next_token {
+    xml.push_str(&format!("<NextToken>{}</NextToken>", token));
+  }
+  // the Reports and Feeds sections also return HasNext
+  if wrapper.is_none() {
+    xml.push_str(&format!("<HasNext>{}</HasNext>", next_token.is_some()));
+  }
+  MockResponse::xml(action, &xml)
+}
+
+fn report_request_info_xml(r: &ReportRequest) -> String {
+  let mut xml = format!(
+    "<ReportRequestId>{}</ReportRequestId><ReportType>{}</ReportType><Scheduled>false</Scheduled><SubmittedDate>{}</SubmittedDate><ReportProcessingStatus>{}</ReportProcessingStatus>",
+    r.report_request_id,
+    escape(&r.report_type),
+    timestamp(&r.submitted_date),
+    r.status
+  );
+  if let Some(ref id) = r.generated_report_id {
+    xml.push_str(&format!(
+      "<GeneratedReportId>{}</GeneratedReportId><CompletedDate>{}</CompletedDate>",
+      id,
+      timestamp(&r.submitted_date)
+    ));
+  }
+  xml
+}
+
+fn feed_submission_info_xml(f: &FeedSubmission, status: &str) -> String {
+  format!(
+    "<FeedSubmissionId>{}</FeedSubmissionId><FeedType>{}</FeedType><SubmittedDate>{}</SubmittedDate><FeedProcessingStatus>{}</FeedProcessingStatus>",
+    f.feed_submission_id,
+    escape(&f.feed_type),
+    timestamp(&f.submitted_date),
+    status
+  )
+}
+
+fn processing_report_xml(feed_submission_id: &str) -> String {
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8"?><AmazonEnvelope xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="amzn-envelope.xsd"><Header><DocumentVersion>1.02</DocumentVersion><MerchantIdentifier>MOCK</MerchantIdentifier></Header><MessageType>ProcessingReport</MessageType><Message><MessageID>1</MessageID><ProcessingReport><DocumentTransactionID>{}</DocumentTransactionID><StatusCode>Complete</StatusCode><ProcessingSummary><MessagesProcessed>1</MessagesProcessed><MessagesSuccessful>1</MessagesSuccessful><MessagesWithError>0</MessagesWithError><MessagesWithWarning>0</MessagesWithWarning></ProcessingSummary></ProcessingReport></Message></AmazonEnvelope>"#,
+    feed_submission_id
+  )
+}
+
+fn parse_destination(req: &MockRequest, prefix: &str) -> Result<Destination, MockResponse> {
+  let channel_param = format!("{}DeliveryChannel", prefix);
+  let delivery_channel = required(req, &channel_param)?.to_string();
+  // Destination.AttributeList.member.N.Key / .Value
+  let list_prefix = format!("{}AttributeList.member.", prefix);
+  let mut attributes = vec![];
+  for &(ref k, ref v) in &req.params {
+    if k.starts_with(&list_prefix) && k.ends_with(".Key") {
+      let value_param = format!("{}Value", &k[..k.len() - 3]);
+      attributes.push((v.clone(), req.param(&value_param).unwrap_or("").to_string()));
+    }
+  }
+  attributes.sort();
+  Ok(Destination {
+    delivery_channel: delivery_channel,
+    attributes: attributes,
+  })
+}
+
+fn destination_xml(d: &Destination) -> String {
+  let members: String = d
+    .attributes
+    .iter()
+    .map(|&(ref k, ref v)| {
+      format!(
+        "<member><Key>{}</Key><Value>{}</Value></member>",
+        escape(k),
+        escape(v)
+      )
+    })
+    .collect();
+  format!(
+    "<DeliveryChannel>{}</DeliveryChannel><AttributeList>{}</AttributeList>",
+    escape(&d.delivery_channel),
+    members
+  )
+}
+
+fn subscription_xml(s: &Subscription) -> String {
+  format!(
+    "<NotificationType>{}</NotificationType><Destination>{}</Destination><IsEnabled>{}</IsEnabled>",
+    escape(&s.notification_type),
+    destination_xml(&s.destination),
+    s.is_enabled
+  )
+}
+
+fn required<'a>(req: &'a MockRequest, name: &str) -> Result<&'a str, MockResponse> {
+  req.param(name).ok_or_else(|| missing_parameter(name))
+}
+
+fn parse_max_count(req: &MockRequest, name: &str) -> Result<Option<usize>, MockResponse> {
+  match req.param(name) {
+    Some(v) => v
+      .parse()
+      .map(Some)
+      .map_err(|_| invalid_parameter(&format!("Invalid {}: {}", name, v))),
+    None => Ok(None),
+  }
+}
+
+fn missing_parameter(name: &str) -> MockResponse {
+  MockResponse::error(
+    400,
+    "Sender",
+    "MissingParameter",
+    &format!("The request must contain the parameter {}.", name),
+  )
+}
+
+fn invalid_parameter(message: &str) -> MockResponse {
+  MockResponse::error(400, "Sender", "InvalidParameterValue", message)
+}
+
+/// Base64 encoded MD5 digest, the format of `Content-MD5` and `ContentMD5Value`
+pub(crate) fn content_md5(content: &[u8]) -> String {
+  let mut md5 = Md5::new();
+  md5.input(content);
+  let mut digest = [0; 16];
+  md5.result(&mut digest);
+  ::base64::encode(&digest)
+}
+
+fn timestamp(date: &DateTime<Utc>) -> String {
+  date.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
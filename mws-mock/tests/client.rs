@@ -0,0 +1,417 @@
+extern crate chrono;
+extern crate mws;
+extern crate mws_mock;
+
+use mws::client::{Client, Method};
+use mws::pagination;
+use mws::result::MwsError;
+use mws::throttle::Quota;
+use mws_mock::{Credentials, MockResponse, MockServer};
+use std::time::Duration;
+
+fn error_code(err: MwsError) -> String {
+  match err {
+    MwsError::ErrorResponse(resp) => {
+      resp
+        .info
+        .expect("error info")
+        .errors
+        .into_iter()
+        .next()
+        .expect("error")
+        .code
+    }
+    err => panic!("unexpected error: {:?}", err),
+  }
+}
+
+#[test]
+fn test_signature() {
+  let server = MockServer::start();
+  let client = server.client();
+  let (status, _, body) = client
+    .request_raw(
+      Method::Post,
+      "/Orders/2013-09-01",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+    .expect("GetServiceStatus");
+  assert_eq!(status.as_u16(), 200);
+  assert!(String::from_utf8(body)
+    .unwrap()
+    .contains("<Status>GREEN</Status>"));
+
+  let mut options = server.client_options();
+  options.secret_key = "wrong".into();
+  let mut client = Client::new(options).unwrap();
+  client.set_throttler(None);
+  let err = client
+    .request_raw(
+      Method::Post,
+      "/Orders/2013-09-01",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+    .unwrap_err();
+  assert_eq!(error_code(err), "SignatureDoesNotMatch");
+}
+
+#[test]
+fn test_credentials_debug() {
+  let debug = format!("{:?}", Credentials::default());
+  assert!(!debug.contains("mock-secret-key"));
+}
+
+#[test]
+fn test_credentials() {
+  let server = MockServer::start_with_credentials(Credentials {
+    mws_auth_token: Some("TOKEN".into()),
+    ..Default::default()
+  });
+
+  let mut options = server.client_options();
+  options.mws_auth_token = None;
+  let mut client = Client::new(options).unwrap();
+  client.set_throttler(None);
+  let err = client
+    .request_raw(
+      Method::Post,
+      "/Orders/2013-09-01",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+    .unwrap_err();
+  assert_eq!(error_code(err), "AccessDenied");
+
+  let err = server
+    .client()
+    .request_raw(
+      Method::Post,
+      "/Unknown",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+    .unwrap_err();
+  assert_eq!(error_code(err), "InvalidAddress");
+}
+
+#[test]
+fn test_list_orders() {
+  use mws::orders::*;
+
+  let server = MockServer::start();
+  server.set_page_size(2);
+  for i in 0..5 {
+    server.add_order(&format!(
+      "<AmazonOrderId>111-0000000-000000{}</AmazonOrderId><OrderStatus>Unshipped</OrderStatus>",
+      i
+    ));
+  }
+  server.add_order_item(
+    "111-0000000-0000000",
+    "<OrderItemId>1</OrderItemId><SellerSKU>SKU</SellerSKU><QuantityOrdered>2</QuantityOrdered>",
+  );
+  let client = server.client();
+
+  let first = ListOrders(
+    &client,
+    ListOrdersParameters {
+      MarketplaceId: vec!["ATVPDKIKX0DER".to_string()],
+      ..Default::default()
+    },
+  );
+  let ids: Vec<String> = pagination::items(&client, first)
+    .map(|order| order.expect("order").AmazonOrderId)
+    .collect();
+  assert_eq!(
+    ids,
+    (0..5)
+      .map(|i| format!("111-0000000-000000{}", i))
+      .collect::<Vec<_>>()
+  );
+  assert_eq!(server.requests().len(), 3);
+
+  let res = ListOrderItems(&client, "111-0000000-0000000".to_string()).expect("ListOrderItems");
+  assert_eq!(res.AmazonOrderId, "111-0000000-0000000");
+  assert_eq!(res.OrderItems.len(), 1);
+  assert_eq!(res.OrderItems[0].QuantityOrdered, 2);
+}
+
+#[test]
+fn test_get_order() {
+  use mws::orders::*;
+
+  let server = MockServer::start();
+  for i in 0..60 {
+    server.add_order(&format!(
+      "<AmazonOrderId>111-0000000-00000{:02}</AmazonOrderId><OrderStatus>Shipped</OrderStatus>",
+      i
+    ));
+  }
+  let client = server.client();
+
+  let ids: Vec<String> = (0..60)
+    .rev()
+    .map(|i| format!("111-0000000-00000{:02}", i))
+    .chain(Some("111-9999999-9999999".to_string()))
+    .collect();
+  let res = GetOrder(&client, ids.clone()).expect("GetOrder");
+  assert_eq!(
+    res
+      .Orders
+      .iter()
+      .map(|order| order.AmazonOrderId.clone())
+      .collect::<Vec<_>>(),
+    &ids[..60]
+  );
+  assert!(res
+    .Orders
+    .iter()
+    .all(|order| order.OrderStatus == OrderStatus::Shipped));
+  assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_reports() {
+  use mws::reports::*;
+
+  let server = MockServer::start();
+  server.set_report_content("_GET_MERCHANT_LISTINGS_DATA_", "sku\tprice\nSKU\t1.00\n");
+  let client = server.client();
+
+  let res = RequestReport(
+    &client,
+    RequestReportParameters {
+      ReportType: "_GET_MERCHANT_LISTINGS_DATA_".to_string(),
+      ..Default::default()
+    },
+  )
+  .expect("RequestReport");
+  let request_id = res.ReportRequestInfo.ReportRequestId;
+
+  let res = GetReportRequestList(
+    &client,
+    GetReportRequestListParameters {
+      ReportRequestIdList: Some(vec![request_id.clone()]),
+      ..Default::default()
+    },
+  )
+  .expect("GetReportRequestList");
+  assert_eq!(res.ReportRequestInfo.len(), 1);
+  assert!(!res.HasNext);
+  let info = &res.ReportRequestInfo[0];
+  assert_eq!(info.ReportProcessingStatus, ReportProcessingStatus::_DONE_);
+  let report_id = info.GeneratedReportId.clone().expect("report id");
+
+  let res = GetReportList(&client, Default::default()).expect("GetReportList");
+  assert_eq!(res.ReportInfo.len(), 1);
+  assert_eq!(res.ReportInfo[0].ReportRequestId, request_id);
+
+  let mut content = vec![];
+  GetReport(&client, report_id, &mut content).expect("GetReport");
+  assert_eq!(content, b"sku\tprice\nSKU\t1.00\n");
+}
+
+#[test]
+fn test_submit_feed() {
+  use mws::feeds::*;
+
+  let server = MockServer::start();
+  let client = server.client();
+  let content = "sku\tquantity\nSKU\t1\n";
+
+  let res = SubmitFeed(
+    &client,
+    SubmitFeedParameters {
+      FeedType: "_POST_FLAT_FILE_INVLOADER_DATA_".to_string(),
+      ..Default::default()
+    },
+    ::std::io::Cursor::new(content),
+    "dibvgOo3MoKVEYzBP6Mrrg==".to_string(),
+    "text/tab-separated-values; charset=iso-8859-1".to_string(),
+  )
+  .expect("SubmitFeed");
+
+  let submissions = server.feed_submissions();
+  assert_eq!(submissions.len(), 1);
+  assert_eq!(
+    res.FeedSubmissionInfo.FeedSubmissionId,
+    submissions[0].feed_submission_id
+  );
+  assert_eq!(submissions[0].feed_type, "_POST_FLAT_FILE_INVLOADER_DATA_");
+  assert_eq!(submissions[0].content, content.as_bytes());
+
+  let err = SubmitFeed(
+    &client,
+    SubmitFeedParameters {
+      FeedType: "_POST_FLAT_FILE_INVLOADER_DATA_".to_string(),
+      ..Default::default()
+    },
+    ::std::io::Cursor::new(content),
+    "AAAAAAAAAAAAAAAAAAAAAA==".to_string(),
+    "text/tab-separated-values; charset=iso-8859-1".to_string(),
+  )
+  .unwrap_err();
+  assert_eq!(error_code(err), "ContentMD5DoesNotMatch");
+}
+
+#[test]
+fn test_throttling() {
+  let server = MockServer::start();
+  server.set_quota(
+    "/Orders/2013-09-01",
+    "GetServiceStatus",
+    Quota::new(2, Duration::from_secs(60)),
+  );
+  let client = server.client();
+  let send = || {
+    client.request_raw(
+      Method::Post,
+      "/Orders/2013-09-01",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+  };
+
+  let (_, headers, _) = send().expect("first request");
+  assert!(headers
+    .iter()
+    .any(|&(ref k, ref v)| k.eq_ignore_ascii_case("x-mws-quota-remaining") && v == "1.0"));
+  send().expect("second request");
+  let err = send().unwrap_err();
+  assert!(err.is_throttled());
+}
+
+#[test]
+fn test_push_response() {
+  use mws::fulfillment_inventory::*;
+
+  let server = MockServer::start();
+  server.add_inventory_supply(
+    "<SellerSKU>SKU</SellerSKU><FNSKU>X000000000</FNSKU><ASIN>B000000000</ASIN><TotalSupplyQuantity>3</TotalSupplyQuantity><InStockSupplyQuantity>3</InStockSupplyQuantity><EarliestAvailability><TimepointType>Immediately</TimepointType></EarliestAvailability>",
+  );
+  server.push_response(
+    "/FulfillmentInventory/2010-10-01",
+    "ListInventorySupply",
+    MockResponse::error(
+      500,
+      "Receiver",
+      "InternalError",
+      "We encountered an internal error.",
+    ),
+  );
+  let client = server.client();
+
+  let err = ListInventorySupply(&client, Default::default()).unwrap_err();
+  assert_eq!(error_code(err), "InternalError");
+
+  let res = ListInventorySupply(&client, Default::default()).expect("ListInventorySupply");
+  assert_eq!(res.InventorySupplyList.len(), 1);
+  assert_eq!(res.InventorySupplyList[0].SellerSKU, "SKU");
+  assert_eq!(res.InventorySupplyList[0].InStockSupplyQuantity, 3);
+}
+
+#[test]
+fn test_subscriptions() {
+  use mws::subscriptions::*;
+
+  let server = MockServer::start();
+  let client = server.client();
+  let destination = || Destination {
+    DeliveryChannel: DeliveryChannel::SQS,
+    AttributeList: vec![AttributeKeyValue {
+      Key: AttributeKey::sqsQueueUrl,
+      Value: "https://sqs.us-east-1.amazonaws.com/111111111/test".to_string(),
+    }],
+  };
+
+  RegisterDestination(
+    &client,
+    RegisterDestinationParameters {
+      MarketplaceId: "ATVPDKIKX0DER".to_string(),
+      Destination: destination(),
+    },
+  )
+  .expect("RegisterDestination");
+  let res = ListRegisteredDestinations(&client, "ATVPDKIKX0DER".to_string())
+    .expect("ListRegisteredDestinations");
+  assert_eq!(res.DestinationList, vec![destination()]);
+
+  CreateSubscription(
+    &client,
+    CreateSubscriptionParameters {
+      MarketplaceId: "ATVPDKIKX0DER".to_string(),
+      Subscription: Subscription {
+        NotificationType: NotificationType::AnyOfferChanged,
+        Destination: destination(),
+        IsEnabled: true,
+      },
+    },
+  )
+  .expect("CreateSubscription");
+  let res = GetSubscription(
+    &client,
+    GetSubscriptionParameters {
+      MarketplaceId: "ATVPDKIKX0DER".to_string(),
+      NotificationType: NotificationType::AnyOfferChanged,
+      Destination: destination(),
+    },
+  )
+  .expect("GetSubscription");
+  assert!(res.Subscription.IsEnabled);
+  assert_eq!(res.Subscription.Destination, destination());
+}
+
+#[test]
+fn test_clock_skew() {
+  let server = MockServer::start();
+  server.set_clock_offset(chrono::Duration::hours(1));
+  let options = server.client_options();
+  let mut client = Client::new(options.clone()).unwrap();
+  client.set_throttler(None);
+  let send = || {
+    client.request_raw(
+      Method::Post,
+      "/Orders/2013-09-01",
+      "2013-09-01",
+      "GetServiceStatus",
+      (),
+    )
+  };
+
+  let err = send().unwrap_err();
+  assert_eq!(error_code(err), "RequestExpired");
+  let offset = options.clock.offset();
+  assert!(offset > chrono::Duration::minutes(59) && offset < chrono::Duration::minutes(61));
+  send().expect("request with corrected clock");
+}
+
+#[test]
+fn test_service_status() {
+  use mws::service_status::{self, ServiceStatus};
+
+  let server = MockServer::start();
+  server.set_service_status("/Products/2011-10-01", "RED");
+  let client = server.client();
+
+  let res = mws::orders::GetServiceStatus(&client).expect("GetServiceStatus");
+  assert_eq!(res.Status, ServiceStatus::GREEN);
+  assert!(res.Timestamp.is_some());
+
+  let sections = service_status::poll_all(&client);
+  assert_eq!(sections.len(), service_status::SECTIONS.len());
+  for section in sections {
+    let status = section.result.expect("GetServiceStatus").Status;
+    if section.name == "Products" {
+      assert_eq!(status, ServiceStatus::RED);
+    } else {
+      assert_eq!(status, ServiceStatus::GREEN);
+    }
+  }
+}
@@ -0,0 +1,625 @@
+//! Futures-based client
+//!
+//! `AsyncClient` mirrors the request surface of [`Client`](../client/struct.Client.html),
+//! but every call returns a future instead of blocking the calling thread.
+//! Requests are sent through an [`AsyncHttpTransport`](../transport/trait.AsyncHttpTransport.html),
+//! which buffers response bodies before they are decoded with `FromXmlStream`.
+//!
+//! Requests go through the same pipeline as `Client`: every attempt waits for the quota of the
+//! throttler, passes through the middleware, and failed attempts are retried according to
+//! `ClientOptions::retry_policy`. Waiting never blocks the calling thread.
+
+use chrono::Utc;
+use client::{
+  complete_attempt, prepare_attempt, sign_request, sign_request_with_form, ClientOptions,
+  ContentType, Method, StatusCode,
+};
+use futures::future::{self, Loop};
+use futures::sync::oneshot;
+use futures::Future;
+use middleware::{Middleware, RequestContext};
+use operation::MwsOperation;
+use reqwest::unstable::async as reqwest_async;
+use result::{MwsError, MwsResult};
+use retry;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use throttle::{ThrottleMode, Throttler};
+use tokio_core::reactor::Handle;
+use transport::{AsyncHttpTransport, HttpRequest, HttpResponse};
+use xmlhelper::decode::{FromXmlStream, Stream};
+use SerializeMwsParams;
+
+/// Boxed future returned by `AsyncClient` and the asynchronous API functions
+pub type MwsFuture<T> = Box<dyn Future<Item = T, Error = MwsError>>;
+
+pub struct AsyncClient {
+  options: ClientOptions,
+  transport: Arc<dyn AsyncHttpTransport>,
+  throttler: Option<Arc<Throttler>>,
+  middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl AsyncClient {
+  pub fn new(options: ClientOptions, handle: &Handle) -> MwsResult<AsyncClient> {
+    Ok(AsyncClient::with_http_client(
+      options,
+      reqwest_async::Client::new(handle),
+    ))
+  }
+
+  pub fn with_http_client(
+    options: ClientOptions,
+    http_client: reqwest_async::Client,
+  ) -> AsyncClient {
+    AsyncClient::with_transport(options, http_client)
+  }
+
+  /// Constructs a client which sends requests through `transport`.
+  pub fn with_transport<T>(options: ClientOptions, transport: T) -> AsyncClient
+  where
+    T: AsyncHttpTransport + 'static,
+  {
+    AsyncClient {
+      options: options,
+      transport: Arc::new(transport),
+      throttler: Some(Arc::new(Throttler::default())),
+      middleware: vec![],
+    }
+  }
+
+  /// Returns the throttler which limits the request rate of this client.
+  pub fn throttler(&self) -> Option<&Arc<Throttler>> {
+    self.throttler.as_ref()
+  }
+
+  /// Replaces the throttler, e.g. to share quotas with a `Client` of the same seller,
+  /// or disables client-side throttling with `None`.
+  pub fn set_throttler(&mut self, throttler: Option<Arc<Throttler>>) {
+    self.throttler = throttler;
+  }
+
+  /// Adds middleware which is called around every request, after the middleware added before.
+  pub fn add_middleware<M>(&mut self, middleware: M)
+  where
+    M: Middleware + 'static,
+  {
+    self.middleware.push(Arc::new(middleware));
+  }
+
+  /// Signs and sends a request, retrying according to the retry policy.
+  ///
+  /// Works like `Client::send`, the request waits for the quota of `throttle_class`
+  /// and server errors are retried only if the request is `idempotent`.
+  fn send<F>(
+    &self,
+    throttle_class: &str,
+    idempotent: bool,
+    request: RequestContext,
+    sign: F,
+  ) -> MwsFuture<HttpResponse>
+  where
+    F: Fn(&ClientOptions, &RequestContext) -> MwsResult<HttpRequest> + 'static,
+  {
+    let pipeline = Rc::new(Pipeline {
+      options: self.options.clone(),
+      transport: self.transport.clone(),
+      throttler: self.throttler.clone(),
+      middleware: self.middleware.clone(),
+      throttle_class: throttle_class.to_string(),
+      idempotent: idempotent,
+      request: request,
+      sign: sign,
+      started_at: Instant::now(),
+    });
+    Box::new(future::loop_fn(1, move |attempt| {
+      send_attempt(pipeline.clone(), attempt)
+    }))
+  }
+
+  /// Sends `operation` and decodes its response.
+  pub fn execute<O>(&self, operation: O) -> MwsFuture<O::Response>
+  where
+    O: MwsOperation + 'static,
+  {
+    let request = self.options.request_context(
+      Method::Post,
+      O::PATH,
+      O::VERSION,
+      O::ACTION,
+      operation.into_params(),
+    );
+    let sign: fn(&ClientOptions, &RequestContext) -> MwsResult<HttpRequest> = if O::FORM {
+      sign_request_with_form
+    } else {
+      sign_request
+    };
+    Box::new(
+      self
+        .send(O::THROTTLE_CLASS, O::IDEMPOTENT, request, sign)
+        .and_then(O::decode_response),
+    )
+  }
+
+  pub fn request<P>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+  ) -> MwsFuture<HttpResponse>
+  where
+    P: SerializeMwsParams,
+  {
+    let request = self
+      .options
+      .request_context(method, path, version, action, parameters);
+    self.send(action, retry::is_idempotent(action), request, sign_request)
+  }
+
+  pub fn request_with_body<P>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+    body: Vec<u8>,
+    content_md5: String,
+    content_type: ContentType,
+  ) -> MwsFuture<HttpResponse>
+  where
+    P: SerializeMwsParams,
+  {
+    let request = self
+      .options
+      .request_context(method, path, version, action, parameters);
+    self.send(
+      action,
+      retry::is_idempotent(action),
+      request,
+      move |options, r| {
+        options.sign_request_with_body(
+          r.method.clone(),
+          &r.path,
+          &r.version,
+          &r.action,
+          r.parameters.clone(),
+          body.clone(),
+          content_md5.clone(),
+          content_type.clone(),
+        )
+      },
+    )
+  }
+
+  pub fn request_with_form<P>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+  ) -> MwsFuture<HttpResponse>
+  where
+    P: SerializeMwsParams,
+  {
+    let request = self
+      .options
+      .request_context(method, path, version, action, parameters);
+    self.send(
+      action,
+      retry::is_idempotent(action),
+      request,
+      sign_request_with_form,
+    )
+  }
+
+  pub fn request_xml<P, T>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+  ) -> MwsFuture<T>
+  where
+    P: SerializeMwsParams,
+    T: FromXmlStream<Stream<HttpResponse>> + 'static,
+  {
+    Box::new(
+      self
+        .request(method, path, version, action, parameters)
+        .and_then(decode_xml),
+    )
+  }
+
+  pub fn request_xml_with_form<P, T>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+  ) -> MwsFuture<T>
+  where
+    P: SerializeMwsParams,
+    T: FromXmlStream<Stream<HttpResponse>> + 'static,
+  {
+    Box::new(
+      self
+        .request_with_form(method, path, version, action, parameters)
+        .and_then(decode_xml),
+    )
+  }
+
+  pub fn request_xml_with_body<P, T>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+    body: Vec<u8>,
+    content_md5: String,
+    content_type: ContentType,
+  ) -> MwsFuture<T>
+  where
+    P: SerializeMwsParams,
+    T: FromXmlStream<Stream<HttpResponse>> + 'static,
+  {
+    Box::new(
+      self
+        .request_with_body(
+          method,
+          path,
+          version,
+          action,
+          parameters,
+          body,
+          content_md5,
+          content_type,
+        )
+        .and_then(decode_xml),
+    )
+  }
+
+  pub fn request_raw<P>(
+    &self,
+    method: Method,
+    path: &str,
+    version: &str,
+    action: &str,
+    parameters: P,
+  ) -> MwsFuture<(StatusCode, Vec<(String, String)>, Vec<u8>)>
+  where
+    P: SerializeMwsParams,
+  {
+    Box::new(
+      self
+        .request(method, path, version, action, parameters)
+        .and_then(|resp| {
+          let status = resp.status.clone();
+          let headers = resp.headers.clone();
+          resp.into_bytes().map(|body| (status, headers, body))
+        }),
+    )
+  }
+}
+
+/// A request sent by `AsyncClient::send`, shared by its attempts
+struct Pipeline<F> {
+  options: ClientOptions,
+  transport: Arc<dyn AsyncHttpTransport>,
+  throttler: Option<Arc<Throttler>>,
+  middleware: Vec<Arc<dyn Middleware>>,
+  throttle_class: String,
+  idempotent: bool,
+  request: RequestContext,
+  sign: F,
+  started_at: Instant,
+}
+
+/// Sends attempt number `attempt`, continues the loop with the next attempt if it should be retried.
+fn send_attempt<F>(pipeline: Rc<Pipeline<F>>, attempt: u32) -> MwsFuture<Loop<HttpResponse, u32>>
+where
+  F: Fn(&ClientOptions, &RequestContext) -> MwsResult<HttpRequest> + 'static,
+{
+  let throttled = throttle(
+    pipeline.throttler.clone(),
+    pipeline.options.endpoint.clone(),
+    pipeline.options.seller_id.clone(),
+    pipeline.request.path.clone(),
+    pipeline.throttle_class.clone(),
+  );
+  Box::new(
+    throttled.and_then(move |_| -> MwsFuture<Loop<HttpResponse, u32>> {
+      let (ctx, http_request) = match prepare_attempt(
+        &pipeline.options,
+        &pipeline.middleware,
+        &pipeline.request,
+        attempt,
+        &pipeline.sign,
+      ) {
+        Ok(v) => v,
+        Err(err) => return Box::new(future::err(err)),
+      };
+      let sent_at = Instant::now();
+      let sent_at_utc = Utc::now();
+      let response = pipeline.transport.send(http_request);
+      Box::new(
+        response.then(move |result| -> MwsFuture<Loop<HttpResponse, u32>> {
+          let result = complete_attempt(
+            &pipeline.options,
+            &pipeline.middleware,
+            &ctx,
+            sent_at,
+            sent_at_utc,
+            result,
+          );
+          let err = match result {
+            Ok(resp) => return Box::new(future::ok(Loop::Break(resp))),
+            Err(err) => err,
+          };
+          match pipeline.options.retry_policy.next_delay(
+            pipeline.idempotent,
+            &err,
+            attempt,
+            pipeline.started_at.elapsed(),
+          ) {
+            Some(wait) => Box::new(delay(wait).map(move |_| Loop::Continue(attempt + 1))),
+            None => Box::new(future::err(err)),
+          }
+        }),
+      )
+    }),
+  )
+}
+
+/// Takes one request from the bucket of `action`, waiting or failing according to the mode
+/// of the throttler.
+fn throttle(
+  throttler: Option<Arc<Throttler>>,
+  endpoint: String,
+  seller_id: String,
+  path: String,
+  action: String,
+) -> MwsFuture<()> {
+  let throttler = match throttler {
+    Some(throttler) => throttler,
+    None => return Box::new(future::ok(())),
+  };
+  Box::new(future::loop_fn((), move |_| -> MwsFuture<Loop<(), ()>> {
+    match throttler.try_acquire(&endpoint, &seller_id, &path, &action) {
+      Ok(()) => Box::new(future::ok(Loop::Break(()))),
+      Err(wait) => match throttler.mode() {
+        ThrottleMode::Wait => Box::new(delay(wait).map(Loop::Continue)),
+        ThrottleMode::Reject => Box::new(future::err(MwsError::Throttled {
+          action: action.clone(),
+          retry_after: wait,
+        })),
+      },
+    }
+  }))
+}
+
+/// Resolves after `duration`.
+///
+/// `AsyncClient` does not own a reactor handle, so the timer runs on a helper thread.
+fn delay(duration: Duration) -> MwsFuture<()> {
+  let (tx, rx) = oneshot::channel();
+  thread::spawn(move || {
+    thread::sleep(duration);
+    tx.send(()).ok();
+  });
+  Box::new(rx.map_err(|_| MwsError::Msg("timer stopped".to_string())))
+}
+
+fn decode_xml<T>(resp: HttpResponse) -> MwsResult<T>
+where
+  T: FromXmlStream<Stream<HttpResponse>>,
+{
+  let mut stream = Stream::new(resp);
+  T::from_xml(&mut stream)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dotenv::dotenv;
+  use std::env;
+  use tokio_core::reactor::Core;
+
+  #[test]
+  #[ignore]
+  fn it_works() {
+    dotenv().ok();
+    let mut core = Core::new().expect("create core");
+    let client = AsyncClient::new(
+      ClientOptions {
+        endpoint: env::var("Endpoint").expect("get Endpoint"),
+        seller_id: env::var("SellerId").expect("get SellerId"),
+        mws_auth_token: None,
+        aws_access_key_id: env::var("AWSAccessKeyId").expect("get AWSAccessKeyId"),
+        secret_key: env::var("SecretKey").expect("get SecretKey").into(),
+        ..Default::default()
+      },
+      &core.handle(),
+    )
+    .expect("create client");
+    let (status, _, body) = core
+      .run(client.request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      ))
+      .expect("send request");
+    let body = String::from_utf8(body).unwrap();
+    assert!(status.is_success());
+    assert!(body.starts_with("<?xml"));
+  }
+
+  struct FakeTransport {
+    responses: ::std::sync::Mutex<Vec<(StatusCode, &'static str)>>,
+    urls: Arc<::std::sync::Mutex<Vec<String>>>,
+  }
+
+  impl AsyncHttpTransport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> MwsFuture<HttpResponse> {
+      self.urls.lock().unwrap().push(request.url);
+      let (status, body) = self.responses.lock().unwrap().remove(0);
+      Box::new(future::ok(HttpResponse::new(status, vec![], body)))
+    }
+  }
+
+  fn get_fake_client(
+    options: ClientOptions,
+    responses: Vec<(StatusCode, &'static str)>,
+  ) -> (AsyncClient, Arc<::std::sync::Mutex<Vec<String>>>) {
+    let urls = Arc::new(::std::sync::Mutex::new(vec![]));
+    let client = AsyncClient::with_transport(
+      ClientOptions {
+        endpoint: "mws.amazonservices.com".to_string(),
+        seller_id: "SELLER".to_string(),
+        aws_access_key_id: "KEY".to_string(),
+        secret_key: "SECRET".into(),
+        ..options
+      },
+      FakeTransport {
+        responses: ::std::sync::Mutex::new(responses),
+        urls: urls.clone(),
+      },
+    );
+    (client, urls)
+  }
+
+  #[test]
+  fn test_retry_and_middleware() {
+    use middleware::ResponseContext;
+    use retry::RetryPolicy;
+
+    struct Recorder(Arc<::std::sync::Mutex<Vec<(u32, Option<StatusCode>)>>>);
+
+    impl Middleware for Recorder {
+      fn before_send(&self, request: &mut RequestContext) {
+        request
+          .parameters
+          .push(("Tag".to_string(), "TAG".to_string()));
+      }
+
+      fn after_receive(&self, request: &RequestContext, response: &ResponseContext) {
+        self
+          .0
+          .lock()
+          .unwrap()
+          .push((request.attempt, response.status.clone()));
+      }
+    }
+
+    let (mut client, urls) = get_fake_client(
+      ClientOptions {
+        retry_policy: RetryPolicy {
+          base_delay: Duration::from_millis(1),
+          jitter: 0.0,
+          ..RetryPolicy::new(3)
+        },
+        ..Default::default()
+      },
+      vec![(StatusCode::ServiceUnavailable, ""), (StatusCode::Ok, "OK")],
+    );
+    let calls = Arc::new(::std::sync::Mutex::new(vec![]));
+    client.add_middleware(Recorder(calls.clone()));
+    let (status, _, body) = client
+      .request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      )
+      .wait()
+      .expect("send request");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(body, b"OK");
+    assert_eq!(
+      *calls.lock().unwrap(),
+      vec![
+        (1, Some(StatusCode::ServiceUnavailable)),
+        (2, Some(StatusCode::Ok)),
+      ]
+    );
+    let urls = urls.lock().unwrap();
+    assert_eq!(urls.len(), 2);
+    assert!(urls.iter().all(|url| url.contains("Tag=TAG")));
+  }
+
+  #[test]
+  fn test_throttle() {
+    use throttle::Quota;
+
+    let (mut client, urls) = get_fake_client(
+      Default::default(),
+      vec![(StatusCode::Ok, "OK"), (StatusCode::Ok, "OK")],
+    );
+    let throttler = Throttler::new(ThrottleMode::Reject);
+    throttler.set_quota(
+      "/Orders/2013-09-01",
+      "GetServiceStatus",
+      Quota::new(1, Duration::from_secs(3600)),
+    );
+    client.set_throttler(Some(Arc::new(throttler)));
+    let send = || {
+      client
+        .request_raw(
+          Method::Post,
+          "/Orders/2013-09-01",
+          "2013-09-01",
+          "GetServiceStatus",
+          (),
+        )
+        .wait()
+    };
+    send().expect("send request");
+    match send() {
+      Err(MwsError::Throttled { ref action, .. }) => assert_eq!(action, "GetServiceStatus"),
+      other => panic!(
+        "unexpected result: {:?}",
+        other.map(|(status, _, _)| status)
+      ),
+    }
+    assert_eq!(urls.lock().unwrap().len(), 1);
+
+    // waits for the bucket instead of failing
+    let (mut client, urls) = get_fake_client(
+      Default::default(),
+      vec![(StatusCode::Ok, "OK"), (StatusCode::Ok, "OK")],
+    );
+    let throttler = Throttler::new(ThrottleMode::Wait);
+    throttler.set_quota(
+      "/Orders/2013-09-01",
+      "GetServiceStatus",
+      Quota::new(1, Duration::from_millis(50)),
+    );
+    client.set_throttler(Some(Arc::new(throttler)));
+    let started = Instant::now();
+    for _ in 0..2 {
+      client
+        .request_raw(
+          Method::Post,
+          "/Orders/2013-09-01",
+          "2013-09-01",
+          "GetServiceStatus",
+          (),
+        )
+        .wait()
+        .expect("send request");
+    }
+    assert!(started.elapsed() >= Duration::from_millis(40));
+    assert_eq!(urls.lock().unwrap().len(), 2);
+  }
+}
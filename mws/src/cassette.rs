@@ -0,0 +1,347 @@
+//! Record and replay MWS traffic
+//!
+//! `CassetteTransport` wraps another transport. In record mode it sends every request
+//! and appends the request and the raw response to a JSON cassette file. In replay mode it
+//! serves the recorded responses without touching the network, so tests which need live
+//! credentials can run offline.
+//!
+//! Requests are matched by method, path, `Action` and the remaining query or form parameters.
+//! `Signature`, `Timestamp` and `Expires` are ignored, and the values of `AWSAccessKeyId`,
+//! `MWSAuthToken`, `SellerId` and `Merchant` are redacted before anything is written.
+//! Identical requests are replayed in the order they were recorded.
+
+use result::{MwsError, MwsResult};
+use serde_json;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use transport::{HttpRequest, HttpResponse, HttpTransport, StatusCode};
+use url::{form_urlencoded, Url};
+
+const REDACTED: &'static str = "REDACTED";
+
+/// Parameters which are not recorded
+static IGNORED_PARAMS: &'static [&'static str] = &["Signature", "Timestamp", "Expires"];
+
+/// Parameters which are recorded with a redacted value
+static SECRET_PARAMS: &'static [&'static str] =
+  &["AWSAccessKeyId", "MWSAuthToken", "SellerId", "Merchant"];
+
+/// A recorded request and its response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+  pub method: String,
+  pub path: String,
+  pub action: String,
+  /// Sorted request parameters with secrets redacted
+  pub params: Vec<(String, String)>,
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  /// Response body, base64 encoded if `body_base64` is `true`
+  pub body: String,
+  #[serde(default)]
+  pub body_base64: bool,
+}
+
+/// Content of a cassette file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+  pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+  pub fn load<P: AsRef<Path>>(path: P) -> MwsResult<Cassette> {
+    let file = File::open(path)?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|err| MwsError::Msg(err.to_string()))
+  }
+
+  pub fn save<P: AsRef<Path>>(&self, path: P) -> MwsResult<()> {
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(BufWriter::new(file), self)
+      .map_err(|err| MwsError::Msg(err.to_string()))
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CassetteMode {
+  Record,
+  Replay,
+}
+
+struct State {
+  cassette: Cassette,
+  /// Marks interactions which have been replayed
+  used: Vec<bool>,
+}
+
+/// Transport which records to or replays from a cassette file
+pub struct CassetteTransport {
+  mode: CassetteMode,
+  path: PathBuf,
+  inner: Option<Box<dyn HttpTransport>>,
+  ignored_params: Vec<String>,
+  state: Mutex<State>,
+}
+
+impl CassetteTransport {
+  /// Sends requests through `inner` and records them to `path`.
+  ///
+  /// The file is overwritten after every request.
+  pub fn record<P, T>(path: P, inner: T) -> CassetteTransport
+  where
+    P: AsRef<Path>,
+    T: HttpTransport + 'static,
+  {
+    CassetteTransport::new(
+      CassetteMode::Record,
+      path.as_ref().to_owned(),
+      Some(Box::new(inner)),
+      Cassette::default(),
+    )
+  }
+
+  /// Serves the responses recorded in `path`.
+  pub fn replay<P: AsRef<Path>>(path: P) -> MwsResult<CassetteTransport> {
+    let cassette = Cassette::load(path.as_ref())?;
+    Ok(CassetteTransport::new(
+      CassetteMode::Replay,
+      path.as_ref().to_owned(),
+      None,
+      cassette,
+    ))
+  }
+
+  fn new(
+    mode: CassetteMode,
+    path: PathBuf,
+    inner: Option<Box<dyn HttpTransport>>,
+    cassette: Cassette,
+  ) -> CassetteTransport {
+    let used = vec![false; cassette.interactions.len()];
+    CassetteTransport {
+      mode: mode,
+      path: path,
+      inner: inner,
+      ignored_params: vec![],
+      state: Mutex::new(State {
+        cassette: cassette,
+        used: used,
+      }),
+    }
+  }
+
+  /// Excludes a parameter from recording and matching, e.g. a `CreatedAfter`
+  /// computed from the current time.
+  pub fn ignore_param<N: Into<String>>(mut self, name: N) -> CassetteTransport {
+    self.ignored_params.push(name.into());
+    self
+  }
+
+  pub fn mode(&self) -> CassetteMode {
+    self.mode
+  }
+
+  /// Returns a copy of the recorded interactions.
+  pub fn cassette(&self) -> Cassette {
+    self.state.lock().unwrap().cassette.clone()
+  }
+
+  fn normalize(&self, request: &HttpRequest) -> MwsResult<(String, String, Vec<(String, String)>)> {
+    let url = Url::parse(&request.url).map_err(|err| MwsError::Msg(err.to_string()))?;
+    let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let is_form = request
+      .header("Content-Type")
+      .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+      .unwrap_or(false);
+    if is_form {
+      if let Some(ref body) = request.body {
+        pairs.extend(form_urlencoded::parse(body).into_owned());
+      }
+    }
+
+    let mut action = String::new();
+    let mut params = vec![];
+    for (k, v) in pairs {
+      if k == "Action" {
+        action = v;
+      } else if IGNORED_PARAMS.contains(&k.as_str()) || self.ignored_params.contains(&k) {
+        continue;
+      } else if SECRET_PARAMS.contains(&k.as_str()) {
+        params.push((k, REDACTED.to_string()));
+      } else {
+        params.push((k, v));
+      }
+    }
+    params.sort();
+    Ok((url.path().to_string(), action, params))
+  }
+}
+
+impl HttpTransport for CassetteTransport {
+  fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+    let method = request.method.to_string();
+    let (path, action, params) = self.normalize(&request)?;
+
+    match self.inner {
+      Some(ref inner) if self.mode == CassetteMode::Record => {
+        let resp = inner.send(request)?;
+        let status = resp.status.clone();
+        let headers = resp.headers.clone();
+        let body = resp.into_bytes()?;
+        let (body_text, body_base64) = match String::from_utf8(body.clone()) {
+          Ok(text) => (text, false),
+          Err(_) => (::base64::encode(&body), true),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        state.cassette.interactions.push(Interaction {
+          method: method,
+          path: path,
+          action: action,
+          params: params,
+          status: status.as_u16(),
+          headers: headers.clone(),
+          body: body_text,
+          body_base64: body_base64,
+        });
+        state.used.push(true);
+        state.cassette.save(&self.path)?;
+
+        Ok(HttpResponse::new(status, headers, body))
+      }
+      _ => {
+        let mut state = self.state.lock().unwrap();
+        let index = {
+          let State {
+            ref cassette,
+            ref used,
+          } = *state;
+          cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .position(|(i, item)| {
+              !used[i]
+                && item.method == method
+                && item.path == path
+                && item.action == action
+                && item.params == params
+            })
+        };
+        let index = index.ok_or_else(|| {
+          MwsError::Msg(format!(
+            "no recorded interaction for '{}' in cassette '{}'",
+            action,
+            self.path.display()
+          ))
+        })?;
+        state.used[index] = true;
+
+        let item = &state.cassette.interactions[index];
+        let body = if item.body_base64 {
+          ::base64::decode(&item.body).map_err(|err| MwsError::Msg(err.to_string()))?
+        } else {
+          item.body.clone().into_bytes()
+        };
+        let status = StatusCode::try_from(item.status)
+          .map_err(|_| MwsError::Msg(format!("invalid status code: {}", item.status)))?;
+        Ok(HttpResponse::new(status, item.headers.clone(), body))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use client::{Client, ClientOptions, Method};
+  use std::env;
+
+  struct FakeTransport;
+
+  impl HttpTransport for FakeTransport {
+    fn send(&self, _: HttpRequest) -> MwsResult<HttpResponse> {
+      Ok(HttpResponse::new(
+        StatusCode::Ok,
+        vec![("x-mws-request-id".to_string(), "REQUEST".to_string())],
+        "<GetServiceStatusResponse/>",
+      ))
+    }
+  }
+
+  fn get_options(seller_id: &str, secret_key: &str) -> ClientOptions {
+    ClientOptions {
+      endpoint: "mws.amazonservices.com".to_string(),
+      seller_id: seller_id.to_string(),
+      mws_auth_token: Some("TOKEN".into()),
+      aws_access_key_id: "KEY".to_string(),
+      secret_key: secret_key.into(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_record_replay() {
+    let path = env::temp_dir().join("mws_cassette_test_record_replay.json");
+
+    let client = Client::with_transport(
+      get_options("SELLER", "SECRET"),
+      CassetteTransport::record(&path, FakeTransport),
+    );
+    client
+      .request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      )
+      .expect("record");
+
+    let cassette = Cassette::load(&path).expect("load cassette");
+    assert_eq!(cassette.interactions.len(), 1);
+    let item = &cassette.interactions[0];
+    assert_eq!(item.path, "/Orders/2013-09-01");
+    assert_eq!(item.action, "GetServiceStatus");
+    assert!(item.params.iter().all(|&(ref k, _)| k != "Signature"));
+    assert!(item
+      .params
+      .contains(&("SellerId".to_string(), REDACTED.to_string())));
+    assert!(item
+      .params
+      .contains(&("MWSAuthToken".to_string(), REDACTED.to_string())));
+
+    // credentials do not matter in replay mode
+    let client = Client::with_transport(
+      get_options("OTHER", "OTHER"),
+      CassetteTransport::replay(&path).expect("replay"),
+    );
+    let (status, headers, body) = client
+      .request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      )
+      .expect("replay");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+      headers,
+      vec![("x-mws-request-id".to_string(), "REQUEST".to_string())]
+    );
+    assert_eq!(body, b"<GetServiceStatusResponse/>");
+
+    // every interaction is replayed once
+    assert!(client
+      .request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      )
+      .is_err());
+  }
+}
@@ -0,0 +1,86 @@
+//! Clock skew compensation
+//!
+//! MWS rejects requests whose `Timestamp` is more than 15 minutes away from its own clock with
+//! `RequestExpired`. `Client` measures the difference from the `x-mws-timestamp` (or `Date`)
+//! header of every response and signs the following requests with the corrected time.
+
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+/// Estimated difference between the clock of MWS and the local clock
+pub struct ServerClock {
+  offset_ms: AtomicIsize,
+}
+
+impl ServerClock {
+  pub fn new() -> ServerClock {
+    ServerClock {
+      offset_ms: AtomicIsize::new(0),
+    }
+  }
+
+  /// Returns the time of MWS minus the local time.
+  pub fn offset(&self) -> Duration {
+    Duration::milliseconds(self.offset_ms.load(Ordering::SeqCst) as i64)
+  }
+
+  pub fn set_offset(&self, offset: Duration) {
+    self
+      .offset_ms
+      .store(offset.num_milliseconds() as isize, Ordering::SeqCst);
+  }
+
+  /// Returns the current time of MWS.
+  pub fn now(&self) -> DateTime<Utc> {
+    Utc::now() + self.offset()
+  }
+
+  /// Updates the offset from `server_time`, which MWS sent in the response to a request
+  /// sent at `sent_at` and answered at `received_at`, both local times.
+  ///
+  /// The server time is assumed to be taken halfway between the two.
+  pub fn observe(
+    &self,
+    server_time: DateTime<Utc>,
+    sent_at: DateTime<Utc>,
+    received_at: DateTime<Utc>,
+  ) {
+    let local_time = sent_at + (received_at.signed_duration_since(sent_at) / 2);
+    self.set_offset(server_time.signed_duration_since(local_time));
+  }
+}
+
+impl Default for ServerClock {
+  fn default() -> ServerClock {
+    ServerClock::new()
+  }
+}
+
+impl fmt::Debug for ServerClock {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("ServerClock")
+      .field("offset", &self.offset())
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_observe() {
+    let clock = ServerClock::new();
+    assert_eq!(clock.offset(), Duration::zero());
+
+    let sent_at: DateTime<Utc> = "2018-01-01T00:00:00Z".parse().unwrap();
+    let received_at = sent_at + Duration::seconds(2);
+    let server_time = sent_at + Duration::minutes(20);
+    clock.observe(server_time, sent_at, received_at);
+    assert_eq!(clock.offset(), Duration::minutes(20) - Duration::seconds(1));
+
+    clock.observe(sent_at - Duration::minutes(5), sent_at, sent_at);
+    assert_eq!(clock.offset(), Duration::minutes(-5));
+  }
+}
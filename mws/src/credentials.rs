@@ -0,0 +1,251 @@
+//! Credential providers
+//!
+//! A [`CredentialProvider`](trait.CredentialProvider.html) resolves the developer keys of a region
+//! and the `MWSAuthToken` a seller granted the developer, so a
+//! [`ClientPool`](../pool/struct.ClientPool.html) can create clients for many sellers.
+
+use result::{MwsError, MwsResult};
+use secret::Secret;
+use serde_json;
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Access keys of a developer account
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DeveloperCredentials {
+  pub aws_access_key_id: String,
+  pub secret_key: Secret,
+}
+
+/// Resolves the credentials used to sign requests
+pub trait CredentialProvider: Send + Sync {
+  /// Returns the access keys of the developer account registered in `region_id`.
+  fn developer_credentials(&self, region_id: &str) -> MwsResult<DeveloperCredentials>;
+
+  /// Returns the auth token `seller_id` granted the developer in `region_id`,
+  /// or `None` if the seller is the developer itself.
+  fn mws_auth_token(&self, seller_id: &str, region_id: &str) -> MwsResult<Option<Secret>>;
+}
+
+/// Reads credentials from environment variables
+///
+/// With the default prefix `MWS`:
+///
+/// - `MWS_NA_AWS_ACCESS_KEY_ID` and `MWS_NA_SECRET_KEY` are the developer keys of the `na` region,
+///   `MWS_AWS_ACCESS_KEY_ID` and `MWS_SECRET_KEY` are used for regions without their own keys
+/// - `MWS_AUTH_TOKEN_A1B2C3` is the auth token of seller `A1B2C3`, in every region
+#[derive(Debug, Clone)]
+pub struct EnvCredentialProvider {
+  prefix: String,
+}
+
+impl EnvCredentialProvider {
+  pub fn new() -> EnvCredentialProvider {
+    EnvCredentialProvider::with_prefix("MWS")
+  }
+
+  pub fn with_prefix<P: Into<String>>(prefix: P) -> EnvCredentialProvider {
+    EnvCredentialProvider {
+      prefix: prefix.into(),
+    }
+  }
+
+  fn var(&self, region_id: &str, name: &str) -> MwsResult<String> {
+    let regional = format!("{}_{}_{}", self.prefix, region_id.to_uppercase(), name);
+    let global = format!("{}_{}", self.prefix, name);
+    env::var(&regional)
+      .or_else(|_| env::var(&global))
+      .map_err(|_| {
+        MwsError::MissingCredentials(format!("neither {} nor {} is set", regional, global))
+      })
+  }
+}
+
+impl Default for EnvCredentialProvider {
+  fn default() -> EnvCredentialProvider {
+    EnvCredentialProvider::new()
+  }
+}
+
+impl CredentialProvider for EnvCredentialProvider {
+  fn developer_credentials(&self, region_id: &str) -> MwsResult<DeveloperCredentials> {
+    Ok(DeveloperCredentials {
+      aws_access_key_id: self.var(region_id, "AWS_ACCESS_KEY_ID")?,
+      secret_key: self.var(region_id, "SECRET_KEY")?.into(),
+    })
+  }
+
+  fn mws_auth_token(&self, seller_id: &str, _region_id: &str) -> MwsResult<Option<Secret>> {
+    Ok(
+      env::var(format!("{}_AUTH_TOKEN_{}", self.prefix, seller_id))
+        .ok()
+        .map(Secret::from),
+    )
+  }
+}
+
+/// An authorization granted by a seller, see `FileCredentialProvider`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SellerAuthorization {
+  pub seller_id: String,
+  pub region_id: String,
+  pub mws_auth_token: Option<Secret>,
+}
+
+/// Credentials loaded from a JSON file
+///
+/// ```json
+/// {
+///   "developers": {
+///     "na": { "aws_access_key_id": "...", "secret_key": "..." },
+///     "eu": { "aws_access_key_id": "...", "secret_key": "..." }
+///   },
+///   "sellers": [
+///     { "seller_id": "A1B2C3", "region_id": "na", "mws_auth_token": "amzn.mws.00000000-..." }
+///   ]
+/// }
+/// ```
+///
+/// Sellers which are not listed are rejected with `MwsError::MissingCredentials`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FileCredentialProvider {
+  pub developers: HashMap<String, DeveloperCredentials>,
+  #[serde(default)]
+  pub sellers: Vec<SellerAuthorization>,
+}
+
+impl FileCredentialProvider {
+  pub fn load<P: AsRef<Path>>(path: P) -> MwsResult<FileCredentialProvider> {
+    let file = File::open(path)?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|err| MwsError::Msg(err.to_string()))
+  }
+
+  pub fn parse(json: &str) -> MwsResult<FileCredentialProvider> {
+    serde_json::from_str(json).map_err(|err| MwsError::Msg(err.to_string()))
+  }
+}
+
+impl CredentialProvider for FileCredentialProvider {
+  fn developer_credentials(&self, region_id: &str) -> MwsResult<DeveloperCredentials> {
+    self.developers.get(region_id).cloned().ok_or_else(|| {
+      MwsError::MissingCredentials(format!("no developer keys for region '{}'", region_id))
+    })
+  }
+
+  fn mws_auth_token(&self, seller_id: &str, region_id: &str) -> MwsResult<Option<Secret>> {
+    self
+      .sellers
+      .iter()
+      .find(|s| s.seller_id == seller_id && s.region_id == region_id)
+      .map(|s| s.mws_auth_token.clone())
+      .ok_or_else(|| {
+        MwsError::MissingCredentials(format!(
+          "seller '{}' is not authorized in region '{}'",
+          seller_id, region_id
+        ))
+      })
+  }
+}
+
+/// Resolves credentials with callbacks, e.g. to read auth tokens from a database
+pub struct CallbackCredentialProvider<D, T> {
+  developer_credentials: D,
+  mws_auth_token: T,
+}
+
+impl<D, T> CallbackCredentialProvider<D, T>
+where
+  D: Fn(&str) -> MwsResult<DeveloperCredentials> + Send + Sync,
+  T: Fn(&str, &str) -> MwsResult<Option<Secret>> + Send + Sync,
+{
+  /// `developer_credentials` is called with the region id,
+  /// `mws_auth_token` with the seller id and the region id.
+  pub fn new(developer_credentials: D, mws_auth_token: T) -> CallbackCredentialProvider<D, T> {
+    CallbackCredentialProvider {
+      developer_credentials: developer_credentials,
+      mws_auth_token: mws_auth_token,
+    }
+  }
+}
+
+impl<D, T> CredentialProvider for CallbackCredentialProvider<D, T>
+where
+  D: Fn(&str) -> MwsResult<DeveloperCredentials> + Send + Sync,
+  T: Fn(&str, &str) -> MwsResult<Option<Secret>> + Send + Sync,
+{
+  fn developer_credentials(&self, region_id: &str) -> MwsResult<DeveloperCredentials> {
+    (self.developer_credentials)(region_id)
+  }
+
+  fn mws_auth_token(&self, seller_id: &str, region_id: &str) -> MwsResult<Option<Secret>> {
+    (self.mws_auth_token)(seller_id, region_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_file_provider() {
+    let provider = FileCredentialProvider::parse(
+      r#"{
+        "developers": {
+          "na": { "aws_access_key_id": "NA_KEY", "secret_key": "NA_SECRET" }
+        },
+        "sellers": [
+          { "seller_id": "SELLER", "region_id": "na", "mws_auth_token": "TOKEN" },
+          { "seller_id": "DEVELOPER", "region_id": "na", "mws_auth_token": null }
+        ]
+      }"#,
+    )
+    .expect("parse");
+    assert_eq!(
+      provider.developer_credentials("na").expect("na"),
+      DeveloperCredentials {
+        aws_access_key_id: "NA_KEY".to_string(),
+        secret_key: "NA_SECRET".into(),
+      }
+    );
+    assert_eq!(
+      provider.mws_auth_token("SELLER", "na").expect("SELLER"),
+      Some("TOKEN".into())
+    );
+    assert_eq!(
+      provider
+        .mws_auth_token("DEVELOPER", "na")
+        .expect("DEVELOPER"),
+      None
+    );
+    assert!(provider.developer_credentials("eu").is_err());
+    assert!(provider.mws_auth_token("SELLER", "eu").is_err());
+  }
+
+  #[test]
+  fn test_env_provider() {
+    env::set_var("MWS_TEST_AWS_ACCESS_KEY_ID", "KEY");
+    env::set_var("MWS_TEST_SECRET_KEY", "SECRET");
+    env::set_var("MWS_TEST_EU_SECRET_KEY", "EU_SECRET");
+    env::set_var("MWS_TEST_AUTH_TOKEN_SELLER", "TOKEN");
+    let provider = EnvCredentialProvider::with_prefix("MWS_TEST");
+    assert_eq!(
+      provider
+        .developer_credentials("na")
+        .expect("na")
+        .secret_key
+        .expose(),
+      "SECRET"
+    );
+    let eu = provider.developer_credentials("eu").expect("eu");
+    assert_eq!(eu.aws_access_key_id, "KEY");
+    assert_eq!(eu.secret_key.expose(), "EU_SECRET");
+    assert_eq!(
+      provider.mws_auth_token("SELLER", "na").expect("SELLER"),
+      Some("TOKEN".into())
+    );
+    assert_eq!(provider.mws_auth_token("OTHER", "na").expect("OTHER"), None);
+  }
+}
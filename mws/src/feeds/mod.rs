@@ -2,8 +2,10 @@
 //!
 //! [Documentation](http://docs.developer.amazonservices.com/en_CA/feeds/Feeds_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
 use client::{Client, ContentType, Method};
+use futures::Future;
 use result::MwsResult;
 use std::io::{Read, Write};
 use xmlhelper::encode;
@@ -173,6 +175,31 @@ where
     .map_err(Into::into)
 }
 
+/// Asynchronous version of [`SubmitFeed`](fn.SubmitFeed.html).
+#[allow(non_snake_case)]
+pub fn SubmitFeedAsync(
+  client: &AsyncClient,
+  parameters: SubmitFeedParameters,
+  content: Vec<u8>,
+  content_md5: String,
+  content_type: String,
+) -> MwsFuture<SubmitFeedResponse> {
+  Box::new(
+    client
+      .request_xml_with_body(
+        Method::Post,
+        PATH,
+        VERSION,
+        "SubmitFeed",
+        parameters,
+        content,
+        content_md5,
+        ContentType(content_type.parse().unwrap()),
+      )
+      .map(|e: SubmitFeedEnvelope| e.into_inner()),
+  )
+}
+
 #[allow(non_snake_case)]
 pub fn GetFeedSubmissionResult<W: Write>(
   client: &Client,
@@ -191,6 +218,28 @@ pub fn GetFeedSubmissionResult<W: Write>(
   Ok(size)
 }
 
+/// Asynchronous version of [`GetFeedSubmissionResult`](fn.GetFeedSubmissionResult.html).
+///
+/// Resolves to the processing report body.
+#[allow(non_snake_case)]
+pub fn GetFeedSubmissionResultAsync(
+  client: &AsyncClient,
+  FeedSubmissionId: String,
+) -> MwsFuture<Vec<u8>> {
+  let params = vec![("FeedSubmissionId".to_string(), FeedSubmissionId)];
+  Box::new(
+    client
+      .request(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetFeedSubmissionResult",
+        params,
+      )
+      .and_then(|resp| resp.into_bytes()),
+  )
+}
+
 /// Parameters for `GetFeedSubmissionList`
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -235,6 +284,13 @@ response_envelope_type!(
   "GetFeedSubmissionListByNextTokenResult"
 );
 
+next_token_response!(
+  GetFeedSubmissionListResponse,
+  FeedSubmissionInfo: FeedSubmissionInfo,
+  GetFeedSubmissionListByNextToken,
+  GetFeedSubmissionListByNextTokenAsync
+);
+
 #[allow(non_snake_case)]
 pub fn GetFeedSubmissionList(
   client: &Client,
@@ -252,6 +308,25 @@ pub fn GetFeedSubmissionList(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetFeedSubmissionList`](fn.GetFeedSubmissionList.html).
+#[allow(non_snake_case)]
+pub fn GetFeedSubmissionListAsync(
+  client: &AsyncClient,
+  parameters: GetFeedSubmissionListParameters,
+) -> MwsFuture<GetFeedSubmissionListResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetFeedSubmissionList",
+        parameters,
+      )
+      .map(|e: GetFeedSubmissionListEnvelope| e.into_inner()),
+  )
+}
+
 #[allow(non_snake_case)]
 pub fn GetFeedSubmissionListByNextToken(
   client: &Client,
@@ -269,3 +344,23 @@ pub fn GetFeedSubmissionListByNextToken(
     .map(|e: GetFeedSubmissionListByNextTokenEnvelope| e.into_inner())
     .map_err(|err| err.into())
 }
+
+/// Asynchronous version of [`GetFeedSubmissionListByNextToken`](fn.GetFeedSubmissionListByNextToken.html).
+#[allow(non_snake_case)]
+pub fn GetFeedSubmissionListByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<GetFeedSubmissionListResponse> {
+  let params = vec![("NextToken".to_string(), next_token)];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetFeedSubmissionListByNextToken",
+        params,
+      )
+      .map(|e: GetFeedSubmissionListByNextTokenEnvelope| e.into_inner()),
+  )
+}
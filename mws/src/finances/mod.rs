@@ -0,0 +1,490 @@
+//! Amazon MWS Finances API - Version 2015-05-01
+//!
+//! [Reference](http://docs.developer.amazonservices.com/en_US/finances/Finances_Overview.html)
+
+use async_client::{AsyncClient, MwsFuture};
+use chrono::{DateTime, Utc};
+use client::Client;
+mod types;
+pub use self::types::*;
+use result::MwsResult;
+
+pub(crate) const PATH: &'static str = "/Finances/2015-05-01";
+pub(crate) const VERSION: &'static str = "2015-05-01";
+
+get_service_status!(PATH, VERSION);
+
+/// Parameters for `ListFinancialEventGroups`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct ListFinancialEventGroupsParameters {
+  // Required API Parameters
+  pub FinancialEventGroupStartedAfter: Option<DateTime<Utc>>,
+
+  // Optional API Parameters
+  pub FinancialEventGroupStartedBefore: Option<DateTime<Utc>>,
+  /// 1 to 100, the default is 100.
+  pub MaxResultsPerPage: Option<i32>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ListFinancialEventGroupsResponse {
+  pub FinancialEventGroupList: Vec<FinancialEventGroup>,
+  pub NextToken: Option<String>,
+}
+
+response_envelope_type!(
+  ListFinancialEventGroupsEnvelope<ListFinancialEventGroupsResponse>,
+  "ListFinancialEventGroupsResponse",
+  "ListFinancialEventGroupsResult"
+);
+
+response_envelope_type!(
+  ListFinancialEventGroupsByNextTokenEnvelope<ListFinancialEventGroupsResponse>,
+  "ListFinancialEventGroupsByNextTokenResponse",
+  "ListFinancialEventGroupsByNextTokenResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListFinancialEventGroups",
+  ListFinancialEventGroupsParameters => ListFinancialEventGroupsEnvelope<ListFinancialEventGroupsResponse>,
+  ByNextToken: ListFinancialEventGroupsByNextTokenParameters => ListFinancialEventGroupsByNextTokenEnvelope
+);
+
+next_token_response!(
+  ListFinancialEventGroupsResponse,
+  FinancialEventGroupList: FinancialEventGroup,
+  ListFinancialEventGroupsByNextToken,
+  ListFinancialEventGroupsByNextTokenAsync
+);
+
+/// Returns financial event groups for a given date range.
+///
+/// A financial event group is a settlement period, its events are returned by
+/// `ListFinancialEvents` with the `FinancialEventGroupId`.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/finances/Finances_ListFinancialEventGroups.html)
+#[allow(non_snake_case)]
+pub fn ListFinancialEventGroups(
+  client: &Client,
+  parameters: ListFinancialEventGroupsParameters,
+) -> MwsResult<ListFinancialEventGroupsResponse> {
+  client.execute(parameters)
+}
+
+/// Asynchronous version of [`ListFinancialEventGroups`](fn.ListFinancialEventGroups.html).
+#[allow(non_snake_case)]
+pub fn ListFinancialEventGroupsAsync(
+  client: &AsyncClient,
+  parameters: ListFinancialEventGroupsParameters,
+) -> MwsFuture<ListFinancialEventGroupsResponse> {
+  client.execute(parameters)
+}
+
+/// Returns the next page of financial event groups using the NextToken parameter.
+#[allow(non_snake_case)]
+pub fn ListFinancialEventGroupsByNextToken(
+  client: &Client,
+  next_token: String,
+) -> MwsResult<ListFinancialEventGroupsResponse> {
+  client.execute(ListFinancialEventGroupsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`ListFinancialEventGroupsByNextToken`](fn.ListFinancialEventGroupsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListFinancialEventGroupsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListFinancialEventGroupsResponse> {
+  client.execute(ListFinancialEventGroupsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Parameters for `ListFinancialEvents`
+///
+/// Specify exactly one of `AmazonOrderId`, `FinancialEventGroupId` or `PostedAfter`.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct ListFinancialEventsParameters {
+  /// 1 to 100, the default is 100.
+  pub MaxResultsPerPage: Option<i32>,
+  pub AmazonOrderId: Option<String>,
+  pub FinancialEventGroupId: Option<String>,
+  pub PostedAfter: Option<DateTime<Utc>>,
+  pub PostedBefore: Option<DateTime<Utc>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ListFinancialEventsResponse {
+  pub FinancialEvents: FinancialEvents,
+  pub NextToken: Option<String>,
+}
+
+response_envelope_type!(
+  ListFinancialEventsEnvelope<ListFinancialEventsResponse>,
+  "ListFinancialEventsResponse",
+  "ListFinancialEventsResult"
+);
+
+response_envelope_type!(
+  ListFinancialEventsByNextTokenEnvelope<ListFinancialEventsResponse>,
+  "ListFinancialEventsByNextTokenResponse",
+  "ListFinancialEventsByNextTokenResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListFinancialEvents",
+  ListFinancialEventsParameters => ListFinancialEventsEnvelope<ListFinancialEventsResponse>,
+  ByNextToken: ListFinancialEventsByNextTokenParameters => ListFinancialEventsByNextTokenEnvelope
+);
+
+/// Every page is a single item, combine them with `FinancialEvents::append`.
+impl ::pagination::NextTokenResponse for ListFinancialEventsResponse {
+  type Item = FinancialEvents;
+
+  fn next_token(&self) -> Option<&str> {
+    self.NextToken.as_ref().map(String::as_str)
+  }
+
+  fn into_items(self) -> Vec<FinancialEvents> {
+    vec![self.FinancialEvents]
+  }
+
+  fn by_next_token(client: &Client, next_token: String) -> MwsResult<Self> {
+    ListFinancialEventsByNextToken(client, next_token)
+  }
+
+  fn by_next_token_async(client: &AsyncClient, next_token: String) -> MwsFuture<Self> {
+    ListFinancialEventsByNextTokenAsync(client, next_token)
+  }
+}
+
+/// Returns financial events for a given order, financial event group, or date range.
+///
+/// Orders are returned about 48 hours after they are shipped, refunds and other events
+/// may take longer.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/finances/Finances_ListFinancialEvents.html)
+#[allow(non_snake_case)]
+pub fn ListFinancialEvents(
+  client: &Client,
+  parameters: ListFinancialEventsParameters,
+) -> MwsResult<ListFinancialEventsResponse> {
+  client.execute(parameters)
+}
+
+/// Asynchronous version of [`ListFinancialEvents`](fn.ListFinancialEvents.html).
+#[allow(non_snake_case)]
+pub fn ListFinancialEventsAsync(
+  client: &AsyncClient,
+  parameters: ListFinancialEventsParameters,
+) -> MwsFuture<ListFinancialEventsResponse> {
+  client.execute(parameters)
+}
+
+/// Returns the next page of financial events using the NextToken parameter.
+#[allow(non_snake_case)]
+pub fn ListFinancialEventsByNextToken(
+  client: &Client,
+  next_token: String,
+) -> MwsResult<ListFinancialEventsResponse> {
+  client.execute(ListFinancialEventsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`ListFinancialEventsByNextToken`](fn.ListFinancialEventsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListFinancialEventsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListFinancialEventsResponse> {
+  client.execute(ListFinancialEventsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_decode_list_financial_event_groups_response() {
+    test_decode_envelope!(
+      ListFinancialEventGroupsEnvelope,
+      r#"
+      <ListFinancialEventGroupsResponse xmlns="http://mws.amazonservices.com/Finances/2015-05-01">
+        <ListFinancialEventGroupsResult>
+          <FinancialEventGroupList>
+            <FinancialEventGroup>
+              <FinancialEventGroupId>22YgYW55IGNhcm5hbCBwbGVhEXAMPLE</FinancialEventGroupId>
+              <ProcessingStatus>Closed</ProcessingStatus>
+              <FundTransferStatus>Successful</FundTransferStatus>
+              <OriginalTotal>
+                <CurrencyCode>USD</CurrencyCode>
+                <CurrencyAmount>19.00</CurrencyAmount>
+              </OriginalTotal>
+              <FundTransferDate>2014-09-09T01:30:00.000-06:00</FundTransferDate>
+              <TraceId>128311029381HSADJEXAMPLE</TraceId>
+              <AccountTail>1212</AccountTail>
+              <FinancialEventGroupStart>2014-09-01T01:30:00.000-06:00</FinancialEventGroupStart>
+              <FinancialEventGroupEnd>2014-09-09T01:30:00.000-06:00</FinancialEventGroupEnd>
+            </FinancialEventGroup>
+          </FinancialEventGroupList>
+          <NextToken>2YgYW55IGNhcm5hbCBwbGVhEXAMPLE</NextToken>
+        </ListFinancialEventGroupsResult>
+        <ResponseMetadata>
+          <RequestId>1105b931-6f1c-4480-8e97-f3b467840a9e</RequestId>
+        </ResponseMetadata>
+      </ListFinancialEventGroupsResponse>
+      "#,
+      ListFinancialEventGroupsResponse {
+        FinancialEventGroupList: vec![FinancialEventGroup {
+          FinancialEventGroupId: "22YgYW55IGNhcm5hbCBwbGVhEXAMPLE".to_string(),
+          ProcessingStatus: Some(ProcessingStatus::Closed),
+          FundTransferStatus: Some("Successful".to_string()),
+          OriginalTotal: Some(Currency {
+            CurrencyCode: "USD".to_string(),
+            CurrencyAmount: "19.00".to_string(),
+          }),
+          ConvertedTotal: None,
+          FundTransferDate: Some("2014-09-09T07:30:00Z".parse().unwrap()),
+          TraceId: Some("128311029381HSADJEXAMPLE".to_string()),
+          AccountTail: Some("1212".to_string()),
+          BeginningBalance: None,
+          FinancialEventGroupStart: Some("2014-09-01T07:30:00Z".parse().unwrap()),
+          FinancialEventGroupEnd: Some("2014-09-09T07:30:00Z".parse().unwrap()),
+        }],
+        NextToken: Some("2YgYW55IGNhcm5hbCBwbGVhEXAMPLE".to_string()),
+      }
+    );
+  }
+
+  #[test]
+  fn test_decode_list_financial_events_response() {
+    let usd = |amount: &str| {
+      Some(Currency {
+        CurrencyCode: "USD".to_string(),
+        CurrencyAmount: amount.to_string(),
+      })
+    };
+    test_decode_envelope!(
+      ListFinancialEventsEnvelope,
+      r#"
+      <ListFinancialEventsResponse xmlns="http://mws.amazonservices.com/Finances/2015-05-01">
+        <ListFinancialEventsResult>
+          <FinancialEvents>
+            <ShipmentEventList>
+              <ShipmentEvent>
+                <AmazonOrderId>333-7777777-7777777</AmazonOrderId>
+                <SellerOrderId>333-7777777-7777777</SellerOrderId>
+                <MarketplaceName>Amazon.com</MarketplaceName>
+                <PostedDate>2015-02-13T09:39:17Z</PostedDate>
+                <ShipmentItemList>
+                  <ShipmentItem>
+                    <SellerSKU>sku</SellerSKU>
+                    <OrderItemId>6882857EXAMPLE</OrderItemId>
+                    <QuantityShipped>1</QuantityShipped>
+                    <ItemChargeList>
+                      <ChargeComponent>
+                        <ChargeType>Principal</ChargeType>
+                        <ChargeAmount>
+                          <CurrencyCode>USD</CurrencyCode>
+                          <CurrencyAmount>10.00</CurrencyAmount>
+                        </ChargeAmount>
+                      </ChargeComponent>
+                    </ItemChargeList>
+                    <ItemFeeList>
+                      <FeeComponent>
+                        <FeeType>Commission</FeeType>
+                        <FeeAmount>
+                          <CurrencyCode>USD</CurrencyCode>
+                          <CurrencyAmount>-1.50</CurrencyAmount>
+                        </FeeAmount>
+                      </FeeComponent>
+                    </ItemFeeList>
+                    <ItemTaxWithheldList>
+                      <TaxWithheldComponent>
+                        <TaxCollectionModel>MarketplaceFacilitator</TaxCollectionModel>
+                        <TaxesWithheld>
+                          <ChargeComponent>
+                            <ChargeType>MarketplaceFacilitatorTax-Principal</ChargeType>
+                            <ChargeAmount>
+                              <CurrencyCode>USD</CurrencyCode>
+                              <CurrencyAmount>-0.80</CurrencyAmount>
+                            </ChargeAmount>
+                          </ChargeComponent>
+                        </TaxesWithheld>
+                      </TaxWithheldComponent>
+                    </ItemTaxWithheldList>
+                  </ShipmentItem>
+                </ShipmentItemList>
+              </ShipmentEvent>
+            </ShipmentEventList>
+            <RefundEventList>
+              <ShipmentEvent>
+                <AmazonOrderId>333-1234567-1234567</AmazonOrderId>
+                <PostedDate>2015-02-20T09:39:17Z</PostedDate>
+                <ShipmentItemAdjustmentList>
+                  <ShipmentItem>
+                    <SellerSKU>sku</SellerSKU>
+                    <OrderItemId>6882857EXAMPLE</OrderItemId>
+                    <OrderAdjustmentItemId>29283EXAMPLE</OrderAdjustmentItemId>
+                    <QuantityShipped>1</QuantityShipped>
+                    <ItemChargeAdjustmentList>
+                      <ChargeComponent>
+                        <ChargeType>Principal</ChargeType>
+                        <ChargeAmount>
+                          <CurrencyCode>USD</CurrencyCode>
+                          <CurrencyAmount>-10.00</CurrencyAmount>
+                        </ChargeAmount>
+                      </ChargeComponent>
+                    </ItemChargeAdjustmentList>
+                  </ShipmentItem>
+                </ShipmentItemAdjustmentList>
+              </ShipmentEvent>
+            </RefundEventList>
+            <RetrochargeEventList>
+              <RetrochargeEvent>
+                <RetrochargeEventType>Retrocharge</RetrochargeEventType>
+                <AmazonOrderId>444-5555555-5555555</AmazonOrderId>
+                <PostedDate>2015-02-21T09:39:17Z</PostedDate>
+                <BaseTax>
+                  <CurrencyCode>USD</CurrencyCode>
+                  <CurrencyAmount>1.00</CurrencyAmount>
+                </BaseTax>
+                <MarketplaceName>Amazon.com</MarketplaceName>
+              </RetrochargeEvent>
+            </RetrochargeEventList>
+            <ServiceFeeEventList>
+              <ServiceFeeEvent>
+                <FeeReason>FBAInboundTransportationFee</FeeReason>
+                <FeeList>
+                  <FeeComponent>
+                    <FeeType>FBAInboundTransportationFee</FeeType>
+                    <FeeAmount>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <CurrencyAmount>-12.00</CurrencyAmount>
+                    </FeeAmount>
+                  </FeeComponent>
+                </FeeList>
+              </ServiceFeeEvent>
+            </ServiceFeeEventList>
+            <AdjustmentEventList>
+              <AdjustmentEvent>
+                <AdjustmentType>FBAInventoryReimbursement</AdjustmentType>
+                <AdjustmentAmount>
+                  <CurrencyCode>USD</CurrencyCode>
+                  <CurrencyAmount>5.00</CurrencyAmount>
+                </AdjustmentAmount>
+                <AdjustmentItemList>
+                  <AdjustmentItem>
+                    <Quantity>1</Quantity>
+                    <PerUnitAmount>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <CurrencyAmount>5.00</CurrencyAmount>
+                    </PerUnitAmount>
+                    <SellerSKU>sku</SellerSKU>
+                  </AdjustmentItem>
+                </AdjustmentItemList>
+                <PostedDate>2015-02-22T09:39:17Z</PostedDate>
+              </AdjustmentEvent>
+            </AdjustmentEventList>
+            <PayWithAmazonEventList/>
+          </FinancialEvents>
+        </ListFinancialEventsResult>
+        <ResponseMetadata>
+          <RequestId>1105b931-6f1c-4480-8e97-f3b467840a9e</RequestId>
+        </ResponseMetadata>
+      </ListFinancialEventsResponse>
+      "#,
+      ListFinancialEventsResponse {
+        FinancialEvents: FinancialEvents {
+          ShipmentEventList: vec![ShipmentEvent {
+            AmazonOrderId: "333-7777777-7777777".to_string(),
+            SellerOrderId: Some("333-7777777-7777777".to_string()),
+            MarketplaceName: Some("Amazon.com".to_string()),
+            PostedDate: Some("2015-02-13T09:39:17Z".parse().unwrap()),
+            ShipmentItemList: vec![ShipmentItem {
+              SellerSKU: "sku".to_string(),
+              OrderItemId: "6882857EXAMPLE".to_string(),
+              QuantityShipped: 1,
+              ItemChargeList: vec![ChargeComponent {
+                ChargeType: "Principal".to_string(),
+                ChargeAmount: usd("10.00"),
+              }],
+              ItemFeeList: vec![FeeComponent {
+                FeeType: "Commission".to_string(),
+                FeeAmount: usd("-1.50"),
+              }],
+              ItemTaxWithheldList: vec![TaxWithheldComponent {
+                TaxCollectionModel: "MarketplaceFacilitator".to_string(),
+                TaxesWithheld: vec![ChargeComponent {
+                  ChargeType: "MarketplaceFacilitatorTax-Principal".to_string(),
+                  ChargeAmount: usd("-0.80"),
+                }],
+              }],
+              ..Default::default()
+            }],
+            ..Default::default()
+          }],
+          RefundEventList: vec![ShipmentEvent {
+            AmazonOrderId: "333-1234567-1234567".to_string(),
+            PostedDate: Some("2015-02-20T09:39:17Z".parse().unwrap()),
+            ShipmentItemAdjustmentList: vec![ShipmentItem {
+              SellerSKU: "sku".to_string(),
+              OrderItemId: "6882857EXAMPLE".to_string(),
+              OrderAdjustmentItemId: Some("29283EXAMPLE".to_string()),
+              QuantityShipped: 1,
+              ItemChargeAdjustmentList: vec![ChargeComponent {
+                ChargeType: "Principal".to_string(),
+                ChargeAmount: usd("-10.00"),
+              }],
+              ..Default::default()
+            }],
+            ..Default::default()
+          }],
+          RetrochargeEventList: vec![RetrochargeEvent {
+            RetrochargeEventType: "Retrocharge".to_string(),
+            AmazonOrderId: "444-5555555-5555555".to_string(),
+            PostedDate: Some("2015-02-21T09:39:17Z".parse().unwrap()),
+            BaseTax: usd("1.00"),
+            ShippingTax: None,
+            MarketplaceName: Some("Amazon.com".to_string()),
+            RetrochargeTaxWithheldComponentList: vec![],
+          }],
+          ServiceFeeEventList: vec![ServiceFeeEvent {
+            FeeReason: Some("FBAInboundTransportationFee".to_string()),
+            FeeList: vec![FeeComponent {
+              FeeType: "FBAInboundTransportationFee".to_string(),
+              FeeAmount: usd("-12.00"),
+            }],
+            ..Default::default()
+          }],
+          AdjustmentEventList: vec![AdjustmentEvent {
+            AdjustmentType: "FBAInventoryReimbursement".to_string(),
+            AdjustmentAmount: usd("5.00"),
+            AdjustmentItemList: vec![AdjustmentItem {
+              Quantity: Some("1".to_string()),
+              PerUnitAmount: usd("5.00"),
+              SellerSKU: Some("sku".to_string()),
+              ..Default::default()
+            }],
+            PostedDate: Some("2015-02-22T09:39:17Z".parse().unwrap()),
+          }],
+          ..Default::default()
+        },
+        NextToken: None,
+      }
+    );
+  }
+}
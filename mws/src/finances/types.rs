@@ -0,0 +1,240 @@
+use chrono::{DateTime, Utc};
+
+/// A currency type and amount
+///
+/// Unlike the other sections, the amount element is named `CurrencyAmount`.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct Currency {
+  /// The three-digit currency code in ISO 4217 format.
+  pub CurrencyCode: String,
+  /// The monetary value, e.g. `-1.50`.
+  pub CurrencyAmount: String,
+}
+
+str_enum! {
+  /// The processing status of a financial event group
+  pub enum ProcessingStatus {
+    Open,
+    Closed,
+  }
+}
+
+/// Information related to a financial event group
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct FinancialEventGroup {
+  pub FinancialEventGroupId: String,
+  /// `Open` if the group is still receiving financial events.
+  pub ProcessingStatus: Option<ProcessingStatus>,
+  /// The status of the fund transfer, e.g. `Succeeded`.
+  pub FundTransferStatus: Option<String>,
+  /// The total amount in the currency of the marketplace.
+  pub OriginalTotal: Option<Currency>,
+  /// The total amount in the currency of the bank account.
+  pub ConvertedTotal: Option<Currency>,
+  pub FundTransferDate: Option<DateTime<Utc>>,
+  /// The trace identifier used by sellers to look up transactions externally.
+  pub TraceId: Option<String>,
+  /// The account tail of the payment instrument.
+  pub AccountTail: Option<String>,
+  /// The balance at the beginning of the settlement period.
+  pub BeginningBalance: Option<Currency>,
+  pub FinancialEventGroupStart: Option<DateTime<Utc>>,
+  pub FinancialEventGroupEnd: Option<DateTime<Utc>>,
+}
+
+/// A charge on a shipment item, e.g. `Principal` or `Tax`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ChargeComponent {
+  pub ChargeType: String,
+  pub ChargeAmount: Option<Currency>,
+}
+
+/// A fee, e.g. `Commission` or `FBAPerUnitFulfillmentFee`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct FeeComponent {
+  pub FeeType: String,
+  pub FeeAmount: Option<Currency>,
+}
+
+/// A payment made directly to the seller, e.g. Cash On Delivery
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct DirectPayment {
+  pub DirectPaymentType: String,
+  pub DirectPaymentAmount: Option<Currency>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct Promotion {
+  pub PromotionType: String,
+  pub PromotionId: String,
+  pub PromotionAmount: Option<Currency>,
+}
+
+/// Taxes withheld by the marketplace under a tax collection model
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct TaxWithheldComponent {
+  /// `MarketplaceFacilitator` or `Standard`
+  pub TaxCollectionModel: String,
+  pub TaxesWithheld: Vec<ChargeComponent>,
+}
+
+/// An item of a shipment, refund, guarantee claim or chargeback
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ShipmentItem {
+  pub SellerSKU: String,
+  pub OrderItemId: String,
+  /// Identifies the item of an adjustment, e.g. a refund.
+  pub OrderAdjustmentItemId: Option<String>,
+  pub QuantityShipped: i32,
+  pub ItemChargeList: Vec<ChargeComponent>,
+  pub ItemChargeAdjustmentList: Vec<ChargeComponent>,
+  pub ItemFeeList: Vec<FeeComponent>,
+  pub ItemFeeAdjustmentList: Vec<FeeComponent>,
+  pub ItemTaxWithheldList: Vec<TaxWithheldComponent>,
+  pub PromotionList: Vec<Promotion>,
+  pub PromotionAdjustmentList: Vec<Promotion>,
+  /// The cost of Amazon Points granted, Japan only.
+  pub CostOfPointsGranted: Option<Currency>,
+  /// The cost of Amazon Points returned, Japan only.
+  pub CostOfPointsReturned: Option<Currency>,
+}
+
+/// A shipment, refund, guarantee claim or chargeback
+///
+/// Items of a shipment are in `ShipmentItemList`, items of the other events in
+/// `ShipmentItemAdjustmentList`.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ShipmentEvent {
+  pub AmazonOrderId: String,
+  pub SellerOrderId: Option<String>,
+  /// The name of the marketplace where the event occurred, e.g. `Amazon.com`.
+  pub MarketplaceName: Option<String>,
+  pub OrderChargeList: Vec<ChargeComponent>,
+  pub OrderChargeAdjustmentList: Vec<ChargeComponent>,
+  pub ShipmentFeeList: Vec<FeeComponent>,
+  pub ShipmentFeeAdjustmentList: Vec<FeeComponent>,
+  pub OrderFeeList: Vec<FeeComponent>,
+  pub OrderFeeAdjustmentList: Vec<FeeComponent>,
+  pub DirectPaymentList: Vec<DirectPayment>,
+  pub PostedDate: Option<DateTime<Utc>>,
+  pub ShipmentItemList: Vec<ShipmentItem>,
+  pub ShipmentItemAdjustmentList: Vec<ShipmentItem>,
+}
+
+/// A refund, which has the same structure as a `ShipmentEvent`
+pub type RefundEvent = ShipmentEvent;
+
+/// An A-to-z Guarantee claim, which has the same structure as a `ShipmentEvent`
+pub type GuaranteeClaimEvent = ShipmentEvent;
+
+/// A chargeback, which has the same structure as a `ShipmentEvent`
+pub type ChargebackEvent = ShipmentEvent;
+
+/// A fee charged by Amazon which is not related to a shipment, e.g. an FBA inbound fee
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ServiceFeeEvent {
+  pub AmazonOrderId: Option<String>,
+  pub FeeReason: Option<String>,
+  pub FeeList: Vec<FeeComponent>,
+  pub SellerSKU: Option<String>,
+  pub FnSKU: Option<String>,
+  pub FeeDescription: Option<String>,
+  pub ASIN: Option<String>,
+}
+
+/// An item of an adjustment, e.g. a reimbursed unit
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct AdjustmentItem {
+  /// The number of units, returned as text by MWS.
+  pub Quantity: Option<String>,
+  pub PerUnitAmount: Option<Currency>,
+  pub TotalAmount: Option<Currency>,
+  pub SellerSKU: Option<String>,
+  pub FnSKU: Option<String>,
+  pub ProductDescription: Option<String>,
+  pub ASIN: Option<String>,
+}
+
+/// An adjustment to the seller's account, e.g. `FBAInventoryReimbursement`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct AdjustmentEvent {
+  pub AdjustmentType: String,
+  pub AdjustmentAmount: Option<Currency>,
+  pub AdjustmentItemList: Vec<AdjustmentItem>,
+  pub PostedDate: Option<DateTime<Utc>>,
+}
+
+/// A charge or refund of tax on shipping, e.g. after an order was cancelled
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct RetrochargeEvent {
+  /// `Retrocharge` or `RetrochargeReversal`
+  pub RetrochargeEventType: String,
+  pub AmazonOrderId: String,
+  pub PostedDate: Option<DateTime<Utc>>,
+  pub BaseTax: Option<Currency>,
+  pub ShippingTax: Option<Currency>,
+  pub MarketplaceName: Option<String>,
+  pub RetrochargeTaxWithheldComponentList: Vec<TaxWithheldComponent>,
+}
+
+/// Financial events of a page of `ListFinancialEvents`
+///
+/// Event types without a field here are skipped.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct FinancialEvents {
+  pub ShipmentEventList: Vec<ShipmentEvent>,
+  pub RefundEventList: Vec<RefundEvent>,
+  pub GuaranteeClaimEventList: Vec<GuaranteeClaimEvent>,
+  pub ChargebackEventList: Vec<ChargebackEvent>,
+  pub RetrochargeEventList: Vec<RetrochargeEvent>,
+  pub ServiceFeeEventList: Vec<ServiceFeeEvent>,
+  pub AdjustmentEventList: Vec<AdjustmentEvent>,
+}
+
+impl FinancialEvents {
+  /// Returns `true` if there are no events.
+  pub fn is_empty(&self) -> bool {
+    self.ShipmentEventList.is_empty()
+      && self.RefundEventList.is_empty()
+      && self.GuaranteeClaimEventList.is_empty()
+      && self.ChargebackEventList.is_empty()
+      && self.RetrochargeEventList.is_empty()
+      && self.ServiceFeeEventList.is_empty()
+      && self.AdjustmentEventList.is_empty()
+  }
+
+  /// Moves the events of `other` to the end of the lists, e.g. to collect all pages.
+  pub fn append(&mut self, mut other: FinancialEvents) {
+    self.ShipmentEventList.append(&mut other.ShipmentEventList);
+    self.RefundEventList.append(&mut other.RefundEventList);
+    self
+      .GuaranteeClaimEventList
+      .append(&mut other.GuaranteeClaimEventList);
+    self
+      .ChargebackEventList
+      .append(&mut other.ChargebackEventList);
+    self
+      .RetrochargeEventList
+      .append(&mut other.RetrochargeEventList);
+    self
+      .ServiceFeeEventList
+      .append(&mut other.ServiceFeeEventList);
+    self
+      .AdjustmentEventList
+      .append(&mut other.AdjustmentEventList);
+  }
+}
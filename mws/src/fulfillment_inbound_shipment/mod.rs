@@ -2,15 +2,19 @@
 //!
 //! [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inbound/FBAInbound_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
 use client::{Client, Method};
+use futures::Future;
 use result::MwsResult;
 
 mod types;
 pub use self::types::*;
 
-static PATH: &'static str = "/FulfillmentInboundShipment/2010-10-01";
-static VERSION: &'static str = "2010-10-01";
+pub(crate) const PATH: &'static str = "/FulfillmentInboundShipment/2010-10-01";
+pub(crate) const VERSION: &'static str = "2010-10-01";
+
+get_service_status!(PATH, VERSION);
 
 /// Parameters for `ListInboundShipments`
 #[allow(non_snake_case)]
@@ -41,6 +45,13 @@ response_envelope_type!(
   "ListInboundShipmentsByNextTokenResult"
 );
 
+next_token_response!(
+  ListInboundShipmentsResponse,
+  ShipmentData: InboundShipmentInfo,
+  ListInboundShipmentsByNextToken,
+  ListInboundShipmentsByNextTokenAsync
+);
+
 /// Returns a list of inbound shipments based on criteria that you specify.
 ///
 /// [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inbound/FBAInbound_ListInboundShipments.html)
@@ -61,6 +72,25 @@ pub fn ListInboundShipments(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInboundShipments`](fn.ListInboundShipments.html).
+#[allow(non_snake_case)]
+pub fn ListInboundShipmentsAsync(
+  client: &AsyncClient,
+  parameters: ListInboundShipmentsParameters,
+) -> MwsFuture<ListInboundShipmentsResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInboundShipments",
+        parameters,
+      )
+      .map(|e: ListInboundShipmentsEnvelope| e.into_inner()),
+  )
+}
+
 /// Returns the next page of inbound shipments using the NextToken parameter.
 ///
 /// [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inbound/FBAInbound_ListInboundShipmentsByNextToken.html)
@@ -82,6 +112,26 @@ pub fn ListInboundShipmentsByNextToken(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInboundShipmentsByNextToken`](fn.ListInboundShipmentsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListInboundShipmentsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListInboundShipmentsResponse> {
+  let params = vec![("NextToken".to_string(), next_token)];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInboundShipmentsByNextToken",
+        params,
+      )
+      .map(|e: ListInboundShipmentsByNextTokenEnvelope| e.into_inner()),
+  )
+}
+
 /// Parameters for `ListInboundShipments`
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -110,6 +160,13 @@ response_envelope_type!(
   "ListInboundShipmentItemsByNextTokenResult"
 );
 
+next_token_response!(
+  ListInboundShipmentItemsResponse,
+  ItemData: InboundShipmentItem,
+  ListInboundShipmentItemsByNextToken,
+  ListInboundShipmentItemsByNextTokenAsync
+);
+
 /// Returns a list of items in a specified inbound shipment, or a list of items that were updated within a specified time frame.
 ///
 /// [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inbound/FBAInbound_ListInboundShipmentItems.html)
@@ -130,6 +187,25 @@ pub fn ListInboundShipmentItems(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInboundShipmentItems`](fn.ListInboundShipmentItems.html).
+#[allow(non_snake_case)]
+pub fn ListInboundShipmentItemsAsync(
+  client: &AsyncClient,
+  parameters: ListInboundShipmentItemsParameters,
+) -> MwsFuture<ListInboundShipmentItemsResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInboundShipmentItems",
+        parameters,
+      )
+      .map(|e: ListInboundShipmentItemsEnvelope| e.into_inner()),
+  )
+}
+
 /// Returns the next page of inbound shipment items using the NextToken parameter.
 ///
 /// [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inbound/FBAInbound_ListInboundShipmentItemsByNextToken.html)
@@ -151,6 +227,26 @@ pub fn ListInboundShipmentItemsByNextToken(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInboundShipmentItemsByNextToken`](fn.ListInboundShipmentItemsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListInboundShipmentItemsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListInboundShipmentItemsResponse> {
+  let params = vec![("NextToken".to_string(), next_token)];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInboundShipmentItemsByNextToken",
+        params,
+      )
+      .map(|e: ListInboundShipmentItemsByNextTokenEnvelope| e.into_inner()),
+  )
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
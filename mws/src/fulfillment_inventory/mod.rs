@@ -2,16 +2,20 @@
 //!
 //! [Documentation](http://docs.developer.amazonservices.com/en_CA/fba_inventory/FBAInventory_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
 use client::{Client, Method};
+use futures::Future;
 mod types;
 pub use self::types::{
   Condition, InventorySupply, InventorySupplyDetail, SupplyType, Timepoint, TimepointType,
 };
 use result::MwsResult;
 
-static PATH: &'static str = "/FulfillmentInventory/2010-10-01";
-static VERSION: &'static str = "2010-10-01";
+pub(crate) const PATH: &'static str = "/FulfillmentInventory/2010-10-01";
+pub(crate) const VERSION: &'static str = "2010-10-01";
+
+get_service_status!(PATH, VERSION);
 
 #[derive(Debug, Serialize, SerializeMwsParams)]
 pub enum ResponseGroup {
@@ -61,6 +65,13 @@ response_envelope_type!(
   "ListInventorySupplyByNextTokenResult"
 );
 
+next_token_response!(
+  ListInventorySupplyResponse,
+  InventorySupplyList: InventorySupply,
+  ListInventorySupplyByNextToken,
+  ListInventorySupplyByNextTokenAsync
+);
+
 /// The ListInventorySupply operation returns information about the availability of
 /// inventory that a seller has in the Amazon Fulfillment Network and in current inbound shipments.
 /// You can check the current availabilty status for your Amazon Fulfillment Network inventory as well
@@ -88,6 +99,25 @@ pub fn ListInventorySupply(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInventorySupply`](fn.ListInventorySupply.html).
+#[allow(non_snake_case)]
+pub fn ListInventorySupplyAsync(
+  client: &AsyncClient,
+  parameters: ListInventorySupplyParameters,
+) -> MwsFuture<ListInventorySupplyResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInventorySupply",
+        parameters,
+      )
+      .map(|e: ListInventorySupplyResponseEnvelope| e.into_inner()),
+  )
+}
+
 /// Returns the next page of information about the availability of a seller's inventory using the NextToken parameter.
 #[allow(non_snake_case)]
 pub fn ListInventorySupplyByNextToken(
@@ -107,6 +137,26 @@ pub fn ListInventorySupplyByNextToken(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListInventorySupplyByNextToken`](fn.ListInventorySupplyByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListInventorySupplyByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListInventorySupplyResponse> {
+  let params = vec![("NextToken".to_string(), next_token)];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListInventorySupplyByNextToken",
+        params,
+      )
+      .map(|e: ListInventorySupplyByNextTokenResponseEnvelope| e.into_inner()),
+  )
+}
+
 // #[cfg(test)]
 // mod tests {
 //   use dotenv::dotenv;
@@ -116,7 +166,7 @@ pub fn ListInventorySupplyByNextToken(
 //   #[test]
 //   fn test_list_inventory_supply() {
 //     dotenv().ok();
-//     let c = get_test_client();
+//     let c = get_test_client("fulfillment_inventory_list_inventory_supply");
 //     let mut params = ListInventorySupplyParameters::default();
 //     params.query_start_datetime = Some("2017-02-07T05:00:00Z".parse().expect("parse created_after"));
 //     let res = ListInventorySupply(&c, params).expect("ListInventorySupply");
@@ -132,7 +182,7 @@ pub fn ListInventorySupplyByNextToken(
 //   fn test_list_inventory_supply_by_next_token() {
 //     dotenv().ok();
 //     let token = "H4sIAAAAAAAAADXPS3KCMAAA0Ks4TF25ACTGMlM7Q4oIYgoCIrqLBAifFJUg4unbTd8N3kfZXerJQO8rSdz7TJpwsZKmb1MbdI7xb3P0xuFmYoQsGRa1ku45cB2fWac7yjhESLu2a+KcVFkBqqBNQo7nGdR/NkFH5q+LyV7Nfpdn30oMS5FhpPXBJY3cs02KVAeR2jAyCEjF4lzUWRBVLd1tQ08YTH0+cnaz06/EmokWD8sDnSEtDkdQ187WrqxrwsVuQRJj+x6GPF1m1RP2WHkwnI5p9CptT1ZLumbtHLjdVWmaBbn5kel7mK9PrkmoEed6Tg4e94HjAt/UtQDn7V6r+hjGRYv+9itJ/vwFMFiuAicBAAA=_mEaNrCkReYPtTpElDaMcKeP_ATVPDKIKX0DER";
-//     let c = get_test_client();
+//     let c = get_test_client("fulfillment_inventory_list_inventory_supply_by_next_token");
 //     let res = ListInventorySupplyByNextToken(&c, token.to_owned()).expect("ListInventorySupplyByNextToken");
 //     match res {
 //       Response::Error(e) => panic!("request error: {:?}", e),
@@ -2,15 +2,19 @@
 //!
 //! [Documentation](https://docs.developer.amazonservices.com/en_US/fba_outbound/FBAOutbound_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
 use client::{Client, Method};
+use futures::Future;
 mod types;
 pub use self::types::*;
 use super::types::ToIso8601;
 use result::MwsResult;
 
-static PATH: &'static str = "/FulfillmentOutboundShipment/2010-10-01";
-static VERSION: &'static str = "2010-10-01";
+pub(crate) const PATH: &'static str = "/FulfillmentOutboundShipment/2010-10-01";
+pub(crate) const VERSION: &'static str = "2010-10-01";
+
+get_service_status!(PATH, VERSION);
 
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, FromXmlStream)]
@@ -31,6 +35,13 @@ response_envelope_type!(
   "ListAllFulfillmentOrdersByNextTokenResult"
 );
 
+next_token_response!(
+  ListAllFulfillmentOrdersResponse,
+  FulfillmentOrders: FulfillmentOrder,
+  ListAllFulfillmentOrdersByNextToken,
+  ListAllFulfillmentOrdersByNextTokenAsync
+);
+
 /// Returns a list of fulfillment orders fulfilled after (or at) a specified date.
 ///
 /// [Documentation](http://docs.developer.amazonservices.com/en_US/orders-2013-09-01/Orders_ListOrders.html)
@@ -54,6 +65,28 @@ pub fn ListAllFulfillmentOrders(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListAllFulfillmentOrders`](fn.ListAllFulfillmentOrders.html).
+#[allow(non_snake_case)]
+pub fn ListAllFulfillmentOrdersAsync(
+  client: &AsyncClient,
+  query_start_date_time: DateTime<Utc>,
+) -> MwsFuture<ListAllFulfillmentOrdersResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListAllFulfillmentOrders",
+        vec![(
+          "QueryStartDateTime".to_string(),
+          query_start_date_time.to_iso8601(),
+        )],
+      )
+      .map(|e: ListAllFulfillmentOrdersEnvelope| e.into_inner()),
+  )
+}
+
 /// Returns the next page of fulfillment orders using the NextToken parameter.
 ///
 /// [Documentation](https://docs.developer.amazonservices.com/en_US/fba_outbound/FBAOutbound_ListAllFulfillmentOrdersByNextToken.html)
@@ -75,6 +108,26 @@ pub fn ListAllFulfillmentOrdersByNextToken(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListAllFulfillmentOrdersByNextToken`](fn.ListAllFulfillmentOrdersByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListAllFulfillmentOrdersByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListAllFulfillmentOrdersResponse> {
+  let params = vec![("NextToken".to_string(), next_token)];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListAllFulfillmentOrdersByNextToken",
+        params,
+      )
+      .map(|e: ListAllFulfillmentOrdersByNextTokenEnvelope| e.into_inner()),
+  )
+}
+
 response_envelope_type!(
   GetFulfillmentOrderEnvelope<GetFulfillmentOrderResponse>,
   "GetFulfillmentOrderResponse",
@@ -109,6 +162,23 @@ pub fn GetFulfillmentOrder(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetFulfillmentOrder`](fn.GetFulfillmentOrder.html).
+#[allow(non_snake_case)]
+pub fn GetFulfillmentOrderAsync(
+  client: &AsyncClient,
+  seller_fulfillment_order_id: String,
+) -> MwsFuture<GetFulfillmentOrderResponse> {
+  let params = vec![(
+    "SellerFulfillmentOrderId".to_string(),
+    seller_fulfillment_order_id,
+  )];
+  Box::new(
+    client
+      .request_xml(Method::Post, PATH, VERSION, "GetFulfillmentOrder", params)
+      .map(|e: GetFulfillmentOrderEnvelope| e.into_inner()),
+  )
+}
+
 pub type GetPackageTrackingDetailsResponse = PackageTrackingDetails;
 
 response_envelope_type!(
@@ -138,6 +208,26 @@ pub fn GetPackageTrackingDetails(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetPackageTrackingDetails`](fn.GetPackageTrackingDetails.html).
+#[allow(non_snake_case)]
+pub fn GetPackageTrackingDetailsAsync(
+  client: &AsyncClient,
+  package_number: &str,
+) -> MwsFuture<GetPackageTrackingDetailsResponse> {
+  let params = vec![("PackageNumber".to_string(), package_number.to_owned())];
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetPackageTrackingDetails",
+        params,
+      )
+      .map(|e: GetPackageTrackingDetailsEnvelope| e.into_inner()),
+  )
+}
+
 /// Item information for a fulfillment order preview.
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -190,6 +280,19 @@ pub fn GetFulfillmentPreview(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetFulfillmentPreview`](fn.GetFulfillmentPreview.html).
+#[allow(non_snake_case)]
+pub fn GetFulfillmentPreviewAsync(
+  client: &AsyncClient,
+  params: GetFulfillmentPreviewParameters,
+) -> MwsFuture<GetFulfillmentPreviewResponse> {
+  Box::new(
+    client
+      .request_xml(Method::Post, PATH, VERSION, "GetFulfillmentPreview", params)
+      .map(|e: GetFulfillmentPreviewEnvelope| e.into_inner()),
+  )
+}
+
 /// Item information for creating a fulfillment order.
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -265,6 +368,25 @@ pub fn CreateFulfillmentOrder(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`CreateFulfillmentOrder`](fn.CreateFulfillmentOrder.html).
+#[allow(non_snake_case)]
+pub fn CreateFulfillmentOrderAsync(
+  client: &AsyncClient,
+  params: CreateFulfillmentOrderParameters,
+) -> MwsFuture<()> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "CreateFulfillmentOrder",
+        params,
+      )
+      .map(|e: CreateFulfillmentOrderEnvelope| e.into_inner()),
+  )
+}
+
 response_envelope_type!(
   CancelFulfillmentOrderEnvelope<()>,
   "CancelFulfillmentOrderResponse",
@@ -291,6 +413,28 @@ pub fn CancelFulfillmentOrder(client: &Client, seller_fulfillment_order_id: &str
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`CancelFulfillmentOrder`](fn.CancelFulfillmentOrder.html).
+#[allow(non_snake_case)]
+pub fn CancelFulfillmentOrderAsync(
+  client: &AsyncClient,
+  seller_fulfillment_order_id: &str,
+) -> MwsFuture<()> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "CancelFulfillmentOrder",
+        vec![(
+          "SellerFulfillmentOrderId".to_owned(),
+          seller_fulfillment_order_id.to_owned(),
+        )],
+      )
+      .map(|e: CancelFulfillmentOrderEnvelope| e.into_inner()),
+  )
+}
+
 #[cfg(test)]
 mod tests {
   use super::super::client::get_test_client;
@@ -301,7 +445,7 @@ mod tests {
   #[ignore]
   fn test_get_package_tracking_details() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("fulfillment_outbound_get_package_tracking_details");
     let res = GetPackageTrackingDetails(&c, "187748827").expect("GetPackageTrackingDetails");
     println!("res = {:#?}", res);
   }
@@ -310,7 +454,7 @@ mod tests {
   #[ignore]
   fn test_get_fufillment_preview() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("fulfillment_outbound_get_fufillment_preview");
     let res = GetFulfillmentPreview(
       &c,
       GetFulfillmentPreviewParameters {
@@ -356,7 +500,7 @@ mod tests {
     use chrono::TimeZone;
 
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("fulfillment_outbound_create_fulfillment_order");
     let res = CreateFulfillmentOrder(
       &c,
       CreateFulfillmentOrderParameters {
@@ -403,7 +547,7 @@ mod tests {
   #[ignore]
   fn test_cancel_fulfillment_order() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("fulfillment_outbound_cancel_fulfillment_order");
     let res = CancelFulfillmentOrder(&c, "S2_TEST_20180517_3").expect("CancelFulfillmentOrder");
     println!("res = {:#?}", res);
   }
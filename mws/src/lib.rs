@@ -14,11 +14,18 @@ extern crate xml;
 extern crate failure_derive;
 extern crate csv;
 extern crate serde;
+extern crate serde_json;
 #[macro_use]
 extern crate serde_derive;
 #[macro_use]
 extern crate mws_derive;
+extern crate rand;
 extern crate xmltree;
+#[macro_use]
+extern crate futures;
+extern crate tokio_core;
+#[macro_use]
+extern crate log;
 
 pub use mws_derive::FromTdffRow;
 
@@ -38,11 +45,26 @@ mod types;
 mod macros;
 #[macro_use]
 pub mod tdff;
+pub mod async_client;
+pub mod cassette;
 pub mod client;
+pub mod clock;
 pub mod constants;
-mod sign;
+pub mod credentials;
+pub mod metadata;
+pub mod middleware;
+pub mod operation;
+pub mod pagination;
+pub mod pool;
+pub mod retry;
+pub mod secret;
+pub mod service_status;
+pub mod sign;
+pub mod throttle;
+pub mod transport;
 
 pub mod feeds;
+pub mod finances;
 pub mod fulfillment_inbound_shipment;
 pub mod fulfillment_inventory;
 pub mod fulfillment_outbound;
@@ -50,6 +72,7 @@ pub mod merchant_fulfillment;
 pub mod orders;
 pub mod products;
 pub mod reports;
+pub mod sellers;
 pub mod subscriptions;
 
 pub use self::types::{ResponseEnvelope, SerializeMwsParams, SerializeMwsParamsContext};
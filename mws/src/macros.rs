@@ -273,3 +273,142 @@ macro_rules! response_envelope_batch_type {
     }
   };
 }
+
+/// Implements `pagination::NextTokenResponse` for the response of a list operation.
+///
+/// Add `HasNext` if the response has a `HasNext` field which must be `true` for `NextToken` to be followed.
+macro_rules! next_token_response {
+  ($response:ty, $items:ident : $item:ty, $by_next_token:ident, $by_next_token_async:ident) => {
+    next_token_response!(@impl $response, $items: $item, $by_next_token, $by_next_token_async, |_r| true);
+  };
+  ($response:ty, $items:ident : $item:ty, $by_next_token:ident, $by_next_token_async:ident, HasNext) => {
+    next_token_response!(@impl $response, $items: $item, $by_next_token, $by_next_token_async, |r| r.HasNext);
+  };
+  (@impl $response:ty, $items:ident : $item:ty, $by_next_token:ident, $by_next_token_async:ident, |$r:ident| $has_next:expr) => {
+    impl ::pagination::NextTokenResponse for $response {
+      type Item = $item;
+
+      fn next_token(&self) -> Option<&str> {
+        let $r = self;
+        if $has_next {
+          self.NextToken.as_ref().map(String::as_str)
+        } else {
+          None
+        }
+      }
+
+      fn into_items(self) -> Vec<$item> {
+        self.$items
+      }
+
+      fn by_next_token(client: &::client::Client, next_token: String) -> ::result::MwsResult<Self> {
+        $by_next_token(client, next_token)
+      }
+
+      fn by_next_token_async(
+        client: &::async_client::AsyncClient,
+        next_token: String,
+      ) -> ::async_client::MwsFuture<Self> {
+        $by_next_token_async(client, next_token)
+      }
+    }
+  };
+}
+
+/// Implements `operation::MwsOperation` for a parameters type.
+///
+/// The response is decoded with an envelope declared by `response_envelope_type!` or
+/// `response_envelope_batch_type!`. Add `form: true` after `idempotent` to send the parameters
+/// as a form, and `ByNextToken` to also declare the parameters of the `...ByNextToken` operation.
+macro_rules! mws_operation {
+  (
+    $path:expr, $version:expr, $action:expr,
+    $params:ident => $envelope:ident < $response:ty >,
+    idempotent: $idempotent:expr, form: $form:expr
+  ) => {
+    mws_operation!(
+      @impl $path, $version, $action, $action, $idempotent, $form,
+      $params => $envelope<$response>
+    );
+  };
+  (
+    $path:expr, $version:expr, $action:expr,
+    $params:ident => $envelope:ident < $response:ty >,
+    idempotent: $idempotent:expr
+  ) => {
+    mws_operation!(
+      @impl $path, $version, $action, $action, $idempotent, false,
+      $params => $envelope<$response>
+    );
+  };
+  (
+    $path:expr, $version:expr, $action:expr,
+    $params:ident => $envelope:ident < $response:ty >,
+    ByNextToken: $by_next_token_params:ident => $by_next_token_envelope:ident
+  ) => {
+    // list operations only read
+    mws_operation!(@impl $path, $version, $action, $action, true, false, $params => $envelope<$response>);
+
+    /// Parameters of the `...ByNextToken` operation
+    #[allow(non_snake_case)]
+    #[derive(Debug, Default, Clone, Serialize, SerializeMwsParams)]
+    pub struct $by_next_token_params {
+      pub NextToken: String,
+    }
+
+    // counts against the quota of the operation it continues
+    mws_operation!(
+      @impl $path, $version, concat!($action, "ByNextToken"), $action, true, false,
+      $by_next_token_params => $by_next_token_envelope<$response>
+    );
+  };
+  (
+    @impl $path:expr, $version:expr, $action:expr, $throttle_class:expr, $idempotent:expr,
+    $form:expr, $params:ident => $envelope:ident < $response:ty >
+  ) => {
+    impl ::operation::MwsOperation for $params {
+      type Params = Self;
+      type Response = $response;
+
+      const PATH: &'static str = $path;
+      const VERSION: &'static str = $version;
+      const ACTION: &'static str = $action;
+      const THROTTLE_CLASS: &'static str = $throttle_class;
+      const IDEMPOTENT: bool = $idempotent;
+      const FORM: bool = $form;
+
+      fn into_params(self) -> Self {
+        self
+      }
+
+      fn decode_response(resp: ::transport::HttpResponse) -> ::result::MwsResult<$response> {
+        let mut stream = ::xmlhelper::decode::Stream::new(resp);
+        let envelope: $envelope = ::xmlhelper::decode::FromXmlStream::from_xml(&mut stream)?;
+        Ok(envelope.into_inner())
+      }
+    }
+  };
+}
+
+/// Declares `GetServiceStatus` and `GetServiceStatusAsync` for the section at `$path`.
+macro_rules! get_service_status {
+  ($path:expr, $version:expr) => {
+    /// Returns the operational status of the section.
+    ///
+    /// [Documentation](http://docs.developer.amazonservices.com/en_US/dev_guide/DG_ServiceStatus.html)
+    #[allow(non_snake_case)]
+    pub fn GetServiceStatus(
+      client: &::client::Client,
+    ) -> ::result::MwsResult<::service_status::GetServiceStatusResponse> {
+      ::service_status::get_service_status(client, $path, $version)
+    }
+
+    /// Asynchronous version of [`GetServiceStatus`](fn.GetServiceStatus.html).
+    #[allow(non_snake_case)]
+    pub fn GetServiceStatusAsync(
+      client: &::async_client::AsyncClient,
+    ) -> ::async_client::MwsFuture<::service_status::GetServiceStatusResponse> {
+      ::service_status::get_service_status_async(client, $path, $version)
+    }
+  };
+}
@@ -3,13 +3,17 @@
 //! [Documentation](http://docs.developer.amazonservices.com/en_US/merch_fulfill/MerchFulfill_Overview.html)
 
 pub use self::types::*;
+use async_client::{AsyncClient, MwsFuture};
 use client::{Client, Method};
+use futures::Future;
 use result::MwsResult;
 
 mod types;
 
-static PATH: &'static str = "/MerchantFulfillment/2015-06-01";
-static VERSION: &'static str = "2015-06-01";
+pub(crate) const PATH: &'static str = "/MerchantFulfillment/2015-06-01";
+pub(crate) const VERSION: &'static str = "2015-06-01";
+
+get_service_status!(PATH, VERSION);
 
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
@@ -51,6 +55,25 @@ pub fn GetEligibleShippingServices(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetEligibleShippingServices`](fn.GetEligibleShippingServices.html).
+#[allow(non_snake_case)]
+pub fn GetEligibleShippingServicesAsync(
+  client: &AsyncClient,
+  params: GetEligibleShippingServicesParameters,
+) -> MwsFuture<GetEligibleShippingServicesResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetEligibleShippingServices",
+        params,
+      )
+      .map(|e: GetEligibleShippingServicesEnvelope| e.into_inner()),
+  )
+}
+
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
 pub struct CreateShipmentResponse {
@@ -88,6 +111,19 @@ pub fn CreateShipment(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`CreateShipment`](fn.CreateShipment.html).
+#[allow(non_snake_case)]
+pub fn CreateShipmentAsync(
+  client: &AsyncClient,
+  params: CreateShipmentParameters,
+) -> MwsFuture<CreateShipmentResponse> {
+  Box::new(
+    client
+      .request_xml(Method::Post, PATH, VERSION, "CreateShipment", params)
+      .map(|e: CreateShipmentEnvelope| e.into_inner()),
+  )
+}
+
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
 pub struct GetShipmentResponse {
@@ -114,6 +150,22 @@ pub fn GetShipment(client: &Client, id: &str) -> MwsResult<GetShipmentResponse>
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetShipment`](fn.GetShipment.html).
+#[allow(non_snake_case)]
+pub fn GetShipmentAsync(client: &AsyncClient, id: &str) -> MwsFuture<GetShipmentResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "GetShipment",
+        vec![("ShipmentId".to_string(), id.to_string())],
+      )
+      .map(|e: GetShipmentEnvelope| e.into_inner()),
+  )
+}
+
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
 pub struct CancelShipmentResponse {
@@ -140,6 +192,22 @@ pub fn CancelShipment(client: &Client, id: &str) -> MwsResult<CancelShipmentResp
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`CancelShipment`](fn.CancelShipment.html).
+#[allow(non_snake_case)]
+pub fn CancelShipmentAsync(client: &AsyncClient, id: &str) -> MwsFuture<CancelShipmentResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "CancelShipment",
+        vec![("ShipmentId".to_string(), id.to_string())],
+      )
+      .map(|e: CancelShipmentEnvelope| e.into_inner()),
+  )
+}
+
 #[cfg(test)]
 mod tests {
   use super::super::client::get_test_client;
@@ -195,7 +263,7 @@ mod tests {
   #[ignore]
   fn test_get_eligible_shipping_services() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("merchant_fulfillment_get_eligible_shipping_services");
     let details = get_test_details();
 
     let res = GetEligibleShippingServices(
@@ -212,7 +280,7 @@ mod tests {
   #[ignore]
   fn test_create_shipment() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("merchant_fulfillment_create_shipment");
     let params = CreateShipmentParameters {
       ShipmentRequestDetails: get_test_details(),
       ShippingServiceId: "USPS_PTP_EXP".to_owned(),
@@ -227,7 +295,7 @@ mod tests {
   #[ignore]
   fn test_get_shipment() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("merchant_fulfillment_get_shipment");
 
     let res = GetShipment(&c, "a4062ca8-faa0-49d4-bb0b-b32433ebdb3a").expect("GetShipment");
     println!("res = {:#?}", res);
@@ -237,7 +305,7 @@ mod tests {
   #[ignore]
   fn test_cancel_shipment() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("merchant_fulfillment_cancel_shipment");
 
     let res = CancelShipment(&c, "a4062ca8-faa0-49d4-bb0b-b32433ebdb3a").expect("CancelShipment");
     println!("res = {:#?}", res);
@@ -0,0 +1,129 @@
+//! Response metadata
+//!
+//! Every MWS response carries a request id and, for most operations, the quota headers
+//! `x-mws-quota-max`, `x-mws-quota-remaining` and `x-mws-quota-resetsOn`.
+//! Use [`Client::with_metadata`](../client/struct.Client.html#method.with_metadata) to get them
+//! together with the decoded payload.
+//!
+//! [Reference](http://docs.developer.amazonservices.com/en_US/dev_guide/DG_ResponseFormat.html)
+
+use chrono::{DateTime, Utc};
+
+/// Metadata of a MWS response
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseMetadata {
+  /// Value of `x-mws-request-id`, Amazon support asks for it when you open a case
+  pub request_id: Option<String>,
+  /// Value of `x-mws-timestamp`, or the `Date` header
+  pub timestamp: Option<DateTime<Utc>>,
+  /// Value of `x-mws-quota-max`
+  pub quota_max: Option<u32>,
+  /// Value of `x-mws-quota-remaining`
+  pub quota_remaining: Option<u32>,
+  /// Value of `x-mws-quota-resetsOn`
+  pub quota_resets_on: Option<DateTime<Utc>>,
+}
+
+impl ResponseMetadata {
+  /// Reads the metadata from response headers.
+  pub fn from_headers(headers: &[(String, String)]) -> ResponseMetadata {
+    let get = |name: &str| {
+      headers
+        .iter()
+        .find(|&&(ref k, _)| k.eq_ignore_ascii_case(name))
+        .map(|&(_, ref v)| v.trim())
+    };
+    let timestamp = get("x-mws-timestamp").and_then(parse_rfc3339).or_else(|| {
+      get("Date")
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+        .map(|v| v.with_timezone(&Utc))
+    });
+    ResponseMetadata {
+      request_id: get("x-mws-request-id").map(ToString::to_string),
+      timestamp: timestamp,
+      quota_max: get("x-mws-quota-max").and_then(parse_quota),
+      quota_remaining: get("x-mws-quota-remaining").and_then(parse_quota),
+      quota_resets_on: get("x-mws-quota-resetsOn").and_then(parse_rfc3339),
+    }
+  }
+}
+
+/// A decoded payload and the metadata of the response it came from
+#[derive(Debug, Clone, Default)]
+pub struct WithMetadata<T> {
+  pub payload: T,
+  pub metadata: ResponseMetadata,
+}
+
+impl<T> WithMetadata<T> {
+  pub fn into_inner(self) -> T {
+    self.payload
+  }
+
+  pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> WithMetadata<U> {
+    WithMetadata {
+      payload: f(self.payload),
+      metadata: self.metadata,
+    }
+  }
+}
+
+fn parse_rfc3339(v: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(v)
+    .ok()
+    .map(|v| v.with_timezone(&Utc))
+}
+
+/// Quota headers are sent as decimals, e.g. `200.0`
+fn parse_quota(v: &str) -> Option<u32> {
+  v.parse::<f64>().ok().map(|v| v as u32)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeZone;
+
+  #[test]
+  fn test_from_headers() {
+    let headers = vec![
+      ("x-mws-request-id".to_string(), "REQUEST".to_string()),
+      (
+        "x-mws-timestamp".to_string(),
+        "2018-08-29T22:23:45.277Z".to_string(),
+      ),
+      ("x-mws-quota-max".to_string(), "6.0".to_string()),
+      ("x-mws-quota-remaining".to_string(), "5.0".to_string()),
+      (
+        "x-mws-quota-resetsOn".to_string(),
+        "2018-08-29T23:00:00.000Z".to_string(),
+      ),
+    ];
+    let metadata = ResponseMetadata::from_headers(&headers);
+    assert_eq!(
+      metadata,
+      ResponseMetadata {
+        request_id: Some("REQUEST".to_string()),
+        timestamp: Some(Utc.ymd(2018, 8, 29).and_hms_milli(22, 23, 45, 277)),
+        quota_max: Some(6),
+        quota_remaining: Some(5),
+        quota_resets_on: Some(Utc.ymd(2018, 8, 29).and_hms(23, 0, 0)),
+      }
+    );
+  }
+
+  #[test]
+  fn test_from_headers_date() {
+    let headers = vec![(
+      "Date".to_string(),
+      "Wed, 29 Aug 2018 22:23:45 GMT".to_string(),
+    )];
+    let metadata = ResponseMetadata::from_headers(&headers);
+    assert_eq!(metadata.request_id, None);
+    assert_eq!(
+      metadata.timestamp,
+      Some(Utc.ymd(2018, 8, 29).and_hms(22, 23, 45))
+    );
+    assert_eq!(metadata.quota_max, None);
+  }
+}
@@ -0,0 +1,69 @@
+//! Request middleware
+//!
+//! Middleware added with [`Client::add_middleware`](../client/struct.Client.html#method.add_middleware)
+//! is called around every attempt to send a request, in the order it was added:
+//! `before_send` before the request is signed, `after_receive` once the response or error is known.
+//!
+//! ```ignore
+//! struct Log;
+//!
+//! impl Middleware for Log {
+//!   fn after_receive(&self, req: &RequestContext, resp: &ResponseContext) {
+//!     println!("{} {}: {:?} in {:?}", req.seller_id, req.action, resp.status, resp.duration);
+//!   }
+//! }
+//!
+//! client.add_middleware(Log);
+//! ```
+
+use client::{Method, StatusCode};
+use result::MwsError;
+use std::time::Duration;
+
+/// A request about to be signed and sent
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+  pub method: Method,
+  pub path: String,
+  pub version: String,
+  pub action: String,
+  pub seller_id: String,
+  /// Parameters of the operation, changes are signed
+  pub parameters: Vec<(String, String)>,
+  /// Additional HTTP headers, e.g. to tag requests
+  pub headers: Vec<(String, String)>,
+  /// Attempt number, starting at 1 and increased by every retry
+  pub attempt: u32,
+}
+
+impl RequestContext {
+  /// Returns the first parameter with the given name.
+  pub fn parameter(&self, name: &str) -> Option<&str> {
+    self
+      .parameters
+      .iter()
+      .find(|&&(ref k, _)| k == name)
+      .map(|&(_, ref v)| v.as_str())
+  }
+}
+
+/// The outcome of an attempt
+#[derive(Debug)]
+pub struct ResponseContext<'a> {
+  /// Status of the response, `None` if no response was received
+  pub status: Option<StatusCode>,
+  pub headers: &'a [(String, String)],
+  /// Time between sending the request and receiving the response headers
+  pub duration: Duration,
+  /// The error returned by the attempt, including error responses
+  pub error: Option<&'a MwsError>,
+}
+
+/// Hooks called around every request sent by a `Client` or an `AsyncClient`
+pub trait Middleware: Send + Sync {
+  /// Called before the request is signed, can change its parameters or add headers.
+  fn before_send(&self, _request: &mut RequestContext) {}
+
+  /// Called once the response or error is known.
+  fn after_receive(&self, _request: &RequestContext, _response: &ResponseContext) {}
+}
@@ -0,0 +1,123 @@
+//! Typed operations
+//!
+//! An [`MwsOperation`](trait.MwsOperation.html) describes everything needed to send a request:
+//! its parameters, section path and version, action name, throttling quota and how its response
+//! is decoded. [`Client::execute`](../client/struct.Client.html#method.execute) sends any operation
+//! through the client's throttler and retry policy:
+//!
+//! ```ignore
+//! let res = client.execute(orders::ListOrdersParameters { .. })?;
+//! let next = client.execute(orders::ListOrdersByNextTokenParameters {
+//!   NextToken: res.NextToken.unwrap(),
+//! })?;
+//! ```
+//!
+//! Operations are usually declared with the `mws_operation!` macro, which also declares the
+//! `...ByNextTokenParameters` companion of list operations.
+
+use result::MwsResult;
+use transport::HttpResponse;
+use SerializeMwsParams;
+
+/// A request to an MWS operation
+pub trait MwsOperation {
+  type Params: SerializeMwsParams;
+  type Response;
+
+  /// Path of the section, e.g. `/Orders/2013-09-01`
+  const PATH: &'static str;
+  /// Version of the section, e.g. `2013-09-01`
+  const VERSION: &'static str;
+  /// Name of the operation, e.g. `ListOrders`
+  const ACTION: &'static str;
+  /// Action name of the quota the operation counts against
+  const THROTTLE_CLASS: &'static str = Self::ACTION;
+  /// `true` if the operation is safe to send twice, which allows the client to retry it
+  /// after a server error
+  const IDEMPOTENT: bool;
+  /// `true` if the parameters are sent as a form in the request body instead of the query
+  /// string, which the Products API requires
+  const FORM: bool = false;
+
+  /// Returns the request parameters.
+  fn into_params(self) -> Self::Params;
+
+  /// Decodes the response of a successful request.
+  fn decode_response(resp: HttpResponse) -> MwsResult<Self::Response>;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use client::{Client, ClientOptions};
+  use orders::{ListOrderItemsParameters, ListOrdersByNextTokenParameters, ListOrdersParameters};
+  use products::GetMyPriceForSKUParameters;
+  use reports::RequestReportParameters;
+  use std::sync::{Arc, Mutex};
+  use transport::{HttpRequest, HttpTransport, StatusCode};
+
+  struct Recorder {
+    urls: Arc<Mutex<Vec<String>>>,
+  }
+
+  impl HttpTransport for Recorder {
+    fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+      self.urls.lock().unwrap().push(request.url);
+      Ok(HttpResponse::new(
+        StatusCode::Ok,
+        vec![],
+        r#"<ListOrdersByNextTokenResponse><ListOrdersByNextTokenResult><Orders><Order><AmazonOrderId>1</AmazonOrderId></Order></Orders></ListOrdersByNextTokenResult></ListOrdersByNextTokenResponse>"#,
+      ))
+    }
+  }
+
+  #[test]
+  fn test_operation() {
+    assert_eq!(ListOrdersParameters::PATH, "/Orders/2013-09-01");
+    assert_eq!(ListOrdersParameters::ACTION, "ListOrders");
+    assert_eq!(ListOrdersParameters::THROTTLE_CLASS, "ListOrders");
+    assert!(ListOrdersParameters::IDEMPOTENT);
+    assert!(!ListOrdersParameters::FORM);
+    assert!(GetMyPriceForSKUParameters::FORM);
+    assert!(!RequestReportParameters::IDEMPOTENT);
+    assert_eq!(
+      ListOrdersByNextTokenParameters::ACTION,
+      "ListOrdersByNextToken"
+    );
+    assert_eq!(
+      ListOrdersByNextTokenParameters::THROTTLE_CLASS,
+      "ListOrders"
+    );
+    assert_eq!(ListOrderItemsParameters::ACTION, "ListOrderItems");
+  }
+
+  #[test]
+  fn test_execute() {
+    let urls = Arc::new(Mutex::new(vec![]));
+    let mut client = Client::with_transport(
+      ClientOptions {
+        endpoint: "mws.amazonservices.com".to_string(),
+        seller_id: "SELLER".to_string(),
+        aws_access_key_id: "KEY".to_string(),
+        secret_key: "SECRET".into(),
+        ..Default::default()
+      },
+      Recorder { urls: urls.clone() },
+    );
+    client.set_throttler(None);
+
+    let res = client
+      .execute(ListOrdersByNextTokenParameters {
+        NextToken: "TOKEN".to_string(),
+      })
+      .expect("ListOrdersByNextToken");
+    assert_eq!(res.Orders.len(), 1);
+    assert_eq!(res.Orders[0].AmazonOrderId, "1");
+
+    let urls = urls.lock().unwrap();
+    assert_eq!(urls.len(), 1);
+    assert!(urls[0].contains("/Orders/2013-09-01?"));
+    assert!(urls[0].contains("Action=ListOrdersByNextToken"));
+    assert!(urls[0].contains("NextToken=TOKEN"));
+  }
+}
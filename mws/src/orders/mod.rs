@@ -2,14 +2,18 @@
 //!
 //! [Documentation](http://docs.developer.amazonservices.com/en_US/orders-2013-09-01/Orders_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
-use client::{Client, Method};
+use client::Client;
+use futures::{future, Future};
 mod types;
 pub use self::types::*;
 use result::MwsResult;
 
-static PATH: &'static str = "/Orders/2013-09-01";
-static VERSION: &'static str = "2013-09-01";
+pub(crate) const PATH: &'static str = "/Orders/2013-09-01";
+pub(crate) const VERSION: &'static str = "2013-09-01";
+
+get_service_status!(PATH, VERSION);
 
 /// Parameters for `ListOrders`
 #[allow(non_snake_case)]
@@ -58,6 +62,21 @@ response_envelope_type!(
   "ListOrdersByNextTokenResult"
 );
 
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListOrders",
+  ListOrdersParameters => ListOrdersEnvelope<ListOrdersResponse>,
+  ByNextToken: ListOrdersByNextTokenParameters => ListOrdersByNextTokenEnvelope
+);
+
+next_token_response!(
+  ListOrdersResponse,
+  Orders: Order,
+  ListOrdersByNextToken,
+  ListOrdersByNextTokenAsync
+);
+
 /// Returns orders created or updated during a time frame that you specify.
 ///
 /// The ListOrders operation returns a list of orders created or updated during a time frame that you specify.
@@ -71,10 +90,16 @@ pub fn ListOrders(
   client: &Client,
   parameters: ListOrdersParameters,
 ) -> MwsResult<ListOrdersResponse> {
-  client
-    .request_xml(Method::Post, PATH, VERSION, "ListOrders", parameters)
-    .map(|e: ListOrdersEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(parameters)
+}
+
+/// Asynchronous version of [`ListOrders`](fn.ListOrders.html).
+#[allow(non_snake_case)]
+pub fn ListOrdersAsync(
+  client: &AsyncClient,
+  parameters: ListOrdersParameters,
+) -> MwsFuture<ListOrdersResponse> {
+  client.execute(parameters)
 }
 
 /// Returns the next page of orders using the NextToken parameter.
@@ -84,11 +109,96 @@ pub fn ListOrders(
 /// If NextToken is not returned, there are no more pages to return.
 #[allow(non_snake_case)]
 pub fn ListOrdersByNextToken(client: &Client, next_token: String) -> MwsResult<ListOrdersResponse> {
-  let params = vec![("NextToken".to_string(), next_token)];
-  client
-    .request_xml(Method::Post, PATH, VERSION, "ListOrdersByNextToken", params)
-    .map(|e: ListOrdersByNextTokenEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(ListOrdersByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`ListOrdersByNextToken`](fn.ListOrdersByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListOrdersByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListOrdersResponse> {
+  client.execute(ListOrdersByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// The maximum number of `AmazonOrderId` values of a `GetOrder` request
+pub const GET_ORDER_MAX_IDS: usize = 50;
+
+/// Parameters for `GetOrder`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetOrderParameters {
+  #[mws_param(list_item_type_name = "Id")]
+  pub AmazonOrderId: Vec<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, FromXmlStream)]
+pub struct GetOrderResponse {
+  pub Orders: Vec<Order>,
+}
+
+response_envelope_type!(
+  GetOrderEnvelope<GetOrderResponse>,
+  "GetOrderResponse",
+  "GetOrderResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetOrder",
+  GetOrderParameters => GetOrderEnvelope<GetOrderResponse>,
+  idempotent: true
+);
+
+/// Returns orders based on the AmazonOrderId values that you specify.
+///
+/// MWS accepts up to `GET_ORDER_MAX_IDS` ids per request, longer lists are split into
+/// multiple requests and the orders are returned in the order of the responses.
+/// Unknown ids are skipped by MWS.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/orders-2013-09-01/Orders_GetOrder.html)
+#[allow(non_snake_case)]
+pub fn GetOrder(client: &Client, amazon_order_ids: Vec<String>) -> MwsResult<GetOrderResponse> {
+  let mut res = GetOrderResponse::default();
+  for ids in amazon_order_ids.chunks(GET_ORDER_MAX_IDS) {
+    let mut page = client.execute(GetOrderParameters {
+      AmazonOrderId: ids.to_vec(),
+    })?;
+    res.Orders.append(&mut page.Orders);
+  }
+  Ok(res)
+}
+
+/// Asynchronous version of [`GetOrder`](fn.GetOrder.html), the batches are requested concurrently.
+#[allow(non_snake_case)]
+pub fn GetOrderAsync(
+  client: &AsyncClient,
+  amazon_order_ids: Vec<String>,
+) -> MwsFuture<GetOrderResponse> {
+  let futures: Vec<_> = amazon_order_ids
+    .chunks(GET_ORDER_MAX_IDS)
+    .map(|ids| {
+      client.execute(GetOrderParameters {
+        AmazonOrderId: ids.to_vec(),
+      })
+    })
+    .collect();
+  Box::new(future::join_all(futures).map(|pages| GetOrderResponse {
+    Orders: pages.into_iter().flat_map(|page| page.Orders).collect(),
+  }))
+}
+
+/// Parameters for `ListOrderItems`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct ListOrderItemsParameters {
+  pub AmazonOrderId: String,
 }
 
 #[allow(non_snake_case)]
@@ -111,17 +221,41 @@ response_envelope_type!(
   "ListOrderItemsByNextTokenResult"
 );
 
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListOrderItems",
+  ListOrderItemsParameters => ListOrderItemsEnvelope<ListOrderItemsResponse>,
+  ByNextToken: ListOrderItemsByNextTokenParameters => ListOrderItemsByNextTokenEnvelope
+);
+
+next_token_response!(
+  ListOrderItemsResponse,
+  OrderItems: OrderItem,
+  ListOrderItemsByNextToken,
+  ListOrderItemsByNextTokenAsync
+);
+
 /// Returns order items based on the AmazonOrderId that you specify.
 #[allow(non_snake_case)]
 pub fn ListOrderItems(
   client: &Client,
   amazon_order_id: String,
 ) -> MwsResult<ListOrderItemsResponse> {
-  let params = vec![("AmazonOrderId".to_string(), amazon_order_id)];
-  client
-    .request_xml(Method::Post, PATH, VERSION, "ListOrderItems", params)
-    .map(|e: ListOrderItemsEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(ListOrderItemsParameters {
+    AmazonOrderId: amazon_order_id,
+  })
+}
+
+/// Asynchronous version of [`ListOrderItems`](fn.ListOrderItems.html).
+#[allow(non_snake_case)]
+pub fn ListOrderItemsAsync(
+  client: &AsyncClient,
+  amazon_order_id: String,
+) -> MwsFuture<ListOrderItemsResponse> {
+  client.execute(ListOrderItemsParameters {
+    AmazonOrderId: amazon_order_id,
+  })
 }
 
 /// Returns the next page of order items using the NextToken parameter.
@@ -130,44 +264,137 @@ pub fn ListOrderItemsByNextToken(
   client: &Client,
   next_token: String,
 ) -> MwsResult<ListOrderItemsResponse> {
-  let params = vec![("NextToken".to_string(), next_token)];
-  client
-    .request_xml(
-      Method::Post,
-      PATH,
-      VERSION,
-      "ListOrderItemsByNextToken",
-      params,
-    )
-    .map(|e: ListOrderItemsByNextTokenEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(ListOrderItemsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`ListOrderItemsByNextToken`](fn.ListOrderItemsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListOrderItemsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListOrderItemsResponse> {
+  client.execute(ListOrderItemsByNextTokenParameters {
+    NextToken: next_token,
+  })
 }
 
 #[cfg(test)]
 mod tests {
   use super::super::client::get_test_client;
   use super::*;
+  use client::ClientOptions;
   use dotenv::dotenv;
+  use std::sync::{Arc, Mutex};
+  use transport::{HttpRequest, HttpResponse, HttpTransport, StatusCode};
 
   #[test]
-  #[ignore]
   fn test_list_orders() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("orders_list_orders");
     let mut params = ListOrdersParameters::default();
     params.MarketplaceId.push("ATVPDKIKX0DER".to_string());
     params.CreatedAfter = Some("2016-11-01T04:00:00Z".parse().expect("parse created_after"));
     params.MaxResultsPerPage = Some(1);
     let res = ListOrders(&c, params).expect("ListOrders");
-    println!("{:#?}", res)
+    assert_eq!(res.Orders.len(), 1);
+    assert_eq!(res.Orders[0].AmazonOrderId, "058-1233752-8214740");
+    assert_eq!(res.Orders[0].OrderStatus, OrderStatus::Shipped);
+    assert!(res.NextToken.is_some());
+  }
+
+  #[test]
+  fn test_get_order_parameters() {
+    use SerializeMwsParams;
+
+    let pairs = GetOrderParameters {
+      AmazonOrderId: vec![
+        "058-1233752-8214740".to_string(),
+        "112-8095165-5463447".to_string(),
+      ],
+    }
+    .into_mws_params();
+    assert_eq!(
+      pairs,
+      vec![
+        (
+          "AmazonOrderId.Id.1".to_string(),
+          "058-1233752-8214740".to_string()
+        ),
+        (
+          "AmazonOrderId.Id.2".to_string(),
+          "112-8095165-5463447".to_string()
+        ),
+      ]
+    );
+  }
+
+  /// Answers `GetOrder` with an order for every requested id
+  struct GetOrderTransport {
+    requests: Arc<Mutex<Vec<Vec<String>>>>,
+  }
+
+  impl HttpTransport for GetOrderTransport {
+    fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+      let ids: Vec<String> = (1..)
+        .map(|i| request.parameter(&format!("AmazonOrderId.Id.{}", i)))
+        .take_while(Option::is_some)
+        .map(Option::unwrap)
+        .collect();
+      let orders: String = ids
+        .iter()
+        .map(|id| format!("<Order><AmazonOrderId>{}</AmazonOrderId></Order>", id))
+        .collect();
+      self.requests.lock().unwrap().push(ids);
+      Ok(HttpResponse::new(
+        StatusCode::Ok,
+        vec![],
+        format!(
+          "<GetOrderResponse><GetOrderResult><Orders>{}</Orders></GetOrderResult></GetOrderResponse>",
+          orders
+        ),
+      ))
+    }
+  }
+
+  #[test]
+  fn test_get_order_chunks() {
+    let requests = Arc::new(Mutex::new(vec![]));
+    let mut client = Client::with_transport(
+      ClientOptions {
+        endpoint: "mws.amazonservices.com".to_string(),
+        seller_id: "SELLER".to_string(),
+        aws_access_key_id: "KEY".to_string(),
+        secret_key: "SECRET".into(),
+        ..Default::default()
+      },
+      GetOrderTransport {
+        requests: requests.clone(),
+      },
+    );
+    client.set_throttler(None);
+
+    let ids: Vec<String> = (0..60).map(|i| format!("112-0000000-{:07}", i)).collect();
+    let res = GetOrder(&client, ids.clone()).expect("GetOrder");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].len(), GET_ORDER_MAX_IDS);
+    assert_eq!(requests[1].len(), 60 - GET_ORDER_MAX_IDS);
+    assert_eq!(requests.concat(), ids);
+    let order_ids: Vec<String> = res.Orders.into_iter().map(|o| o.AmazonOrderId).collect();
+    assert_eq!(order_ids, ids);
   }
 
   #[test]
-  #[ignore]
   fn test_list_order_items() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("orders_list_order_items");
     let res = ListOrderItems(&c, "112-8095165-5463447".to_string()).expect("ListOrderItems");
-    println!("{:#?}", res)
+    assert_eq!(res.AmazonOrderId, "112-8095165-5463447");
+    assert_eq!(res.OrderItems.len(), 1);
+    assert_eq!(res.OrderItems[0].SellerSKU, "CBA_OTF_1");
+    assert_eq!(res.OrderItems[0].QuantityOrdered, 1);
   }
 }
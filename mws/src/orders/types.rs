@@ -10,7 +10,12 @@ pub struct ShippingAddress {
   pub Name: String,
   pub AddressLine1: String,
   pub AddressLine2: String,
+  pub AddressLine3: Option<String>,
+  pub County: Option<String>,
+  pub District: Option<String>,
   pub Phone: Option<String>,
+  /// `Commercial` or `Residential`
+  pub AddressType: Option<String>,
 }
 
 #[allow(non_snake_case)]
@@ -20,6 +25,81 @@ pub struct CurrencyAmount {
   pub Amount: String,
 }
 
+/// A payment used to pay for a COD order
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct PaymentExecutionDetailItem {
+  /// The amount paid with `PaymentMethod`.
+  pub Payment: Option<CurrencyAmount>,
+  /// `COD`, `GC` (gift card) or `PointsAccount`
+  pub PaymentMethod: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct TaxClassification {
+  /// The type of tax, e.g. `VATNumber` or `CSTNumber`.
+  pub Name: String,
+  pub Value: String,
+}
+
+/// Tax information about the buyer, used to issue tax invoices (Turkey only)
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct BuyerTaxInfo {
+  pub CompanyLegalName: Option<String>,
+  pub TaxingRegion: Option<String>,
+  pub TaxClassifications: Vec<TaxClassification>,
+}
+
+/// Tax information about the marketplace (India only)
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct MarketplaceTaxInfo {
+  pub TaxClassifications: Vec<TaxClassification>,
+}
+
+/// Fulfillment instructions of the order
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct FulfillmentInstruction {
+  /// The store chain (ID) that should fulfill the order.
+  pub FulfillmentSupplySourceId: Option<String>,
+}
+
+/// The number of Amazon Points granted with the purchase of an item (Japan only)
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct PointsGranted {
+  pub PointsNumber: i32,
+  pub PointsMonetaryValue: Option<CurrencyAmount>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ProductInfo {
+  /// The total number of items included in the product ordered.
+  pub NumberOfItems: Option<i32>,
+}
+
+/// How taxes of an item are collected
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct TaxCollection {
+  /// `MarketplaceFacilitator`
+  pub Model: String,
+  /// `Amazon Services, Inc.`
+  pub ResponsibleParty: String,
+}
+
+/// Buyer information for a custom order from the Amazon Custom program
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct BuyerCustomizedInfo {
+  /// The location of a zip file containing the customization data.
+  pub CustomizedURL: String,
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
 pub struct Order {
@@ -49,8 +129,8 @@ pub struct Order {
   pub NumberOfItemsShipped: i32,
   /// The number of items unshipped.
   pub NumberOfItemsUnshipped: i32,
-
-  // pub PaymentExecutionDetail: ?
+  /// Information about sub-payment methods for a Cash On Delivery (COD) order.
+  pub PaymentExecutionDetail: Vec<PaymentExecutionDetailItem>,
   /// The payment method for the order.
   /// This response element is limited to Cash On Delivery (COD)
   /// and Convenience Store (CVS) payment methods.
@@ -59,8 +139,8 @@ pub struct Order {
   /// the PaymentMethodDetails response element to get payment
   /// method information.
   pub PaymentMethod: PaymentMethod,
-
-  // pub PaymentMethodDetails: ?
+  /// The payment method details of the order, e.g. `Standard` or `GiftCertificate`.
+  pub PaymentMethodDetails: Vec<String>,
   /// true if this is a replacement order.
   pub IsReplacementOrder: bool,
   /// The AmazonOrderId value for the order that is being replaced.
@@ -74,8 +154,8 @@ pub struct Order {
   /// The county of the buyer.
   /// This element is used only in the Brazil marketplace.
   pub BuyerCounty: String,
-
-  // pub BuyerTaxInfo: ?
+  /// The tax information about the buyer.
+  pub BuyerTaxInfo: Option<BuyerTaxInfo>,
   /// The shipment service level category of the order.
   /// ShipmentServiceLevelCategory values: Expedited, FreeEconomy, NextDay, SameDay, SecondDay, Scheduled, Standard
   pub ShipmentServiceLevelCategory: String,
@@ -132,6 +212,21 @@ pub struct Order {
   pub PromiseResponseDueDate: Option<DateTime<Utc>>,
   /// true if the Estimated Ship Date is set for the order.
   pub IsEstimatedShipDateSet: bool,
+  /// true if the order is a GlobalExpress order.
+  pub IsGlobalExpressEnabled: bool,
+  /// true if the item within this order was bought and re-sold by Amazon Business EU SARL
+  /// (ABEU).
+  pub IsSoldByAB: bool,
+  /// The seller's friendly name registered in the marketplace.
+  pub SellerDisplayName: Option<String>,
+  /// The tax information about the marketplace.
+  pub MarketplaceTaxInfo: Option<MarketplaceTaxInfo>,
+  /// The recommended location for the seller to ship the items from.
+  pub DefaultShipFromLocationAddress: Option<ShippingAddress>,
+  /// Contains the instructions about the fulfillment like where should it be fulfilled from.
+  pub FulfillmentInstruction: Option<FulfillmentInstruction>,
+  /// true if the buyer picks the order up in a store (In-Store PickUp).
+  pub IsISPU: bool,
 }
 
 str_enum! {
@@ -212,6 +307,43 @@ pub struct OrderItem {
   pub ShippingPrice: Option<CurrencyAmount>,
   pub ShippingDiscount: Option<CurrencyAmount>,
   pub ShippingTax: Option<CurrencyAmount>,
+  pub ShippingDiscountTax: Option<CurrencyAmount>,
+  pub PromotionDiscountTax: Option<CurrencyAmount>,
+  /// The number and value of Amazon Points granted with the purchase of the item.
+  pub PointsGranted: Option<PointsGranted>,
+  pub ProductInfo: Option<ProductInfo>,
+  /// The promotion identifiers provided by the seller when the promotions were created.
+  pub PromotionIds: Vec<String>,
+  /// The fee charged for COD service.
+  pub CODFee: Option<CurrencyAmount>,
+  /// The discount on the COD fee.
+  pub CODFeeDiscount: Option<CurrencyAmount>,
+  /// true if the item is a gift.
+  pub IsGift: bool,
+  pub GiftMessageText: Option<String>,
+  pub GiftWrapLevel: Option<String>,
+  /// The condition of the item as described by the seller.
+  pub ConditionNote: Option<String>,
+  /// `New`, `Used`, `Collectible`, `Refurbished`, `Preorder` or `Club`
+  pub ConditionId: Option<String>,
+  /// The subcondition of the item, e.g. `Mint` or `Good`.
+  pub ConditionSubtypeId: Option<String>,
+  /// The start of the time window of a scheduled delivery.
+  pub ScheduledDeliveryStartDate: Option<DateTime<Utc>>,
+  /// The end of the time window of a scheduled delivery.
+  pub ScheduledDeliveryEndDate: Option<DateTime<Utc>>,
+  /// `BusinessPrice` if the item price is only available to Amazon Business buyers.
+  pub PriceDesignation: Option<String>,
+  /// Information about withheld taxes.
+  pub TaxCollection: Option<TaxCollection>,
+  /// true if the product type of the item requires a serial number on shipment.
+  pub SerialNumberRequired: bool,
+  /// true if the product is enrolled in the Transparency program.
+  pub IsTransparency: bool,
+  /// The IOSS number of the seller, EU marketplaces only.
+  pub IossNumber: Option<String>,
+  /// Buyer information for custom orders from the Amazon Custom program.
+  pub BuyerCustomizedInfo: Option<BuyerCustomizedInfo>,
 }
 
 #[cfg(test)]
@@ -316,6 +448,8 @@ mod tests {
           Name: "First Last".to_string(),
           AddressLine1: "8888 J AVE".to_string(),
           AddressLine2: "".to_string(),
+          AddressType: Some("Residential".to_string()),
+          ..Default::default()
         }),
         BuyerCounty: "".to_string(),
         EarliestDeliveryDate: Some("2018-12-20T08:00:00Z".parse().unwrap()),
@@ -329,6 +463,8 @@ mod tests {
         PurchaseOrderNumber: "".to_owned(),
         ShippedByAmazonTFM: false,
         TFMShipmentStatus: "".to_owned(),
+        PaymentMethodDetails: vec!["Standard".to_string()],
+        ..Default::default()
       }
     );
   }
@@ -370,6 +506,143 @@ mod tests {
         ShippingPrice: None,
         ShippingDiscount: None,
         ShippingTax: None,
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_decode_order_details() {
+    test_decode!(
+      Order,
+      r#"
+        <AmazonOrderId>503-0000000-0000000</AmazonOrderId>
+        <OrderStatus>Unshipped</OrderStatus>
+        <PaymentMethod>COD</PaymentMethod>
+        <PaymentExecutionDetail>
+          <PaymentExecutionDetailItem>
+            <Payment>
+              <CurrencyCode>JPY</CurrencyCode>
+              <Amount>10</Amount>
+            </Payment>
+            <PaymentMethod>PointsAccount</PaymentMethod>
+          </PaymentExecutionDetailItem>
+          <PaymentExecutionDetailItem>
+            <Payment>
+              <CurrencyCode>JPY</CurrencyCode>
+              <Amount>30</Amount>
+            </Payment>
+            <PaymentMethod>COD</PaymentMethod>
+          </PaymentExecutionDetailItem>
+        </PaymentExecutionDetail>
+        <BuyerTaxInfo>
+          <CompanyLegalName>Company</CompanyLegalName>
+          <TaxingRegion>Istanbul</TaxingRegion>
+          <TaxClassifications>
+            <TaxClassification>
+              <Name>VKN</Name>
+              <Value>1234567890</Value>
+            </TaxClassification>
+          </TaxClassifications>
+        </BuyerTaxInfo>
+        <IsGlobalExpressEnabled>true</IsGlobalExpressEnabled>
+        <SellerDisplayName>Seller</SellerDisplayName>"#,
+      Order {
+        AmazonOrderId: "503-0000000-0000000".to_string(),
+        OrderStatus: OrderStatus::Unshipped,
+        PaymentMethod: PaymentMethod::COD,
+        PaymentExecutionDetail: vec![
+          PaymentExecutionDetailItem {
+            Payment: Some(CurrencyAmount {
+              CurrencyCode: "JPY".to_string(),
+              Amount: "10".to_string(),
+            }),
+            PaymentMethod: "PointsAccount".to_string(),
+          },
+          PaymentExecutionDetailItem {
+            Payment: Some(CurrencyAmount {
+              CurrencyCode: "JPY".to_string(),
+              Amount: "30".to_string(),
+            }),
+            PaymentMethod: "COD".to_string(),
+          },
+        ],
+        BuyerTaxInfo: Some(BuyerTaxInfo {
+          CompanyLegalName: Some("Company".to_string()),
+          TaxingRegion: Some("Istanbul".to_string()),
+          TaxClassifications: vec![TaxClassification {
+            Name: "VKN".to_string(),
+            Value: "1234567890".to_string(),
+          }],
+        }),
+        IsGlobalExpressEnabled: true,
+        SellerDisplayName: Some("Seller".to_string()),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_decode_orderitem_details() {
+    test_decode!(
+      OrderItem,
+      r#"<OrderItemId>68828574383266</OrderItemId>
+        <QuantityOrdered>1</QuantityOrdered>
+        <PointsGranted>
+          <PointsNumber>10</PointsNumber>
+          <PointsMonetaryValue>
+            <CurrencyCode>JPY</CurrencyCode>
+            <Amount>10.00</Amount>
+          </PointsMonetaryValue>
+        </PointsGranted>
+        <ProductInfo>
+          <NumberOfItems>12</NumberOfItems>
+        </ProductInfo>
+        <PromotionIds>
+          <PromotionId>FREESHIP</PromotionId>
+          <PromotionId>BOGO</PromotionId>
+        </PromotionIds>
+        <IsGift>true</IsGift>
+        <ConditionId>Used</ConditionId>
+        <ConditionSubtypeId>Mint</ConditionSubtypeId>
+        <ScheduledDeliveryStartDate>2013-09-09T01:30:00Z</ScheduledDeliveryStartDate>
+        <TaxCollection>
+          <Model>MarketplaceFacilitator</Model>
+          <ResponsibleParty>Amazon Services, Inc.</ResponsibleParty>
+        </TaxCollection>
+        <SerialNumberRequired>true</SerialNumberRequired>
+        <BuyerCustomizedInfo>
+          <CustomizedURL>https://zme-caps.amazon.com/t/bR6qHkzSOxuB/J8nbWhze0Bd3DkajkOdY-XQbWkFralegp2sr_QZiKEE/1</CustomizedURL>
+        </BuyerCustomizedInfo>"#,
+      OrderItem {
+        OrderItemId: "68828574383266".to_string(),
+        QuantityOrdered: 1,
+        PointsGranted: Some(PointsGranted {
+          PointsNumber: 10,
+          PointsMonetaryValue: Some(CurrencyAmount {
+            CurrencyCode: "JPY".to_string(),
+            Amount: "10.00".to_string(),
+          }),
+        }),
+        ProductInfo: Some(ProductInfo {
+          NumberOfItems: Some(12),
+        }),
+        PromotionIds: vec!["FREESHIP".to_string(), "BOGO".to_string()],
+        IsGift: true,
+        ConditionId: Some("Used".to_string()),
+        ConditionSubtypeId: Some("Mint".to_string()),
+        ScheduledDeliveryStartDate: Some("2013-09-09T01:30:00Z".parse().unwrap()),
+        TaxCollection: Some(TaxCollection {
+          Model: "MarketplaceFacilitator".to_string(),
+          ResponsibleParty: "Amazon Services, Inc.".to_string(),
+        }),
+        SerialNumberRequired: true,
+        BuyerCustomizedInfo: Some(BuyerCustomizedInfo {
+          CustomizedURL:
+            "https://zme-caps.amazon.com/t/bR6qHkzSOxuB/J8nbWhze0Bd3DkajkOdY-XQbWkFralegp2sr_QZiKEE/1"
+              .to_string(),
+        }),
+        ..Default::default()
       }
     );
   }
@@ -0,0 +1,311 @@
+//! NextToken pagination
+//!
+//! List operations return the first page of results and a `NextToken` which is passed to the
+//! matching `...ByNextToken` operation to get the next page. [`pages`](fn.pages.html) and
+//! [`items`](fn.items.html) follow the tokens until the last page:
+//!
+//! ```ignore
+//! let first = orders::ListOrders(&client, params);
+//! for order in pagination::items(&client, first) {
+//!   let order = order?;
+//! }
+//! ```
+//!
+//! The `...ByNextToken` requests go through the `Client` or `AsyncClient` like any other
+//! request, so they wait for its throttler, in the bucket of the list operation they continue,
+//! and are retried according to its retry policy. Iteration stops after the first error.
+
+use async_client::{AsyncClient, MwsFuture};
+use client::Client;
+use futures::{stream, Future, Stream};
+use result::{MwsError, MwsResult};
+use std::vec;
+
+/// A page of a list operation
+pub trait NextTokenResponse: Sized {
+  type Item;
+
+  /// Returns the token of the next page, or `None` on the last page.
+  fn next_token(&self) -> Option<&str>;
+
+  /// Returns the items of the page.
+  fn into_items(self) -> Vec<Self::Item>;
+
+  /// Requests the page identified by `next_token`.
+  fn by_next_token(client: &Client, next_token: String) -> MwsResult<Self>;
+
+  /// Asynchronous version of `by_next_token`.
+  fn by_next_token_async(client: &AsyncClient, next_token: String) -> MwsFuture<Self>;
+}
+
+enum State<T> {
+  First(MwsResult<T>),
+  Next(String),
+  Done,
+}
+
+/// Iterator over the pages of a list operation, see [`pages`](fn.pages.html)
+pub struct Pages<'a, T> {
+  client: &'a Client,
+  state: State<T>,
+}
+
+/// Returns an iterator over `first` and the following pages.
+pub fn pages<T: NextTokenResponse>(client: &Client, first: MwsResult<T>) -> Pages<T> {
+  Pages {
+    client: client,
+    state: State::First(first),
+  }
+}
+
+impl<'a, T: NextTokenResponse> Iterator for Pages<'a, T> {
+  type Item = MwsResult<T>;
+
+  fn next(&mut self) -> Option<MwsResult<T>> {
+    let page = match ::std::mem::replace(&mut self.state, State::Done) {
+      State::First(page) => page,
+      State::Next(next_token) => T::by_next_token(self.client, next_token),
+      State::Done => return None,
+    };
+    if let Ok(ref page) = page {
+      if let Some(next_token) = page.next_token() {
+        self.state = State::Next(next_token.to_string());
+      }
+    }
+    Some(page)
+  }
+}
+
+/// Iterator over the items of all pages of a list operation, see [`items`](fn.items.html)
+pub struct Items<'a, T: NextTokenResponse> {
+  pages: Pages<'a, T>,
+  items: vec::IntoIter<T::Item>,
+}
+
+/// Returns an iterator over the items of `first` and the following pages.
+pub fn items<T: NextTokenResponse>(client: &Client, first: MwsResult<T>) -> Items<T> {
+  Items {
+    pages: pages(client, first),
+    items: vec![].into_iter(),
+  }
+}
+
+impl<'a, T: NextTokenResponse> Iterator for Items<'a, T> {
+  type Item = MwsResult<T::Item>;
+
+  fn next(&mut self) -> Option<MwsResult<T::Item>> {
+    loop {
+      if let Some(item) = self.items.next() {
+        return Some(Ok(item));
+      }
+      match self.pages.next() {
+        Some(Ok(page)) => self.items = page.into_items().into_iter(),
+        Some(Err(err)) => return Some(Err(err)),
+        None => return None,
+      }
+    }
+  }
+}
+
+enum AsyncState<T> {
+  First(MwsFuture<T>),
+  Next(String),
+}
+
+/// Returns a stream of `first` and the following pages.
+pub fn pages_async<'a, T>(
+  client: &'a AsyncClient,
+  first: MwsFuture<T>,
+) -> Box<dyn Stream<Item = T, Error = MwsError> + 'a>
+where
+  T: NextTokenResponse + 'static,
+{
+  Box::new(stream::unfold(
+    Some(AsyncState::First(first)),
+    move |state| {
+      let page = match state? {
+        AsyncState::First(page) => page,
+        AsyncState::Next(next_token) => T::by_next_token_async(client, next_token),
+      };
+      Some(page.map(|page| {
+        let next = page
+          .next_token()
+          .map(|next_token| AsyncState::Next(next_token.to_string()));
+        (page, next)
+      }))
+    },
+  ))
+}
+
+/// Returns a stream of the items of `first` and the following pages.
+pub fn items_async<'a, T>(
+  client: &'a AsyncClient,
+  first: MwsFuture<T>,
+) -> Box<dyn Stream<Item = T::Item, Error = MwsError> + 'a>
+where
+  T: NextTokenResponse + 'static,
+  T::Item: 'a,
+{
+  Box::new(
+    pages_async(client, first)
+      .map(|page| stream::iter_ok(page.into_items()))
+      .flatten(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use client::ClientOptions;
+  use futures::future;
+  use orders::{ListOrders, ListOrdersAsync, ListOrdersParameters};
+  use retry::RetryPolicy;
+  use std::sync::{Arc, Mutex};
+  use std::time::Duration;
+  use throttle::{Quota, ThrottleMode, Throttler};
+  use transport::{AsyncHttpTransport, HttpRequest, HttpResponse, HttpTransport, StatusCode};
+
+  /// Serves three pages of `ListOrders`
+  struct OrderPages {
+    actions: Arc<Mutex<Vec<String>>>,
+  }
+
+  impl HttpTransport for OrderPages {
+    fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+      let mut actions = self.actions.lock().unwrap();
+      let (action, page) = if request.url.contains("Action=ListOrdersByNextToken") {
+        let page = if request.url.contains("NextToken=2") {
+          2
+        } else {
+          1
+        };
+        ("ListOrdersByNextToken", page)
+      } else {
+        ("ListOrders", 0)
+      };
+      actions.push(action.to_string());
+      let next_token = if page < 2 {
+        format!("<NextToken>{}</NextToken>", page + 1)
+      } else {
+        String::new()
+      };
+      let body = format!(
+        r#"<{action}Response><{action}Result><Orders><Order><AmazonOrderId>{page}-1</AmazonOrderId></Order><Order><AmazonOrderId>{page}-2</AmazonOrderId></Order></Orders>{next_token}</{action}Result></{action}Response>"#,
+        action = action,
+        page = page,
+        next_token = next_token
+      );
+      Ok(HttpResponse::new(StatusCode::Ok, vec![], body))
+    }
+  }
+
+  /// Serves `OrderPages` asynchronously and rejects the first `ListOrdersByNextToken`
+  /// request with `RequestThrottled`
+  struct ThrottledOrderPages {
+    pages: OrderPages,
+    throttled: Mutex<bool>,
+  }
+
+  impl AsyncHttpTransport for ThrottledOrderPages {
+    fn send(&self, request: HttpRequest) -> MwsFuture<HttpResponse> {
+      let mut throttled = self.throttled.lock().unwrap();
+      if !*throttled && request.url.contains("Action=ListOrdersByNextToken") {
+        *throttled = true;
+        self
+          .pages
+          .actions
+          .lock()
+          .unwrap()
+          .push("RequestThrottled".to_string());
+        return Box::new(future::ok(HttpResponse::new(
+          StatusCode::ServiceUnavailable,
+          vec![],
+          r#"<ErrorResponse><Error><Type>Sender</Type><Code>RequestThrottled</Code><Message>Request is throttled</Message></Error><RequestId>REQUEST</RequestId></ErrorResponse>"#,
+        )));
+      }
+      Box::new(future::result(self.pages.send(request)))
+    }
+  }
+
+  #[test]
+  fn test_items() {
+    let actions = Arc::new(Mutex::new(vec![]));
+    let mut client = Client::with_transport(
+      ClientOptions {
+        endpoint: "mws.amazonservices.com".to_string(),
+        seller_id: "SELLER".to_string(),
+        aws_access_key_id: "KEY".to_string(),
+        secret_key: "SECRET".into(),
+        ..Default::default()
+      },
+      OrderPages {
+        actions: actions.clone(),
+      },
+    );
+    client.set_throttler(None);
+
+    let first = ListOrders(&client, ListOrdersParameters::default());
+    let ids: Vec<String> = items(&client, first)
+      .map(|order| order.expect("order").AmazonOrderId)
+      .collect();
+    assert_eq!(ids, vec!["0-1", "0-2", "1-1", "1-2", "2-1", "2-2"]);
+    assert_eq!(
+      *actions.lock().unwrap(),
+      vec![
+        "ListOrders",
+        "ListOrdersByNextToken",
+        "ListOrdersByNextToken"
+      ]
+    );
+  }
+
+  #[test]
+  fn test_items_async() {
+    let actions = Arc::new(Mutex::new(vec![]));
+    let mut client = AsyncClient::with_transport(
+      ClientOptions {
+        endpoint: "mws.amazonservices.com".to_string(),
+        seller_id: "SELLER".to_string(),
+        aws_access_key_id: "KEY".to_string(),
+        secret_key: "SECRET".into(),
+        retry_policy: RetryPolicy {
+          base_delay: Duration::from_millis(1),
+          throttled_delay: Duration::from_millis(1),
+          jitter: 0.0,
+          ..RetryPolicy::new(3)
+        },
+        ..Default::default()
+      },
+      ThrottledOrderPages {
+        pages: OrderPages {
+          actions: actions.clone(),
+        },
+        throttled: Mutex::new(false),
+      },
+    );
+    let throttler = Throttler::new(ThrottleMode::Wait);
+    throttler.set_quota(
+      "/Orders/2013-09-01",
+      "ListOrders",
+      Quota::new(1, Duration::from_millis(5)),
+    );
+    client.set_throttler(Some(Arc::new(throttler)));
+
+    let first = ListOrdersAsync(&client, ListOrdersParameters::default());
+    let ids: Vec<String> = items_async(&client, first)
+      .map(|order| order.AmazonOrderId)
+      .collect()
+      .wait()
+      .expect("orders");
+    assert_eq!(ids, vec!["0-1", "0-2", "1-1", "1-2", "2-1", "2-2"]);
+    assert_eq!(
+      *actions.lock().unwrap(),
+      vec![
+        "ListOrders",
+        "RequestThrottled",
+        "ListOrdersByNextToken",
+        "ListOrdersByNextToken"
+      ]
+    );
+  }
+}
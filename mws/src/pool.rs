@@ -0,0 +1,274 @@
+//! Clients for many sellers
+//!
+//! A [`ClientPool`](struct.ClientPool.html) hands out one client per seller and region.
+//! Credentials come from a [`CredentialProvider`](../credentials/trait.CredentialProvider.html),
+//! endpoints from [`constants::get_region`](../constants/fn.get_region.html).
+//! All clients of a pool share the HTTP transport, the middleware and a throttler,
+//! which keeps separate quotas for every seller and region. Clients of the same region share the
+//! clock skew measured from the responses of the region.
+//!
+//! ```ignore
+//! let pool = ClientPool::new(EnvCredentialProvider::new());
+//! let client = pool.get("A1B2C3", constants::REGION_ID_EU)?;
+//! let res = orders::ListOrders(&client, params)?;
+//! ```
+
+use client::{Client, ClientOptions};
+use clock::ServerClock;
+use constants;
+use credentials::CredentialProvider;
+use middleware::Middleware;
+use reqwest;
+use result::{MwsError, MwsResult};
+use retry::RetryPolicy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use throttle::Throttler;
+use transport::HttpTransport;
+
+pub struct ClientPool {
+  provider: Box<dyn CredentialProvider>,
+  transport: Arc<dyn HttpTransport>,
+  throttler: Option<Arc<Throttler>>,
+  middleware: Vec<Arc<dyn Middleware>>,
+  retry_policy: RetryPolicy,
+  endpoints: HashMap<String, String>,
+  clocks: Mutex<HashMap<String, Arc<ServerClock>>>,
+  clients: Mutex<HashMap<(String, String), Arc<Client>>>,
+}
+
+impl ClientPool {
+  pub fn new<P>(provider: P) -> ClientPool
+  where
+    P: CredentialProvider + 'static,
+  {
+    ClientPool::with_transport(provider, reqwest::Client::new())
+  }
+
+  /// Constructs a pool whose clients send requests through `transport`.
+  pub fn with_transport<P, T>(provider: P, transport: T) -> ClientPool
+  where
+    P: CredentialProvider + 'static,
+    T: HttpTransport + 'static,
+  {
+    ClientPool {
+      provider: Box::new(provider),
+      transport: Arc::new(transport),
+      throttler: Some(Arc::new(Throttler::default())),
+      middleware: vec![],
+      retry_policy: RetryPolicy::default(),
+      endpoints: HashMap::new(),
+      clocks: Mutex::new(HashMap::new()),
+      clients: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Replaces the throttler shared by the clients, or disables client-side throttling with `None`.
+  pub fn set_throttler(&mut self, throttler: Option<Arc<Throttler>>) {
+    self.throttler = throttler;
+    self.clear();
+  }
+
+  /// Sets the retry policy of the clients.
+  pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+    self.retry_policy = retry_policy;
+    self.clear();
+  }
+
+  /// Adds middleware to the clients, see `Client::add_middleware`.
+  pub fn add_middleware<M>(&mut self, middleware: M)
+  where
+    M: Middleware + 'static,
+  {
+    self.middleware.push(Arc::new(middleware));
+    self.clear();
+  }
+
+  /// Sends the requests of `region_id` to `endpoint` instead of the region's endpoint.
+  pub fn set_endpoint<E: Into<String>>(&mut self, region_id: &str, endpoint: E) {
+    self
+      .endpoints
+      .insert(region_id.to_string(), endpoint.into());
+    self.clear();
+  }
+
+  /// Returns the client of `seller_id` in `region_id`, creating it on first use.
+  pub fn get(&self, seller_id: &str, region_id: &str) -> MwsResult<Arc<Client>> {
+    let key = (seller_id.to_string(), region_id.to_string());
+    if let Some(client) = self.clients.lock().unwrap().get(&key) {
+      return Ok(client.clone());
+    }
+
+    let client = Arc::new(self.create(seller_id, region_id)?);
+    Ok(
+      self
+        .clients
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert(client)
+        .clone(),
+    )
+  }
+
+  /// Drops the client of `seller_id` in `region_id`,
+  /// e.g. after the seller revoked or renewed the authorization.
+  pub fn remove(&self, seller_id: &str, region_id: &str) {
+    self
+      .clients
+      .lock()
+      .unwrap()
+      .remove(&(seller_id.to_string(), region_id.to_string()));
+  }
+
+  /// Drops all clients, they are created again with fresh credentials.
+  pub fn clear(&self) {
+    self.clients.lock().unwrap().clear();
+  }
+
+  fn create(&self, seller_id: &str, region_id: &str) -> MwsResult<Client> {
+    let endpoint = match self.endpoints.get(region_id) {
+      Some(endpoint) => endpoint.clone(),
+      None => constants::get_region(region_id)
+        .map(|region| region.endpoint.to_string())
+        .ok_or_else(|| MwsError::UnknownRegion(region_id.to_string()))?,
+    };
+    let developer = self.provider.developer_credentials(region_id)?;
+    let options = ClientOptions {
+      endpoint: endpoint,
+      seller_id: seller_id.to_string(),
+      mws_auth_token: self.provider.mws_auth_token(seller_id, region_id)?,
+      aws_access_key_id: developer.aws_access_key_id,
+      secret_key: developer.secret_key,
+      retry_policy: self.retry_policy.clone(),
+      clock: self
+        .clocks
+        .lock()
+        .unwrap()
+        .entry(region_id.to_string())
+        .or_insert_with(Default::default)
+        .clone(),
+      ..Default::default()
+    };
+    Ok(Client::with_shared(
+      options,
+      self.transport.clone(),
+      self.throttler.clone(),
+      self.middleware.clone(),
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use client::Method;
+  use credentials::FileCredentialProvider;
+  use std::time::Duration;
+  use throttle::{Quota, ThrottleMode};
+  use transport::{HttpRequest, HttpResponse, StatusCode};
+
+  struct Recorder {
+    urls: Arc<Mutex<Vec<String>>>,
+  }
+
+  impl HttpTransport for Recorder {
+    fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+      self.urls.lock().unwrap().push(request.url);
+      Ok(HttpResponse::new(StatusCode::Ok, vec![], "OK"))
+    }
+  }
+
+  #[test]
+  fn test_pool() {
+    let provider = FileCredentialProvider::parse(
+      r#"{
+        "developers": {
+          "na": { "aws_access_key_id": "NA_KEY", "secret_key": "NA_SECRET" },
+          "eu": { "aws_access_key_id": "EU_KEY", "secret_key": "EU_SECRET" }
+        },
+        "sellers": [
+          { "seller_id": "SELLER", "region_id": "na", "mws_auth_token": "TOKEN" },
+          { "seller_id": "SELLER", "region_id": "eu", "mws_auth_token": null }
+        ]
+      }"#,
+    )
+    .expect("parse");
+    let urls = Arc::new(Mutex::new(vec![]));
+    let pool = ClientPool::with_transport(provider, Recorder { urls: urls.clone() });
+
+    let na = pool.get("SELLER", "na").expect("na");
+    assert!(Arc::ptr_eq(&na, &pool.get("SELLER", "na").expect("na")));
+    let eu = pool.get("SELLER", "eu").expect("eu");
+    assert!(!Arc::ptr_eq(&na, &eu));
+    assert!(pool.get("OTHER", "na").is_err());
+    match pool.get("SELLER", "xx") {
+      Err(MwsError::UnknownRegion(ref id)) if id == "xx" => {}
+      res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+
+    for client in &[na, eu] {
+      client
+        .request_raw(
+          Method::Post,
+          "/Orders/2013-09-01",
+          "2013-09-01",
+          "GetServiceStatus",
+          (),
+        )
+        .expect("send request");
+    }
+    let urls = urls.lock().unwrap();
+    assert!(urls[0].starts_with("https://mws.amazonservices.com/"));
+    assert!(urls[0].contains("AWSAccessKeyId=NA_KEY"));
+    assert!(urls[0].contains("MWSAuthToken=TOKEN"));
+    assert!(urls[1].starts_with("https://mws-eu.amazonservices.com/"));
+    assert!(urls[1].contains("AWSAccessKeyId=EU_KEY"));
+    assert!(!urls[1].contains("MWSAuthToken"));
+  }
+
+  #[test]
+  fn test_pool_throttles_regions_separately() {
+    let provider = FileCredentialProvider::parse(
+      r#"{
+        "developers": {
+          "na": { "aws_access_key_id": "NA_KEY", "secret_key": "NA_SECRET" },
+          "eu": { "aws_access_key_id": "EU_KEY", "secret_key": "EU_SECRET" }
+        },
+        "sellers": [
+          { "seller_id": "SELLER", "region_id": "na", "mws_auth_token": null },
+          { "seller_id": "SELLER", "region_id": "eu", "mws_auth_token": null }
+        ]
+      }"#,
+    )
+    .expect("parse");
+    let throttler = Throttler::new(ThrottleMode::Reject);
+    throttler.set_quota(
+      "/Orders/2013-09-01",
+      "GetServiceStatus",
+      Quota::new(1, Duration::from_secs(3600)),
+    );
+    let urls = Arc::new(Mutex::new(vec![]));
+    let mut pool = ClientPool::with_transport(provider, Recorder { urls: urls.clone() });
+    pool.set_throttler(Some(Arc::new(throttler)));
+
+    let send = |client: &Client| {
+      client.request_raw(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "GetServiceStatus",
+        (),
+      )
+    };
+    let na = pool.get("SELLER", "na").expect("na");
+    let eu = pool.get("SELLER", "eu").expect("eu");
+    send(&na).expect("na");
+    send(&eu).expect("eu");
+    match send(&na) {
+      Err(MwsError::Throttled { .. }) => {}
+      res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+    assert_eq!(urls.lock().unwrap().len(), 2);
+  }
+}
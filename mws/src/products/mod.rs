@@ -2,14 +2,19 @@
 //!
 //! [Reference](http://docs.developer.amazonservices.com/en_US/products/Products_Overview.html)
 
-use client::{Client, Method};
+use async_client::{AsyncClient, MwsFuture};
+use client::Client;
+use futures::{future, Future};
 use result::MwsResult;
+use xmlhelper::decode::{FromXmlStream, XmlEventStream};
 
 pub mod types;
 pub use self::types::*;
 
-static PATH: &'static str = "/Products/2011-10-01";
-static VERSION: &'static str = "2011-10-01";
+pub(crate) const PATH: &'static str = "/Products/2011-10-01";
+pub(crate) const VERSION: &'static str = "2011-10-01";
+
+get_service_status!(PATH, VERSION);
 
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
@@ -33,21 +38,79 @@ pub struct GetLowestPricedOffersForSKUParameters {
   pub ItemCondition: ItemCondition,
 }
 
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetLowestPricedOffersForSKU",
+  GetLowestPricedOffersForSKUParameters
+    => GetLowestPricedOffersForSKUResponseEnvelope<GetLowestPricedOffersForSKUResponse>,
+  idempotent: true,
+  form: true
+);
+
 #[allow(non_snake_case)]
 pub fn GetLowestPricedOffersForSKU(
   client: &Client,
   params: GetLowestPricedOffersForSKUParameters,
 ) -> MwsResult<GetLowestPricedOffersForSKUResponse> {
-  client
-    .request_xml_with_form(
-      Method::Post,
-      PATH,
-      VERSION,
-      "GetLowestPricedOffersForSKU",
-      params,
-    )
-    .map(|e: GetLowestPricedOffersForSKUResponseEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetLowestPricedOffersForSKU`](fn.GetLowestPricedOffersForSKU.html).
+#[allow(non_snake_case)]
+pub fn GetLowestPricedOffersForSKUAsync(
+  client: &AsyncClient,
+  params: GetLowestPricedOffersForSKUParameters,
+) -> MwsFuture<GetLowestPricedOffersForSKUResponse> {
+  client.execute(params)
+}
+
+/// The response of `GetLowestPricedOffersForASIN`, `Identifier.ASIN` is set instead of
+/// `Identifier.SellerSKU`
+pub type GetLowestPricedOffersForASINResponse = GetLowestPricedOffersForSKUResponse;
+
+response_envelope_type!(
+  GetLowestPricedOffersForASINResponseEnvelope<GetLowestPricedOffersForASINResponse>,
+  "GetLowestPricedOffersForASINResponse",
+  "GetLowestPricedOffersForASINResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetLowestPricedOffersForASINParameters {
+  pub MarketplaceId: String,
+  pub ASIN: String,
+  pub ItemCondition: ItemCondition,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetLowestPricedOffersForASIN",
+  GetLowestPricedOffersForASINParameters
+    => GetLowestPricedOffersForASINResponseEnvelope<GetLowestPricedOffersForASINResponse>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the lowest priced offers for a single product, based on ASIN.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetLowestPricedOffersForASIN.html)
+#[allow(non_snake_case)]
+pub fn GetLowestPricedOffersForASIN(
+  client: &Client,
+  params: GetLowestPricedOffersForASINParameters,
+) -> MwsResult<GetLowestPricedOffersForASINResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetLowestPricedOffersForASIN`](fn.GetLowestPricedOffersForASIN.html).
+#[allow(non_snake_case)]
+pub fn GetLowestPricedOffersForASINAsync(
+  client: &AsyncClient,
+  params: GetLowestPricedOffersForASINParameters,
+) -> MwsFuture<GetLowestPricedOffersForASINResponse> {
+  client.execute(params)
 }
 
 response_envelope_batch_type!(
@@ -73,22 +136,771 @@ pub struct GetMyPriceForASINResult {
   #[from_xml_stream(from_attr = "status")]
   pub Status: String,
   pub Product: product::Product,
+  /// Set if `Status` is not `Success`.
+  pub Error: Option<ProductError>,
 }
 
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetMyPriceForASIN",
+  GetMyPriceForASINParameters => GetMyPriceForASINResponseEnvelope<Vec<GetMyPriceForASINResult>>,
+  idempotent: true,
+  form: true
+);
+
 #[allow(non_snake_case)]
 pub fn GetMyPriceForASIN(
   client: &Client,
   params: GetMyPriceForASINParameters,
 ) -> MwsResult<Vec<GetMyPriceForASINResult>> {
-  client
-    .request_xml_with_form(Method::Post, PATH, VERSION, "GetMyPriceForASIN", params)
-    .map(|e: GetMyPriceForASINResponseEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetMyPriceForASIN`](fn.GetMyPriceForASIN.html).
+#[allow(non_snake_case)]
+pub fn GetMyPriceForASINAsync(
+  client: &AsyncClient,
+  params: GetMyPriceForASINParameters,
+) -> MwsFuture<Vec<GetMyPriceForASINResult>> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetMyPriceForSKUResponseEnvelope<GetMyPriceForSKUResult>,
+  "GetMyPriceForSKUResponse",
+  "GetMyPriceForSKUResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetMyPriceForSKUParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "SellerSKU")]
+  pub SellerSKUList: Vec<String>,
+  pub ItemCondition: Option<ItemCondition>,
+}
+
+/// The result of a SellerSKU of `GetMyPriceForSKU`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetMyPriceForSKUResult {
+  #[from_xml_stream(from_attr = "SellerSKU")]
+  pub SellerSKU: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetMyPriceForSKU",
+  GetMyPriceForSKUParameters => GetMyPriceForSKUResponseEnvelope<Vec<GetMyPriceForSKUResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns pricing information for your own offer listings, based on SellerSKU.
+///
+/// Up to 20 SellerSKUs can be requested per call.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetMyPriceForSKU.html)
+#[allow(non_snake_case)]
+pub fn GetMyPriceForSKU(
+  client: &Client,
+  params: GetMyPriceForSKUParameters,
+) -> MwsResult<Vec<GetMyPriceForSKUResult>> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetMyPriceForSKU`](fn.GetMyPriceForSKU.html).
+#[allow(non_snake_case)]
+pub fn GetMyPriceForSKUAsync(
+  client: &AsyncClient,
+  params: GetMyPriceForSKUParameters,
+) -> MwsFuture<Vec<GetMyPriceForSKUResult>> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetCompetitivePricingForSKUResponseEnvelope<GetCompetitivePricingForSKUResult>,
+  "GetCompetitivePricingForSKUResponse",
+  "GetCompetitivePricingForSKUResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetCompetitivePricingForSKUParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "SellerSKU")]
+  pub SellerSKUList: Vec<String>,
+}
+
+/// The result of a SellerSKU of `GetCompetitivePricingForSKU`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetCompetitivePricingForSKUResult {
+  #[from_xml_stream(from_attr = "SellerSKU")]
+  pub SellerSKU: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetCompetitivePricingForSKU",
+  GetCompetitivePricingForSKUParameters
+    => GetCompetitivePricingForSKUResponseEnvelope<Vec<GetCompetitivePricingForSKUResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the current competitive price of a product, based on SellerSKU.
+///
+/// The competitive prices are in `Product.CompetitivePricing`. Up to 20 SellerSKUs can be
+/// requested per call.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetCompetitivePricingForSKU.html)
+#[allow(non_snake_case)]
+pub fn GetCompetitivePricingForSKU(
+  client: &Client,
+  params: GetCompetitivePricingForSKUParameters,
+) -> MwsResult<Vec<GetCompetitivePricingForSKUResult>> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetCompetitivePricingForSKU`](fn.GetCompetitivePricingForSKU.html).
+#[allow(non_snake_case)]
+pub fn GetCompetitivePricingForSKUAsync(
+  client: &AsyncClient,
+  params: GetCompetitivePricingForSKUParameters,
+) -> MwsFuture<Vec<GetCompetitivePricingForSKUResult>> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetCompetitivePricingForASINResponseEnvelope<GetCompetitivePricingForASINResult>,
+  "GetCompetitivePricingForASINResponse",
+  "GetCompetitivePricingForASINResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetCompetitivePricingForASINParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "ASIN")]
+  pub ASINList: Vec<String>,
+}
+
+/// The result of an ASIN of `GetCompetitivePricingForASIN`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetCompetitivePricingForASINResult {
+  #[from_xml_stream(from_attr = "ASIN")]
+  pub ASIN: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetCompetitivePricingForASIN",
+  GetCompetitivePricingForASINParameters
+    => GetCompetitivePricingForASINResponseEnvelope<Vec<GetCompetitivePricingForASINResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the current competitive price of a product, based on ASIN.
+///
+/// The competitive prices are in `Product.CompetitivePricing`. Up to 20 ASINs can be
+/// requested per call.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetCompetitivePricingForASIN.html)
+#[allow(non_snake_case)]
+pub fn GetCompetitivePricingForASIN(
+  client: &Client,
+  params: GetCompetitivePricingForASINParameters,
+) -> MwsResult<Vec<GetCompetitivePricingForASINResult>> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetCompetitivePricingForASIN`](fn.GetCompetitivePricingForASIN.html).
+#[allow(non_snake_case)]
+pub fn GetCompetitivePricingForASINAsync(
+  client: &AsyncClient,
+  params: GetCompetitivePricingForASINParameters,
+) -> MwsFuture<Vec<GetCompetitivePricingForASINResult>> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetLowestOfferListingsForSKUResponseEnvelope<GetLowestOfferListingsForSKUResult>,
+  "GetLowestOfferListingsForSKUResponse",
+  "GetLowestOfferListingsForSKUResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetLowestOfferListingsForSKUParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "SellerSKU")]
+  pub SellerSKUList: Vec<String>,
+  pub ItemCondition: Option<ItemCondition>,
+  /// Excludes your own offer listings from the results.
+  pub ExcludeMe: Option<bool>,
+}
+
+/// The result of a SellerSKU of `GetLowestOfferListingsForSKU`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetLowestOfferListingsForSKUResult {
+  #[from_xml_stream(from_attr = "SellerSKU")]
+  pub SellerSKU: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  /// `false` if not all offer listings were considered, e.g. because there are too many.
+  #[from_xml_stream(from_attr = "AllOfferListingsConsidered")]
+  pub AllOfferListingsConsidered: Option<bool>,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetLowestOfferListingsForSKU",
+  GetLowestOfferListingsForSKUParameters
+    => GetLowestOfferListingsForSKUResponseEnvelope<Vec<GetLowestOfferListingsForSKUResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns pricing information for the lowest-price active offer listings for up to 20
+/// products, based on SellerSKU.
+///
+/// The listings are in `Product.LowestOfferListings`.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetLowestOfferListingsForSKU.html)
+#[allow(non_snake_case)]
+pub fn GetLowestOfferListingsForSKU(
+  client: &Client,
+  params: GetLowestOfferListingsForSKUParameters,
+) -> MwsResult<Vec<GetLowestOfferListingsForSKUResult>> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetLowestOfferListingsForSKU`](fn.GetLowestOfferListingsForSKU.html).
+#[allow(non_snake_case)]
+pub fn GetLowestOfferListingsForSKUAsync(
+  client: &AsyncClient,
+  params: GetLowestOfferListingsForSKUParameters,
+) -> MwsFuture<Vec<GetLowestOfferListingsForSKUResult>> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetLowestOfferListingsForASINResponseEnvelope<GetLowestOfferListingsForASINResult>,
+  "GetLowestOfferListingsForASINResponse",
+  "GetLowestOfferListingsForASINResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetLowestOfferListingsForASINParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "ASIN")]
+  pub ASINList: Vec<String>,
+  pub ItemCondition: Option<ItemCondition>,
+  /// Excludes your own offer listings from the results.
+  pub ExcludeMe: Option<bool>,
+}
+
+/// The result of an ASIN of `GetLowestOfferListingsForASIN`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetLowestOfferListingsForASINResult {
+  #[from_xml_stream(from_attr = "ASIN")]
+  pub ASIN: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  /// `false` if not all offer listings were considered, e.g. because there are too many.
+  #[from_xml_stream(from_attr = "AllOfferListingsConsidered")]
+  pub AllOfferListingsConsidered: Option<bool>,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetLowestOfferListingsForASIN",
+  GetLowestOfferListingsForASINParameters
+    => GetLowestOfferListingsForASINResponseEnvelope<Vec<GetLowestOfferListingsForASINResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns pricing information for the lowest-price active offer listings for up to 20
+/// products, based on ASIN.
+///
+/// The listings are in `Product.LowestOfferListings`.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetLowestOfferListingsForASIN.html)
+#[allow(non_snake_case)]
+pub fn GetLowestOfferListingsForASIN(
+  client: &Client,
+  params: GetLowestOfferListingsForASINParameters,
+) -> MwsResult<Vec<GetLowestOfferListingsForASINResult>> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetLowestOfferListingsForASIN`](fn.GetLowestOfferListingsForASIN.html).
+#[allow(non_snake_case)]
+pub fn GetLowestOfferListingsForASINAsync(
+  client: &AsyncClient,
+  params: GetLowestOfferListingsForASINParameters,
+) -> MwsFuture<Vec<GetLowestOfferListingsForASINResult>> {
+  client.execute(params)
+}
+
+/// The maximum number of ASINs of a `GetMatchingProduct` request
+pub const GET_MATCHING_PRODUCT_MAX_ASINS: usize = 10;
+
+/// The maximum number of ids of a `GetMatchingProductForId` request
+pub const GET_MATCHING_PRODUCT_FOR_ID_MAX_IDS: usize = 5;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct ListMatchingProductsParameters {
+  pub MarketplaceId: String,
+  /// A search string with the same support as that provided on Amazon marketplace websites.
+  pub Query: String,
+  /// An identifier for the context within which the given search will be performed, e.g.
+  /// `Electronics`.
+  pub QueryContextId: Option<String>,
+}
+
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct ListMatchingProductsResponse {
+  pub Products: Vec<product::Product>,
+}
+
+response_envelope_type!(
+  ListMatchingProductsResponseEnvelope<ListMatchingProductsResponse>,
+  "ListMatchingProductsResponse",
+  "ListMatchingProductsResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListMatchingProducts",
+  ListMatchingProductsParameters
+    => ListMatchingProductsResponseEnvelope<ListMatchingProductsResponse>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns a list of products and their attributes, based on a search query.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_ListMatchingProducts.html)
+#[allow(non_snake_case)]
+pub fn ListMatchingProducts(
+  client: &Client,
+  params: ListMatchingProductsParameters,
+) -> MwsResult<ListMatchingProductsResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`ListMatchingProducts`](fn.ListMatchingProducts.html).
+#[allow(non_snake_case)]
+pub fn ListMatchingProductsAsync(
+  client: &AsyncClient,
+  params: ListMatchingProductsParameters,
+) -> MwsFuture<ListMatchingProductsResponse> {
+  client.execute(params)
+}
+
+response_envelope_batch_type!(
+  GetMatchingProductResponseEnvelope<GetMatchingProductResult>,
+  "GetMatchingProductResponse",
+  "GetMatchingProductResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Clone, Serialize, SerializeMwsParams)]
+pub struct GetMatchingProductParameters {
+  pub MarketplaceId: String,
+  #[mws_param(list_item_type_name = "ASIN")]
+  pub ASINList: Vec<String>,
+}
+
+/// The result of an ASIN of `GetMatchingProduct`
+///
+/// `Product` is set if `Status` is `Success`, `Error` otherwise.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetMatchingProductResult {
+  #[from_xml_stream(from_attr = "ASIN")]
+  pub ASIN: String,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  pub Product: Option<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetMatchingProduct",
+  GetMatchingProductParameters => GetMatchingProductResponseEnvelope<Vec<GetMatchingProductResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns a list of products and their attributes, based on a list of ASIN values.
+///
+/// MWS accepts up to `GET_MATCHING_PRODUCT_MAX_ASINS` ASINs per request, longer lists are
+/// split into multiple requests. There is one result for each ASIN.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetMatchingProduct.html)
+#[allow(non_snake_case)]
+pub fn GetMatchingProduct(
+  client: &Client,
+  params: GetMatchingProductParameters,
+) -> MwsResult<Vec<GetMatchingProductResult>> {
+  let mut results = vec![];
+  for asins in params.ASINList.chunks(GET_MATCHING_PRODUCT_MAX_ASINS) {
+    let mut batch = client.execute(GetMatchingProductParameters {
+      MarketplaceId: params.MarketplaceId.clone(),
+      ASINList: asins.to_vec(),
+    })?;
+    results.append(&mut batch);
+  }
+  Ok(results)
+}
+
+/// Asynchronous version of [`GetMatchingProduct`](fn.GetMatchingProduct.html), the batches are
+/// requested concurrently.
+#[allow(non_snake_case)]
+pub fn GetMatchingProductAsync(
+  client: &AsyncClient,
+  params: GetMatchingProductParameters,
+) -> MwsFuture<Vec<GetMatchingProductResult>> {
+  let futures: Vec<_> = params
+    .ASINList
+    .chunks(GET_MATCHING_PRODUCT_MAX_ASINS)
+    .map(|asins| {
+      client.execute(GetMatchingProductParameters {
+        MarketplaceId: params.MarketplaceId.clone(),
+        ASINList: asins.to_vec(),
+      })
+    })
+    .collect();
+  Box::new(future::join_all(futures).map(|batches| batches.into_iter().flatten().collect()))
+}
+
+response_envelope_batch_type!(
+  GetMatchingProductForIdResponseEnvelope<GetMatchingProductForIdResult>,
+  "GetMatchingProductForIdResponse",
+  "GetMatchingProductForIdResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Clone, Serialize, SerializeMwsParams)]
+pub struct GetMatchingProductForIdParameters {
+  pub MarketplaceId: String,
+  pub IdType: IdType,
+  #[mws_param(list_item_type_name = "Id")]
+  pub IdList: Vec<String>,
+}
+
+/// The result of an id of `GetMatchingProductForId`
+///
+/// An id can match multiple products, e.g. an UPC used by several ASINs.
+/// `Error` is set if `Status` is not `Success`.
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetMatchingProductForIdResult {
+  #[from_xml_stream(from_attr = "Id")]
+  pub Id: String,
+  #[from_xml_stream(from_attr = "IdType")]
+  pub IdType: IdType,
+  #[from_xml_stream(from_attr = "status")]
+  pub Status: String,
+  pub Products: Vec<product::Product>,
+  pub Error: Option<ProductError>,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetMatchingProductForId",
+  GetMatchingProductForIdParameters
+    => GetMatchingProductForIdResponseEnvelope<Vec<GetMatchingProductForIdResult>>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns a list of products and their attributes, based on a list of ASIN, GCID, SellerSKU,
+/// UPC, EAN, ISBN, and JAN values.
+///
+/// MWS accepts up to `GET_MATCHING_PRODUCT_FOR_ID_MAX_IDS` ids per request, longer lists are
+/// split into multiple requests. There is one result for each id.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetMatchingProductForId.html)
+#[allow(non_snake_case)]
+pub fn GetMatchingProductForId(
+  client: &Client,
+  params: GetMatchingProductForIdParameters,
+) -> MwsResult<Vec<GetMatchingProductForIdResult>> {
+  let mut results = vec![];
+  for ids in params.IdList.chunks(GET_MATCHING_PRODUCT_FOR_ID_MAX_IDS) {
+    let mut batch = client.execute(GetMatchingProductForIdParameters {
+      MarketplaceId: params.MarketplaceId.clone(),
+      IdType: params.IdType.clone(),
+      IdList: ids.to_vec(),
+    })?;
+    results.append(&mut batch);
+  }
+  Ok(results)
+}
+
+/// Asynchronous version of [`GetMatchingProductForId`](fn.GetMatchingProductForId.html), the
+/// batches are requested concurrently.
+#[allow(non_snake_case)]
+pub fn GetMatchingProductForIdAsync(
+  client: &AsyncClient,
+  params: GetMatchingProductForIdParameters,
+) -> MwsFuture<Vec<GetMatchingProductForIdResult>> {
+  let futures: Vec<_> = params
+    .IdList
+    .chunks(GET_MATCHING_PRODUCT_FOR_ID_MAX_IDS)
+    .map(|ids| {
+      client.execute(GetMatchingProductForIdParameters {
+        MarketplaceId: params.MarketplaceId.clone(),
+        IdType: params.IdType.clone(),
+        IdList: ids.to_vec(),
+      })
+    })
+    .collect();
+  Box::new(future::join_all(futures).map(|batches| batches.into_iter().flatten().collect()))
+}
+
+/// The maximum number of `FeesEstimateRequest` of a `GetMyFeesEstimate` request
+pub const GET_MY_FEES_ESTIMATE_MAX_REQUESTS: usize = 20;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetMyFeesEstimateParameters {
+  #[mws_param(list_item_type_name = "FeesEstimateRequest")]
+  pub FeesEstimateRequestList: Vec<fees::FeesEstimateRequest>,
+}
+
+#[derive(FromXmlStream, Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetMyFeesEstimateResponse {
+  pub FeesEstimateResultList: Vec<fees::FeesEstimateResult>,
+}
+
+response_envelope_type!(
+  GetMyFeesEstimateResponseEnvelope<GetMyFeesEstimateResponse>,
+  "GetMyFeesEstimateResponse",
+  "GetMyFeesEstimateResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetMyFeesEstimate",
+  GetMyFeesEstimateParameters => GetMyFeesEstimateResponseEnvelope<GetMyFeesEstimateResponse>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the estimated fees for a list of products.
+///
+/// MWS accepts up to `GET_MY_FEES_ESTIMATE_MAX_REQUESTS` requests per call, longer lists are
+/// split into multiple requests. Use `FeesEstimateIdentifier.SellerInputIdentifier` to match
+/// the results with the requests.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetMyFeesEstimate.html)
+#[allow(non_snake_case)]
+pub fn GetMyFeesEstimate(
+  client: &Client,
+  params: GetMyFeesEstimateParameters,
+) -> MwsResult<GetMyFeesEstimateResponse> {
+  let mut res = GetMyFeesEstimateResponse::default();
+  for requests in params
+    .FeesEstimateRequestList
+    .chunks(GET_MY_FEES_ESTIMATE_MAX_REQUESTS)
+  {
+    let mut batch = client.execute(GetMyFeesEstimateParameters {
+      FeesEstimateRequestList: requests.to_vec(),
+    })?;
+    res
+      .FeesEstimateResultList
+      .append(&mut batch.FeesEstimateResultList);
+  }
+  Ok(res)
+}
+
+/// Asynchronous version of [`GetMyFeesEstimate`](fn.GetMyFeesEstimate.html), the batches are
+/// requested concurrently.
+#[allow(non_snake_case)]
+pub fn GetMyFeesEstimateAsync(
+  client: &AsyncClient,
+  params: GetMyFeesEstimateParameters,
+) -> MwsFuture<GetMyFeesEstimateResponse> {
+  let futures: Vec<_> = params
+    .FeesEstimateRequestList
+    .chunks(GET_MY_FEES_ESTIMATE_MAX_REQUESTS)
+    .map(|requests| {
+      client.execute(GetMyFeesEstimateParameters {
+        FeesEstimateRequestList: requests.to_vec(),
+      })
+    })
+    .collect();
+  Box::new(future::join_all(futures).map(|batches| {
+    GetMyFeesEstimateResponse {
+      FeesEstimateResultList: batches
+        .into_iter()
+        .flat_map(|batch| batch.FeesEstimateResultList)
+        .collect(),
+    }
+  }))
+}
+
+/// The response of `GetProductCategoriesForSKU` and `GetProductCategoriesForASIN`
+#[derive(Default, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct GetProductCategoriesResponse {
+  /// The leaf categories of the product, the `Self` elements.
+  pub Categories: Vec<ProductCategory>,
+}
+
+impl<S: XmlEventStream> FromXmlStream<S> for GetProductCategoriesResponse {
+  fn from_xml(s: &mut S) -> MwsResult<GetProductCategoriesResponse> {
+    use xmlhelper::decode::fold_elements;
+    fold_elements(s, GetProductCategoriesResponse::default(), |s, response| {
+      if s.local_name() == "Self" {
+        response.Categories.push(ProductCategory::from_xml(s)?);
+      }
+      Ok(())
+    })
+  }
+}
+
+response_envelope_type!(
+  GetProductCategoriesForSKUResponseEnvelope<GetProductCategoriesResponse>,
+  "GetProductCategoriesForSKUResponse",
+  "GetProductCategoriesForSKUResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetProductCategoriesForSKUParameters {
+  pub MarketplaceId: String,
+  pub SellerSKU: String,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetProductCategoriesForSKU",
+  GetProductCategoriesForSKUParameters
+    => GetProductCategoriesForSKUResponseEnvelope<GetProductCategoriesResponse>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the parent product categories that a product belongs to, based on SellerSKU.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetProductCategoriesForSKU.html)
+#[allow(non_snake_case)]
+pub fn GetProductCategoriesForSKU(
+  client: &Client,
+  params: GetProductCategoriesForSKUParameters,
+) -> MwsResult<Vec<ProductCategory>> {
+  client.execute(params).map(|res| res.Categories)
+}
+
+/// Asynchronous version of [`GetProductCategoriesForSKU`](fn.GetProductCategoriesForSKU.html).
+#[allow(non_snake_case)]
+pub fn GetProductCategoriesForSKUAsync(
+  client: &AsyncClient,
+  params: GetProductCategoriesForSKUParameters,
+) -> MwsFuture<Vec<ProductCategory>> {
+  Box::new(client.execute(params).map(|res| res.Categories))
+}
+
+response_envelope_type!(
+  GetProductCategoriesForASINResponseEnvelope<GetProductCategoriesResponse>,
+  "GetProductCategoriesForASINResponse",
+  "GetProductCategoriesForASINResult"
+);
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetProductCategoriesForASINParameters {
+  pub MarketplaceId: String,
+  pub ASIN: String,
+}
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetProductCategoriesForASIN",
+  GetProductCategoriesForASINParameters
+    => GetProductCategoriesForASINResponseEnvelope<GetProductCategoriesResponse>,
+  idempotent: true,
+  form: true
+);
+
+/// Returns the parent product categories that a product belongs to, based on ASIN.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/products/Products_GetProductCategoriesForASIN.html)
+#[allow(non_snake_case)]
+pub fn GetProductCategoriesForASIN(
+  client: &Client,
+  params: GetProductCategoriesForASINParameters,
+) -> MwsResult<Vec<ProductCategory>> {
+  client.execute(params).map(|res| res.Categories)
+}
+
+/// Asynchronous version of [`GetProductCategoriesForASIN`](fn.GetProductCategoriesForASIN.html).
+#[allow(non_snake_case)]
+pub fn GetProductCategoriesForASINAsync(
+  client: &AsyncClient,
+  params: GetProductCategoriesForASINParameters,
+) -> MwsFuture<Vec<ProductCategory>> {
+  Box::new(client.execute(params).map(|res| res.Categories))
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use client::MwsErrorCode;
 
   #[test]
   fn test_get_my_price_for_sku_response() {
@@ -178,6 +990,7 @@ mod tests {
         GetMyPriceForASINResult {
           ASIN: "B073000000".to_string(),
           Status: "Success".to_string(),
+          Error: None,
           Product: product::Product {
             Identifiers: product::Identifier {
               MarketplaceASIN: Some(product::MarketplaceASIN {
@@ -238,11 +1051,13 @@ mod tests {
                 SellerSKU: "sku-fbm".to_string(),
               }
             ],
+            ..Default::default()
           }
         },
         GetMyPriceForASINResult {
           ASIN: "B073000001".to_string(),
           Status: "Success".to_string(),
+          Error: None,
           Product: product::Product {
             Identifiers: product::Identifier {
               MarketplaceASIN: Some(product::MarketplaceASIN {
@@ -251,10 +1066,798 @@ mod tests {
               }),
               ..Default::default()
             },
-            Offers: vec![]
+            Offers: vec![],
+            ..Default::default()
           }
         }
       ]
     );
   }
+
+  #[test]
+  fn test_get_matching_product_for_id_response() {
+    use xmlhelper::decode::{FromXmlStream, Stream};
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetMatchingProductForIdResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetMatchingProductForIdResult Id="082676082658" IdType="UPC" status="Success">
+            <Products xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01" xmlns:ns2="http://mws.amazonservices.com/schema/Products/2011-10-01/default.xsd">
+              <Product>
+                <Identifiers>
+                  <MarketplaceASIN>
+                    <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                    <ASIN>B000JXV91Y</ASIN>
+                  </MarketplaceASIN>
+                </Identifiers>
+                <AttributeSets>
+                  <ns2:ItemAttributes xml:lang="en-US">
+                    <ns2:Binding>Apparel</ns2:Binding>
+                    <ns2:Brand>Pearl iZUMi</ns2:Brand>
+                    <ns2:Department>mens</ns2:Department>
+                    <ns2:Feature>Fabric: 100% polyester</ns2:Feature>
+                    <ns2:Feature>Machine wash</ns2:Feature>
+                    <ns2:ItemDimensions>
+                      <ns2:Height Units="inches">1.00</ns2:Height>
+                      <ns2:Length Units="inches">10.00</ns2:Length>
+                    </ns2:ItemDimensions>
+                    <ns2:ListPrice>
+                      <ns2:Amount>35.00</ns2:Amount>
+                      <ns2:CurrencyCode>USD</ns2:CurrencyCode>
+                    </ns2:ListPrice>
+                    <ns2:PackageDimensions>
+                      <ns2:Weight Units="pounds">0.40</ns2:Weight>
+                    </ns2:PackageDimensions>
+                    <ns2:PackageQuantity>1</ns2:PackageQuantity>
+                    <ns2:ProductGroup>Apparel</ns2:ProductGroup>
+                    <ns2:ProductTypeName>SHIRT</ns2:ProductTypeName>
+                    <ns2:SmallImage>
+                      <ns2:URL>http://ecx.images-amazon.com/images/I/41ty3Sn%2BU8L._SL75_.jpg</ns2:URL>
+                      <ns2:Height Units="pixels">75</ns2:Height>
+                      <ns2:Width Units="pixels">75</ns2:Width>
+                    </ns2:SmallImage>
+                    <ns2:Title>Pearl iZUMi Men's Quest Jersey</ns2:Title>
+                  </ns2:ItemAttributes>
+                </AttributeSets>
+                <Relationships>
+                  <ns2:VariationParent>
+                    <Identifiers>
+                      <MarketplaceASIN>
+                        <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                        <ASIN>B002KT3XQC</ASIN>
+                      </MarketplaceASIN>
+                    </Identifiers>
+                  </ns2:VariationParent>
+                </Relationships>
+                <SalesRankings>
+                  <SalesRank>
+                    <ProductCategoryId>apparel_display_on_website</ProductCategoryId>
+                    <Rank>1326</Rank>
+                  </SalesRank>
+                </SalesRankings>
+              </Product>
+            </Products>
+          </GetMatchingProductForIdResult>
+          <GetMatchingProductForIdResult Id="1234567890" IdType="UPC" status="ClientError">
+            <Error>
+              <Type>Sender</Type>
+              <Code>InvalidParameterValue</Code>
+              <Message>Invalid UPC identifier 1234567890 for marketplace ATVPDKIKX0DER</Message>
+            </Error>
+          </GetMatchingProductForIdResult>
+          <ResponseMetadata>
+            <RequestId>3e353f76-2ef6-442e-a714-6bbc26f96626</RequestId>
+          </ResponseMetadata>
+        </GetMatchingProductForIdResponse>
+      "#,
+    ));
+    let results = GetMatchingProductForIdResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(results.len(), 2);
+
+    let result = &results[0];
+    assert_eq!(result.Id, "082676082658");
+    assert_eq!(result.IdType, IdType::UPC);
+    assert_eq!(result.Status, "Success");
+    assert_eq!(result.Error, None);
+    assert_eq!(result.Products.len(), 1);
+    let product = &result.Products[0];
+    assert_eq!(product.asin(), Some("B000JXV91Y"));
+    assert_eq!(
+      product.AttributeSets,
+      vec![product::ItemAttributes {
+        Lang: Some("en-US".to_string()),
+        Binding: Some("Apparel".to_string()),
+        Brand: Some("Pearl iZUMi".to_string()),
+        Department: Some("mens".to_string()),
+        Feature: vec![
+          "Fabric: 100% polyester".to_string(),
+          "Machine wash".to_string(),
+        ],
+        ItemDimensions: Some(product::Dimensions {
+          Height: Some(product::DecimalWithUnits {
+            Units: Some("inches".to_string()),
+            Value: "1.00".to_string(),
+          }),
+          Length: Some(product::DecimalWithUnits {
+            Units: Some("inches".to_string()),
+            Value: "10.00".to_string(),
+          }),
+          ..Default::default()
+        }),
+        ListPrice: Some(MoneyType {
+          Amount: "35.00".to_string(),
+          CurrencyCode: "USD".to_string(),
+        }),
+        PackageDimensions: Some(product::Dimensions {
+          Weight: Some(product::DecimalWithUnits {
+            Units: Some("pounds".to_string()),
+            Value: "0.40".to_string(),
+          }),
+          ..Default::default()
+        }),
+        PackageQuantity: Some(1),
+        ProductGroup: Some("Apparel".to_string()),
+        ProductTypeName: Some("SHIRT".to_string()),
+        SmallImage: Some(product::Image {
+          URL: "http://ecx.images-amazon.com/images/I/41ty3Sn%2BU8L._SL75_.jpg".to_string(),
+          Height: Some(product::DecimalWithUnits {
+            Units: Some("pixels".to_string()),
+            Value: "75".to_string(),
+          }),
+          Width: Some(product::DecimalWithUnits {
+            Units: Some("pixels".to_string()),
+            Value: "75".to_string(),
+          }),
+        }),
+        Title: Some("Pearl iZUMi Men's Quest Jersey".to_string()),
+        ..Default::default()
+      }]
+    );
+    assert_eq!(
+      product.Relationships.VariationParent,
+      vec![product::VariationParent {
+        Identifiers: product::Identifier {
+          MarketplaceASIN: Some(product::MarketplaceASIN {
+            MarketplaceId: "ATVPDKIKX0DER".to_string(),
+            ASIN: "B002KT3XQC".to_string(),
+          }),
+          ..Default::default()
+        },
+      }]
+    );
+    assert_eq!(
+      product.SalesRankings,
+      vec![product::SalesRank {
+        ProductCategoryId: "apparel_display_on_website".to_string(),
+        Rank: 1326,
+      }]
+    );
+
+    let result = &results[1];
+    assert_eq!(result.Status, "ClientError");
+    assert!(result.Products.is_empty());
+    let error = result.Error.as_ref().expect("error");
+    assert_eq!(error.Type, "Sender");
+    assert_eq!(error.error_code(), MwsErrorCode::InvalidParameterValue);
+  }
+
+  #[test]
+  fn test_get_matching_product_response() {
+    use xmlhelper::decode::{FromXmlStream, Stream};
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetMatchingProductResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetMatchingProductResult ASIN="B002L7A1OG" status="Success">
+            <Product xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01" xmlns:ns2="http://mws.amazonservices.com/schema/Products/2011-10-01/default.xsd">
+              <Identifiers>
+                <MarketplaceASIN>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <ASIN>B002L7A1OG</ASIN>
+                </MarketplaceASIN>
+              </Identifiers>
+              <AttributeSets>
+                <ns2:ItemAttributes xml:lang="en-US">
+                  <ns2:Title>Variation Parent</ns2:Title>
+                </ns2:ItemAttributes>
+              </AttributeSets>
+              <Relationships>
+                <VariationChild>
+                  <Identifiers>
+                    <MarketplaceASIN>
+                      <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                      <ASIN>B002KT3XQM</ASIN>
+                    </MarketplaceASIN>
+                  </Identifiers>
+                  <ns2:Color>Black</ns2:Color>
+                  <ns2:Size>Small</ns2:Size>
+                </VariationChild>
+                <VariationChild>
+                  <Identifiers>
+                    <MarketplaceASIN>
+                      <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                      <ASIN>B002KT3XQW</ASIN>
+                    </MarketplaceASIN>
+                  </Identifiers>
+                  <ns2:Color>Black</ns2:Color>
+                  <ns2:Size>Medium</ns2:Size>
+                </VariationChild>
+              </Relationships>
+              <SalesRankings/>
+            </Product>
+          </GetMatchingProductResult>
+          <GetMatchingProductResult ASIN="B0000000XX" status="ClientError">
+            <Error>
+              <Type>Sender</Type>
+              <Code>InvalidParameterValue</Code>
+              <Message>ASIN B0000000XX is not valid for marketplace ATVPDKIKX0DER</Message>
+            </Error>
+          </GetMatchingProductResult>
+          <ResponseMetadata>
+            <RequestId>b12caebc-1f06-4c4a-b3d2-b1e1d8ef9cb0</RequestId>
+          </ResponseMetadata>
+        </GetMatchingProductResponse>
+      "#,
+    ));
+    let results = GetMatchingProductResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(results.len(), 2);
+
+    let product = results[0].Product.as_ref().expect("product");
+    assert_eq!(results[0].ASIN, "B002L7A1OG");
+    assert_eq!(
+      product.AttributeSets[0].Title,
+      Some("Variation Parent".to_string())
+    );
+    let children: Vec<_> = product
+      .Relationships
+      .VariationChild
+      .iter()
+      .map(|c| {
+        (
+          c.Identifiers
+            .MarketplaceASIN
+            .as_ref()
+            .map(|id| id.ASIN.as_str()),
+          c.Size.as_ref().map(String::as_str),
+        )
+      })
+      .collect();
+    assert_eq!(
+      children,
+      vec![
+        (Some("B002KT3XQM"), Some("Small")),
+        (Some("B002KT3XQW"), Some("Medium")),
+      ]
+    );
+    assert!(product.SalesRankings.is_empty());
+
+    assert_eq!(results[1].Product, None);
+    assert_eq!(
+      results[1].Error.as_ref().map(|e| e.Code.as_str()),
+      Some("InvalidParameterValue")
+    );
+  }
+
+  #[test]
+  fn test_get_competitive_pricing_for_asin_response() {
+    use xmlhelper::decode::{FromXmlStream, Stream};
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetCompetitivePricingForASINResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetCompetitivePricingForASINResult ASIN="B002L7A1OG" status="Success">
+            <Product xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01" xmlns:ns2="http://mws.amazonservices.com/schema/Products/2011-10-01/default.xsd">
+              <Identifiers>
+                <MarketplaceASIN>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <ASIN>B002L7A1OG</ASIN>
+                </MarketplaceASIN>
+              </Identifiers>
+              <CompetitivePricing>
+                <CompetitivePrices>
+                  <CompetitivePrice belongsToRequester="false" condition="New" subcondition="New">
+                    <CompetitivePriceId>1</CompetitivePriceId>
+                    <Price>
+                      <LandedPrice>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>19.99</Amount>
+                      </LandedPrice>
+                      <ListingPrice>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>15.99</Amount>
+                      </ListingPrice>
+                      <Shipping>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>4.00</Amount>
+                      </Shipping>
+                    </Price>
+                  </CompetitivePrice>
+                </CompetitivePrices>
+                <NumberOfOfferListings>
+                  <OfferListingCount condition="New">12</OfferListingCount>
+                  <OfferListingCount condition="Any">13</OfferListingCount>
+                </NumberOfOfferListings>
+              </CompetitivePricing>
+              <SalesRankings>
+                <SalesRank>
+                  <ProductCategoryId>book_display_on_website</ProductCategoryId>
+                  <Rank>557</Rank>
+                </SalesRank>
+              </SalesRankings>
+            </Product>
+          </GetCompetitivePricingForASINResult>
+          <GetCompetitivePricingForASINResult ASIN="B0000000XX" status="ClientError">
+            <Error>
+              <Type>Sender</Type>
+              <Code>InvalidParameterValue</Code>
+              <Message>ASIN B0000000XX is not valid for marketplace ATVPDKIKX0DER</Message>
+            </Error>
+          </GetCompetitivePricingForASINResult>
+          <ResponseMetadata>
+            <RequestId>b12caebc-1f06-4c4a-b3d2-b1e1d8ef9cb0</RequestId>
+          </ResponseMetadata>
+        </GetCompetitivePricingForASINResponse>
+      "#,
+    ));
+    let results = GetCompetitivePricingForASINResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].ASIN, "B002L7A1OG");
+    let product = results[0].Product.as_ref().expect("product");
+    assert_eq!(
+      product.CompetitivePricing,
+      Some(product::CompetitivePricing {
+        CompetitivePrices: vec![product::CompetitivePrice {
+          BelongsToRequester: Some(false),
+          Condition: "New".to_string(),
+          Subcondition: "New".to_string(),
+          CompetitivePriceId: "1".to_string(),
+          Price: product::Price {
+            LandedPrice: MoneyType {
+              CurrencyCode: "USD".to_string(),
+              Amount: "19.99".to_string(),
+            },
+            ListingPrice: MoneyType {
+              CurrencyCode: "USD".to_string(),
+              Amount: "15.99".to_string(),
+            },
+            Shipping: MoneyType {
+              CurrencyCode: "USD".to_string(),
+              Amount: "4.00".to_string(),
+            },
+          },
+        }],
+        NumberOfOfferListings: vec![
+          product::OfferListingCount {
+            Condition: "New".to_string(),
+            Value: 12,
+          },
+          product::OfferListingCount {
+            Condition: "Any".to_string(),
+            Value: 13,
+          },
+        ],
+        TradeInValue: None,
+      })
+    );
+    assert_eq!(product.SalesRankings[0].Rank, 557);
+
+    assert_eq!(results[1].Status, "ClientError");
+    assert_eq!(results[1].Product, None);
+    assert_eq!(
+      results[1].Error.as_ref().map(|e| e.error_code()),
+      Some(MwsErrorCode::InvalidParameterValue)
+    );
+  }
+
+  #[test]
+  fn test_get_lowest_offer_listings_for_sku_response() {
+    use xmlhelper::decode::{FromXmlStream, Stream};
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetLowestOfferListingsForSKUResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetLowestOfferListingsForSKUResult SellerSKU="SKU2468" status="Success" AllOfferListingsConsidered="true">
+            <Product xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01" xmlns:ns2="http://mws.amazonservices.com/schema/Products/2011-10-01/default.xsd">
+              <Identifiers>
+                <MarketplaceASIN>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <ASIN>1933890517</ASIN>
+                </MarketplaceASIN>
+                <SKUIdentifier>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <SellerId>A1IMEXAMPLEWRC</SellerId>
+                  <SellerSKU>SKU2468</SellerSKU>
+                </SKUIdentifier>
+              </Identifiers>
+              <LowestOfferListings>
+                <LowestOfferListing>
+                  <Qualifiers>
+                    <ItemCondition>Used</ItemCondition>
+                    <ItemSubcondition>Good</ItemSubcondition>
+                    <FulfillmentChannel>Amazon</FulfillmentChannel>
+                    <ShipsDomestically>True</ShipsDomestically>
+                    <ShippingTime>
+                      <Max>0-2 days</Max>
+                    </ShippingTime>
+                    <SellerPositiveFeedbackRating>90-94%</SellerPositiveFeedbackRating>
+                  </Qualifiers>
+                  <NumberOfOfferListingsConsidered>1</NumberOfOfferListingsConsidered>
+                  <SellerFeedbackCount>1180</SellerFeedbackCount>
+                  <Price>
+                    <LandedPrice>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <Amount>176.76</Amount>
+                    </LandedPrice>
+                    <ListingPrice>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <Amount>176.76</Amount>
+                    </ListingPrice>
+                    <Shipping>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <Amount>0.00</Amount>
+                    </Shipping>
+                  </Price>
+                  <MultipleOffersAtLowestPrice>False</MultipleOffersAtLowestPrice>
+                </LowestOfferListing>
+              </LowestOfferListings>
+            </Product>
+          </GetLowestOfferListingsForSKUResult>
+          <ResponseMetadata>
+            <RequestId>e6c92dc3-0b03-4e3b-a16c-9d0bc15c9fb2</RequestId>
+          </ResponseMetadata>
+        </GetLowestOfferListingsForSKUResponse>
+      "#,
+    ));
+    let results = GetLowestOfferListingsForSKUResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].SellerSKU, "SKU2468");
+    assert_eq!(results[0].AllOfferListingsConsidered, Some(true));
+    let product = results[0].Product.as_ref().expect("product");
+    assert_eq!(product.asin(), Some("1933890517"));
+    assert_eq!(
+      product.LowestOfferListings,
+      vec![product::LowestOfferListing {
+        Qualifiers: product::Qualifiers {
+          ItemCondition: ItemCondition::Used,
+          ItemSubcondition: "Good".to_string(),
+          FulfillmentChannel: "Amazon".to_string(),
+          ShipsDomestically: "True".to_string(),
+          ShippingTime: Some(product::ShippingTime {
+            Max: "0-2 days".to_string(),
+          }),
+          SellerPositiveFeedbackRating: "90-94%".to_string(),
+        },
+        NumberOfOfferListingsConsidered: 1,
+        SellerFeedbackCount: 1180,
+        Price: product::Price {
+          LandedPrice: MoneyType {
+            CurrencyCode: "USD".to_string(),
+            Amount: "176.76".to_string(),
+          },
+          ListingPrice: MoneyType {
+            CurrencyCode: "USD".to_string(),
+            Amount: "176.76".to_string(),
+          },
+          Shipping: MoneyType {
+            CurrencyCode: "USD".to_string(),
+            Amount: "0.00".to_string(),
+          },
+        },
+        MultipleOffersAtLowestPrice: "False".to_string(),
+      }]
+    );
+  }
+
+  #[test]
+  fn test_get_my_fees_estimate_parameters() {
+    use SerializeMwsParams;
+
+    let params = GetMyFeesEstimateParameters {
+      FeesEstimateRequestList: vec![fees::FeesEstimateRequest {
+        MarketplaceId: "ATVPDKIKX0DER".to_string(),
+        IdType: IdType::ASIN,
+        IdValue: "B002KT3XQM".to_string(),
+        IsAmazonFulfilled: true,
+        Identifier: "request1".to_string(),
+        PriceToEstimateFees: fees::PriceToEstimateFees {
+          ListingPrice: MoneyType {
+            Amount: "30.00".to_string(),
+            CurrencyCode: "USD".to_string(),
+          },
+          Shipping: Some(MoneyType {
+            Amount: "3.99".to_string(),
+            CurrencyCode: "USD".to_string(),
+          }),
+          Points: Some(Points {
+            PointsNumber: 0,
+            ..Default::default()
+          }),
+        },
+      }],
+    };
+    let prefix = "FeesEstimateRequestList.FeesEstimateRequest.1";
+    let expected: Vec<(String, String)> = vec![
+      ("MarketplaceId", "ATVPDKIKX0DER"),
+      ("IdType", "ASIN"),
+      ("IdValue", "B002KT3XQM"),
+      ("IsAmazonFulfilled", "true"),
+      ("Identifier", "request1"),
+      ("PriceToEstimateFees.ListingPrice.Amount", "30.00"),
+      ("PriceToEstimateFees.ListingPrice.CurrencyCode", "USD"),
+      ("PriceToEstimateFees.Shipping.Amount", "3.99"),
+      ("PriceToEstimateFees.Shipping.CurrencyCode", "USD"),
+      ("PriceToEstimateFees.Points.PointsNumber", "0"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (format!("{}.{}", prefix, k), v.to_string()))
+    .collect();
+    assert_eq!(params.into_mws_params(), expected);
+  }
+
+  #[test]
+  fn test_get_my_fees_estimate_response() {
+    use xmlhelper::decode::{FromXmlStream, Stream};
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetMyFeesEstimateResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetMyFeesEstimateResult>
+            <FeesEstimateResultList>
+              <FeesEstimateResult>
+                <FeesEstimateIdentifier>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <IdType>ASIN</IdType>
+                  <SellerId>A1IMEXAMPLEWRC</SellerId>
+                  <SellerInputIdentifier>request1</SellerInputIdentifier>
+                  <IsAmazonFulfilled>true</IsAmazonFulfilled>
+                  <IdValue>B002KT3XQM</IdValue>
+                  <PriceToEstimateFees>
+                    <ListingPrice>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <Amount>30.00</Amount>
+                    </ListingPrice>
+                    <Shipping>
+                      <CurrencyCode>USD</CurrencyCode>
+                      <Amount>0.00</Amount>
+                    </Shipping>
+                  </PriceToEstimateFees>
+                </FeesEstimateIdentifier>
+                <FeesEstimate>
+                  <TimeOfFeesEstimation>2015-07-19T23:15:11.859Z</TimeOfFeesEstimation>
+                  <TotalFeesEstimate>
+                    <CurrencyCode>USD</CurrencyCode>
+                    <Amount>7.91</Amount>
+                  </TotalFeesEstimate>
+                  <FeeDetailList>
+                    <FeeDetail>
+                      <FeeType>AmazonReferralFee</FeeType>
+                      <FeeAmount>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>4.50</Amount>
+                      </FeeAmount>
+                      <FeePromotion>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>0.00</Amount>
+                      </FeePromotion>
+                      <FinalFee>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>4.50</Amount>
+                      </FinalFee>
+                    </FeeDetail>
+                    <FeeDetail>
+                      <FeeType>FBAFees</FeeType>
+                      <FeeAmount>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>3.41</Amount>
+                      </FeeAmount>
+                      <FinalFee>
+                        <CurrencyCode>USD</CurrencyCode>
+                        <Amount>3.41</Amount>
+                      </FinalFee>
+                      <IncludedFeeDetailList>
+                        <IncludedFeeDetail>
+                          <FeeType>FBAPickAndPack</FeeType>
+                          <FeeAmount>
+                            <CurrencyCode>USD</CurrencyCode>
+                            <Amount>3.41</Amount>
+                          </FeeAmount>
+                          <FinalFee>
+                            <CurrencyCode>USD</CurrencyCode>
+                            <Amount>3.41</Amount>
+                          </FinalFee>
+                        </IncludedFeeDetail>
+                      </IncludedFeeDetailList>
+                    </FeeDetail>
+                  </FeeDetailList>
+                </FeesEstimate>
+                <Status>Success</Status>
+              </FeesEstimateResult>
+              <FeesEstimateResult>
+                <FeesEstimateIdentifier>
+                  <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+                  <IdType>ASIN</IdType>
+                  <SellerInputIdentifier>request2</SellerInputIdentifier>
+                  <IdValue>B0000000XX</IdValue>
+                </FeesEstimateIdentifier>
+                <Status>ClientError</Status>
+                <Error>
+                  <Type>Sender</Type>
+                  <Code>InvalidParameterValue</Code>
+                  <Message>There is an client-side error. Please verify your inputs.</Message>
+                </Error>
+              </FeesEstimateResult>
+            </FeesEstimateResultList>
+          </GetMyFeesEstimateResult>
+          <ResponseMetadata>
+            <RequestId>4b1ae2f9-6b37-4b5f-8e3f-ec8bb5d2c4f8</RequestId>
+          </ResponseMetadata>
+        </GetMyFeesEstimateResponse>
+      "#,
+    ));
+    let res = GetMyFeesEstimateResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(res.FeesEstimateResultList.len(), 2);
+
+    let result = &res.FeesEstimateResultList[0];
+    assert_eq!(
+      result.FeesEstimateIdentifier.SellerInputIdentifier,
+      "request1"
+    );
+    let estimate = result.FeesEstimate.as_ref().expect("estimate");
+    assert_eq!(estimate.TotalFeesEstimate.Amount, "7.91");
+    assert_eq!(estimate.FeeDetailList.len(), 2);
+    assert_eq!(
+      estimate.FeeDetailList[1].IncludedFeeDetailList[0].FeeType,
+      "FBAPickAndPack"
+    );
+
+    let usd = |amount: &str| MoneyType {
+      Amount: amount.to_string(),
+      CurrencyCode: "USD".to_string(),
+    };
+    assert_eq!(result.net_proceeds().unwrap(), Some(usd("22.09")));
+    // referral fee: 4.50 / 30.00 * 20.00 = 3.00
+    assert_eq!(
+      result.net_proceeds_at(&usd("20.00"), None).unwrap(),
+      Some(usd("13.59"))
+    );
+    assert_eq!(
+      result
+        .net_proceeds_at(&usd("2.00"), Some(&usd("0.50")))
+        .unwrap(),
+      Some(usd("-1.29"))
+    );
+    assert!(result
+      .net_proceeds_at(
+        &MoneyType {
+          Amount: "20.00".to_string(),
+          CurrencyCode: "EUR".to_string(),
+        },
+        None
+      )
+      .is_err());
+
+    let result = &res.FeesEstimateResultList[1];
+    assert_eq!(result.Status, "ClientError");
+    assert_eq!(result.net_proceeds().unwrap(), None);
+    assert_eq!(
+      result.Error.as_ref().map(|e| e.error_code()),
+      Some(MwsErrorCode::InvalidParameterValue)
+    );
+  }
+
+  #[test]
+  fn test_net_proceeds_jpy() {
+    let jpy = |amount: &str| MoneyType {
+      Amount: amount.to_string(),
+      CurrencyCode: "JPY".to_string(),
+    };
+    let fee = |fee_type: &str, amount: &str| fees::FeeDetail {
+      FeeType: fee_type.to_string(),
+      FeeAmount: jpy(amount),
+      FinalFee: jpy(amount),
+      ..Default::default()
+    };
+    let result = fees::FeesEstimateResult {
+      FeesEstimateIdentifier: fees::FeesEstimateIdentifier {
+        MarketplaceId: "A1VC38T7YXB528".to_string(),
+        PriceToEstimateFees: fees::PriceToEstimateFees {
+          ListingPrice: jpy("1200"),
+          Shipping: None,
+          Points: None,
+        },
+        ..Default::default()
+      },
+      FeesEstimate: Some(fees::FeesEstimate {
+        TimeOfFeesEstimation: None,
+        TotalFeesEstimate: jpy("350"),
+        FeeDetailList: vec![fee("AmazonReferralFee", "180.00"), fee("FBAFees", "170")],
+      }),
+      Status: "Success".to_string(),
+      Error: None,
+    };
+
+    assert_eq!(result.net_proceeds().unwrap(), Some(jpy("850")));
+    // referral fee: 180 / 1200 * 1000 = 150
+    assert_eq!(
+      result.net_proceeds_at(&jpy("999.6"), None).unwrap(),
+      Some(jpy("680"))
+    );
+    assert_eq!(
+      result.net_proceeds_at(&jpy("100"), None).unwrap(),
+      Some(jpy("-85"))
+    );
+  }
+
+  #[test]
+  fn test_get_product_categories_for_sku_response() {
+    use xmlhelper::decode::Stream;
+
+    let mut s = Stream::new(::std::io::Cursor::new(
+      r#"
+        <GetProductCategoriesForSKUResponse xmlns="http://mws.amazonservices.com/schema/Products/2011-10-01">
+          <GetProductCategoriesForSKUResult>
+            <Self>
+              <ProductCategoryId>271581011</ProductCategoryId>
+              <ProductCategoryName>Men's</ProductCategoryName>
+              <Parent>
+                <ProductCategoryId>1036682</ProductCategoryId>
+                <ProductCategoryName>Clothing &amp; Accessories</ProductCategoryName>
+                <Parent>
+                  <ProductCategoryId>1036592</ProductCategoryId>
+                  <ProductCategoryName>Apparel &amp; Accessories</ProductCategoryName>
+                </Parent>
+              </Parent>
+            </Self>
+            <Self>
+              <ProductCategoryId>2420095011</ProductCategoryId>
+              <ProductCategoryName>Jerseys</ProductCategoryName>
+              <Parent>
+                <ProductCategoryId>3375301</ProductCategoryId>
+                <ProductCategoryName>Sports &amp; Outdoors</ProductCategoryName>
+              </Parent>
+            </Self>
+          </GetProductCategoriesForSKUResult>
+          <ResponseMetadata>
+            <RequestId>fbce5b62-67cc-4ab8-86f3-EXAMPLE22e4e</RequestId>
+          </ResponseMetadata>
+        </GetProductCategoriesForSKUResponse>
+      "#,
+    ));
+    let categories = GetProductCategoriesForSKUResponseEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner()
+      .Categories;
+    assert_eq!(
+      categories[1],
+      ProductCategory {
+        ProductCategoryId: "2420095011".to_string(),
+        ProductCategoryName: "Jerseys".to_string(),
+        Parent: Some(Box::new(ProductCategory {
+          ProductCategoryId: "3375301".to_string(),
+          ProductCategoryName: "Sports & Outdoors".to_string(),
+          Parent: None,
+        })),
+      }
+    );
+    assert_eq!(
+      categories[0].path_names(),
+      vec!["Apparel & Accessories", "Clothing & Accessories", "Men's"]
+    );
+    let ids: Vec<_> = categories[0]
+      .path()
+      .into_iter()
+      .map(|c| c.ProductCategoryId.as_str())
+      .collect();
+    assert_eq!(ids, vec!["1036592", "1036682", "271581011"]);
+  }
 }
@@ -1,6 +1,9 @@
 //! https://docs.developer.amazonservices.com/en_US/products/Products_Datatypes.html
 
 use chrono::{DateTime, Utc};
+use client::MwsErrorCode;
+use result::MwsResult;
+use xmlhelper::decode::{FromXmlStream, XmlEventStream};
 
 pub mod product {
   use super::*;
@@ -47,12 +50,589 @@ pub mod product {
     pub SellerSKU: String,
   }
 
+  /// A decimal value and its unit, e.g. `<ns2:Height Units="inches">1.50</ns2:Height>`
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct DecimalWithUnits {
+    #[from_xml_stream(from_attr = "Units")]
+    pub Units: Option<String>,
+    #[from_xml_stream(from_content)]
+    pub Value: String,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct Dimensions {
+    pub Height: Option<DecimalWithUnits>,
+    pub Length: Option<DecimalWithUnits>,
+    pub Width: Option<DecimalWithUnits>,
+    pub Weight: Option<DecimalWithUnits>,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct Image {
+    pub URL: String,
+    pub Height: Option<DecimalWithUnits>,
+    pub Width: Option<DecimalWithUnits>,
+  }
+
+  /// The `ns2:ItemAttributes` element of a product
+  ///
+  /// Only the common attributes are decoded, the available attributes depend on the
+  /// product group.
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct ItemAttributes {
+    /// The language of the attributes, e.g. `en-US`.
+    #[from_xml_stream(from_attr = "lang")]
+    pub Lang: Option<String>,
+    pub Binding: Option<String>,
+    pub Brand: Option<String>,
+    pub Color: Option<String>,
+    pub Department: Option<String>,
+    #[from_xml_stream(no_list_wrapper)]
+    pub Feature: Vec<String>,
+    pub ItemDimensions: Option<Dimensions>,
+    pub IsAdultProduct: Option<bool>,
+    pub Label: Option<String>,
+    pub ListPrice: Option<MoneyType>,
+    pub Manufacturer: Option<String>,
+    pub Model: Option<String>,
+    pub NumberOfItems: Option<i32>,
+    pub PackageDimensions: Option<Dimensions>,
+    pub PackageQuantity: Option<i32>,
+    pub PartNumber: Option<String>,
+    pub ProductGroup: Option<String>,
+    pub ProductTypeName: Option<String>,
+    pub Publisher: Option<String>,
+    /// The release date in `YYYY-MM-DD` format.
+    pub ReleaseDate: Option<String>,
+    pub Size: Option<String>,
+    pub SmallImage: Option<Image>,
+    pub Studio: Option<String>,
+    pub Title: Option<String>,
+  }
+
+  /// The variation parent of a product
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct VariationParent {
+    pub Identifiers: Identifier,
+  }
+
+  /// A variation child of a product and the attributes it varies by
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct VariationChild {
+    pub Identifiers: Identifier,
+    pub Color: Option<String>,
+    pub Edition: Option<String>,
+    pub Flavor: Option<String>,
+    pub HardwarePlatform: Option<String>,
+    pub MaterialType: Option<String>,
+    pub MetalType: Option<String>,
+    pub Model: Option<String>,
+    pub OperatingSystem: Option<String>,
+    pub RingSize: Option<String>,
+    pub Scent: Option<String>,
+    pub Size: Option<String>,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct Relationships {
+    #[from_xml_stream(no_list_wrapper)]
+    pub VariationParent: Vec<VariationParent>,
+    #[from_xml_stream(no_list_wrapper)]
+    pub VariationChild: Vec<VariationChild>,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct SalesRank {
+    /// The product category, e.g. `electronics_display_on_website` or a browse node id.
+    pub ProductCategoryId: String,
+    pub Rank: i32,
+  }
+
+  /// A competitive price, e.g. the Buy Box price
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct CompetitivePrice {
+    #[from_xml_stream(from_attr = "belongsToRequester")]
+    pub BelongsToRequester: Option<bool>,
+    #[from_xml_stream(from_attr = "condition")]
+    pub Condition: String,
+    #[from_xml_stream(from_attr = "subcondition")]
+    pub Subcondition: String,
+    /// `1` for the New Buy Box price, `2` for the Used Buy Box price.
+    pub CompetitivePriceId: String,
+    pub Price: Price,
+  }
+
+  /// The number of offer listings in a condition
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct OfferListingCount {
+    #[from_xml_stream(from_attr = "condition")]
+    pub Condition: String,
+    #[from_xml_stream(from_content)]
+    pub Value: i32,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct CompetitivePricing {
+    pub CompetitivePrices: Vec<CompetitivePrice>,
+    pub NumberOfOfferListings: Vec<OfferListingCount>,
+    pub TradeInValue: Option<MoneyType>,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct ShippingTime {
+    /// The maximum shipping time range, e.g. `0-2 days`.
+    pub Max: String,
+  }
+
+  /// The attributes an offer listing is grouped by
+  ///
+  /// MWS returns `True` and `False` for the boolean values, these are kept as strings.
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct Qualifiers {
+    pub ItemCondition: ItemCondition,
+    pub ItemSubcondition: String,
+    /// `Amazon` or `Merchant`
+    pub FulfillmentChannel: String,
+    pub ShipsDomestically: String,
+    pub ShippingTime: Option<ShippingTime>,
+    /// The feedback rating range, e.g. `98-100%`.
+    pub SellerPositiveFeedbackRating: String,
+  }
+
+  /// The lowest price of a group of offer listings
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct LowestOfferListing {
+    pub Qualifiers: Qualifiers,
+    pub NumberOfOfferListingsConsidered: i32,
+    pub SellerFeedbackCount: i32,
+    pub Price: Price,
+    pub MultipleOffersAtLowestPrice: String,
+  }
+
   #[allow(non_snake_case)]
   #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
   pub struct Product {
     pub Identifiers: product::Identifier,
+    pub AttributeSets: Vec<ItemAttributes>,
+    pub Relationships: Relationships,
+    pub CompetitivePricing: Option<CompetitivePricing>,
+    pub SalesRankings: Vec<SalesRank>,
+    pub LowestOfferListings: Vec<LowestOfferListing>,
     pub Offers: Vec<Offer>,
   }
+
+  impl Product {
+    /// Returns the ASIN of the product.
+    pub fn asin(&self) -> Option<&str> {
+      self
+        .Identifiers
+        .MarketplaceASIN
+        .as_ref()
+        .map(|id| id.ASIN.as_str())
+    }
+  }
+}
+
+pub mod fees {
+  //! Types of `GetMyFeesEstimate`
+
+  use super::*;
+  use result::{MwsError, MwsResult};
+
+  /// The price used to estimate fees
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, Clone, PartialEq, Serialize, FromXmlStream, SerializeMwsParams)]
+  pub struct PriceToEstimateFees {
+    pub ListingPrice: MoneyType,
+    pub Shipping: Option<MoneyType>,
+    pub Points: Option<Points>,
+  }
+
+  /// A product, price, and fulfillment channel to estimate fees for
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, Clone, Serialize, SerializeMwsParams)]
+  pub struct FeesEstimateRequest {
+    pub MarketplaceId: String,
+    /// `ASIN` or `SellerSKU`
+    pub IdType: IdType,
+    pub IdValue: String,
+    pub IsAmazonFulfilled: bool,
+    /// A unique value to match the result with the request, returned as
+    /// `FeesEstimateIdentifier.SellerInputIdentifier`.
+    pub Identifier: String,
+    pub PriceToEstimateFees: PriceToEstimateFees,
+  }
+
+  /// The request of a `FeesEstimateResult`
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct FeesEstimateIdentifier {
+    pub MarketplaceId: String,
+    pub IdType: IdType,
+    pub SellerId: String,
+    pub SellerInputIdentifier: String,
+    pub IsAmazonFulfilled: bool,
+    pub IdValue: String,
+    pub PriceToEstimateFees: PriceToEstimateFees,
+  }
+
+  /// A fee included in a `FeeDetail`, e.g. the `FBAPickAndPack` fee of `FBAFees`
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct IncludedFeeDetail {
+    pub FeeType: String,
+    pub FeeAmount: MoneyType,
+    pub FeePromotion: Option<MoneyType>,
+    pub TaxAmount: Option<MoneyType>,
+    pub FinalFee: MoneyType,
+  }
+
+  /// A fee, e.g. `AmazonReferralFee`, `VariableClosingFee` or `FBAFees`
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct FeeDetail {
+    pub FeeType: String,
+    pub FeeAmount: MoneyType,
+    pub FeePromotion: Option<MoneyType>,
+    pub TaxAmount: Option<MoneyType>,
+    /// `FeeAmount` minus `FeePromotion`, plus `TaxAmount`.
+    pub FinalFee: MoneyType,
+    pub IncludedFeeDetailList: Vec<IncludedFeeDetail>,
+  }
+
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct FeesEstimate {
+    pub TimeOfFeesEstimation: Option<DateTime<Utc>>,
+    pub TotalFeesEstimate: MoneyType,
+    pub FeeDetailList: Vec<FeeDetail>,
+  }
+
+  /// The estimated fees of a `FeesEstimateRequest`
+  ///
+  /// `FeesEstimate` is set if `Status` is `Success`, `Error` otherwise.
+  #[allow(non_snake_case)]
+  #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+  pub struct FeesEstimateResult {
+    pub FeesEstimateIdentifier: FeesEstimateIdentifier,
+    pub FeesEstimate: Option<FeesEstimate>,
+    pub Status: String,
+    pub Error: Option<ProductError>,
+  }
+
+  impl FeesEstimateResult {
+    /// Returns the amount the seller receives at the estimated price: the listing price plus
+    /// shipping, minus the total fees.
+    ///
+    /// Returns `Ok(None)` if there is no estimate, e.g. if `Status` is `ClientError`.
+    pub fn net_proceeds(&self) -> MwsResult<Option<MoneyType>> {
+      let price = &self.FeesEstimateIdentifier.PriceToEstimateFees;
+      self.net_proceeds_at(&price.ListingPrice, price.Shipping.as_ref())
+    }
+
+    /// Estimates the amount the seller receives if the item sells for `listing_price` plus
+    /// `shipping`, e.g. to check a candidate price against a margin floor without another
+    /// request.
+    ///
+    /// `AmazonReferralFee` is a percentage of the sales price, it is scaled from the estimated
+    /// price to the candidate price. All other fees are assumed to be fixed and the minimum
+    /// referral fee is not considered. Amounts are rounded half away from zero to the minor
+    /// unit of the currency, e.g. cents for `USD` and whole yen for `JPY`.
+    ///
+    /// Returns `Ok(None)` if there is no estimate, e.g. if `Status` is `ClientError`.
+    pub fn net_proceeds_at(
+      &self,
+      listing_price: &MoneyType,
+      shipping: Option<&MoneyType>,
+    ) -> MwsResult<Option<MoneyType>> {
+      match self.FeesEstimate {
+        Some(ref estimate) => self
+          .compute_net_proceeds(estimate, listing_price, shipping)
+          .map(Some),
+        None => Ok(None),
+      }
+    }
+
+    fn compute_net_proceeds(
+      &self,
+      estimate: &FeesEstimate,
+      listing_price: &MoneyType,
+      shipping: Option<&MoneyType>,
+    ) -> MwsResult<MoneyType> {
+      let currency = &listing_price.CurrencyCode;
+      let price = sales_price(listing_price, shipping, currency)?;
+      let mut fees = to_minor_units(&estimate.TotalFeesEstimate, currency)?;
+      if let Some(referral_fee) = estimate
+        .FeeDetailList
+        .iter()
+        .find(|fee| fee.FeeType == "AmazonReferralFee")
+      {
+        let estimated = &self.FeesEstimateIdentifier.PriceToEstimateFees;
+        let estimated_price = sales_price(
+          &estimated.ListingPrice,
+          estimated.Shipping.as_ref(),
+          currency,
+        )?;
+        if estimated_price != 0 {
+          let fee = to_minor_units(&referral_fee.FinalFee, currency)?;
+          fees = fees - fee + div_round(fee * price, estimated_price);
+        }
+      }
+      Ok(from_minor_units(price - fees, currency))
+    }
+  }
+
+  /// Currencies of the MWS marketplaces without a minor unit according to ISO 4217
+  ///
+  /// The currencies of all other marketplaces, e.g. `USD`, `EUR`, `GBP` or `INR`, have a minor
+  /// unit of 2 decimal places.
+  static ZERO_DECIMAL_CURRENCIES: &'static [&'static str] = &["JPY"];
+
+  /// Returns the number of decimal places of the minor unit of `currency`, e.g. 2 for `USD`.
+  fn minor_unit_digits(currency: &str) -> usize {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) {
+      0
+    } else {
+      2
+    }
+  }
+
+  /// Parses an amount into minor units of its currency, e.g. `"12.3"` USD into `1230`
+  /// and `"1200"` JPY into `1200`.
+  ///
+  /// Extra decimal places are rounded half away from zero, e.g. `"12.345"` USD into `1235`.
+  fn to_minor_units(money: &MoneyType, currency: &str) -> MwsResult<i64> {
+    if money.CurrencyCode != currency {
+      return Err(
+        format!(
+          "currency mismatch: expected '{}', found '{}'",
+          currency, money.CurrencyCode
+        )
+        .into(),
+      );
+    }
+    let err = || MwsError::ParseString {
+      what: money.Amount.clone(),
+      message: "invalid amount".to_string(),
+    };
+    let amount = money.Amount.trim();
+    let (negative, amount) = if amount.starts_with('-') {
+      (true, &amount[1..])
+    } else {
+      (false, amount)
+    };
+    let mut parts = amount.splitn(2, '.');
+    let units = parts.next().unwrap_or("");
+    let decimals = parts.next().unwrap_or("");
+    if units.is_empty()
+      || !units.chars().all(|c| c.is_ascii_digit())
+      || !decimals.chars().all(|c| c.is_ascii_digit())
+    {
+      return Err(err());
+    }
+    let digits = minor_unit_digits(currency);
+    let mut decimals: Vec<i64> = decimals
+      .chars()
+      .map(|c| i64::from(c as u8 - b'0'))
+      .collect();
+    let round_up = decimals.get(digits).map_or(false, |&d| d >= 5);
+    decimals.resize(digits, 0);
+    let units: i64 = units.parse().map_err(|_| err())?;
+    let mut minor = decimals
+      .into_iter()
+      .fold(Some(units), |acc, d| {
+        acc
+          .and_then(|acc| acc.checked_mul(10))
+          .and_then(|acc| acc.checked_add(d))
+      })
+      .ok_or_else(err)?;
+    if round_up {
+      minor += 1;
+    }
+    Ok(if negative { -minor } else { minor })
+  }
+
+  /// Returns the listing price plus shipping in minor units.
+  fn sales_price(
+    listing_price: &MoneyType,
+    shipping: Option<&MoneyType>,
+    currency: &str,
+  ) -> MwsResult<i64> {
+    Ok(
+      to_minor_units(listing_price, currency)?
+        + match shipping {
+          Some(shipping) => to_minor_units(shipping, currency)?,
+          None => 0,
+        },
+    )
+  }
+
+  /// Formats an amount in minor units of `currency`, e.g. `1230` USD as `"12.30"`.
+  fn from_minor_units(minor: i64, currency: &str) -> MoneyType {
+    let digits = minor_unit_digits(currency);
+    let amount = if digits == 0 {
+      minor.to_string()
+    } else {
+      let scale = 10i64.pow(digits as u32);
+      let sign = if minor < 0 { "-" } else { "" };
+      format!(
+        "{}{}.{:0width$}",
+        sign,
+        minor.abs() / scale,
+        minor.abs() % scale,
+        width = digits
+      )
+    };
+    MoneyType {
+      Amount: amount,
+      CurrencyCode: currency.to_string(),
+    }
+  }
+
+  /// Divides with rounding half away from zero.
+  fn div_round(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    let r = a % b;
+    if r != 0 && r.abs() * 2 >= b.abs() {
+      if (a < 0) != (b < 0) {
+        q - 1
+      } else {
+        q + 1
+      }
+    } else {
+      q
+    }
+  }
+}
+
+/// A product category and its parent categories
+///
+/// MWS returns the category as a chain from the leaf to the root:
+/// each `Parent` element contains the parent category of the enclosing element.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ProductCategory {
+  /// The browse node id of the category.
+  pub ProductCategoryId: String,
+  pub ProductCategoryName: String,
+  pub Parent: Option<Box<ProductCategory>>,
+}
+
+impl ProductCategory {
+  /// Returns the categories from the root to this category.
+  pub fn path(&self) -> Vec<&ProductCategory> {
+    let mut path = vec![];
+    let mut category = Some(self);
+    while let Some(c) = category {
+      path.push(c);
+      category = c.Parent.as_ref().map(|parent| &**parent);
+    }
+    path.reverse();
+    path
+  }
+
+  /// Returns the category names from the root to this category, e.g.
+  /// `["Apparel & Accessories", "Clothing & Accessories", "Men's"]`.
+  pub fn path_names(&self) -> Vec<&str> {
+    self
+      .path()
+      .into_iter()
+      .map(|c| c.ProductCategoryName.as_str())
+      .collect()
+  }
+}
+
+// A derived impl would instantiate `from_xml` for a new stream type at each level of `Parent`,
+// the events are consumed without nested element scoped streams instead.
+impl<S: XmlEventStream> FromXmlStream<S> for ProductCategory {
+  fn from_xml(s: &mut S) -> MwsResult<ProductCategory> {
+    use xml::reader::XmlEvent;
+
+    // leaf first
+    let mut chain = vec![ProductCategory::default()];
+    let mut depth = 0;
+    let mut field: Option<String> = None;
+    while let Some(event) = s.next() {
+      match event? {
+        XmlEvent::StartElement { name, .. } => {
+          if name.local_name == "Parent" {
+            depth += 1;
+            if chain.len() <= depth {
+              chain.push(ProductCategory::default());
+            }
+          } else {
+            field = Some(name.local_name);
+          }
+        }
+        XmlEvent::Characters(value) => match field.as_ref().map(String::as_str) {
+          Some("ProductCategoryId") => chain[depth].ProductCategoryId = value,
+          Some("ProductCategoryName") => chain[depth].ProductCategoryName = value,
+          _ => {}
+        },
+        XmlEvent::EndElement { name } => {
+          if name.local_name == "Parent" {
+            depth = depth.saturating_sub(1);
+          } else {
+            field = None;
+          }
+        }
+        _ => {}
+      }
+    }
+    Ok(
+      chain
+        .into_iter()
+        .rev()
+        .fold(None, |parent, mut category| {
+          category.Parent = parent.map(Box::new);
+          Some(category)
+        })
+        .unwrap_or_default(),
+    )
+  }
+}
+
+/// An error of a single identifier in a batch operation, e.g. an invalid ASIN
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ProductError {
+  pub Type: String,
+  pub Code: String,
+  pub Message: String,
+}
+
+impl ProductError {
+  /// Returns the error code as a `MwsErrorCode`.
+  pub fn error_code(&self) -> MwsErrorCode {
+    MwsErrorCode::from(self.Code.as_ref())
+  }
+}
+
+str_enum! {
+  /// The type of the identifiers of `GetMatchingProductForId`
+  pub enum IdType {
+    ASIN,
+    GCID,
+    SellerSKU,
+    UPC,
+    EAN,
+    ISBN,
+    JAN,
+  }
 }
 
 str_enum! {
@@ -77,7 +657,10 @@ str_enum! {
 #[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
 pub struct Identifier {
   pub MarketplaceId: String,
+  /// Set in the response of `GetLowestPricedOffersForSKU`.
   pub SellerSKU: String,
+  /// Set in the response of `GetLowestPricedOffersForASIN`.
+  pub ASIN: String,
   pub ItemCondition: ItemCondition,
   pub TimeOfOfferChange: Option<DateTime<Utc>>,
 }
@@ -94,14 +677,17 @@ pub struct OfferCount {
 }
 
 #[allow(non_snake_case)]
-#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, FromXmlStream, SerializeMwsParams)]
 pub struct MoneyType {
   pub Amount: String,
   pub CurrencyCode: String,
 }
 
+/// Amazon Points (Japan only)
+///
+/// Only `PointsNumber` is used in requests.
 #[allow(non_snake_case)]
-#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, FromXmlStream, SerializeMwsParams)]
 pub struct Points {
   pub PointsNumber: i32,
   pub PointsMonetaryValue: MoneyType,
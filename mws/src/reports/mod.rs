@@ -2,15 +2,19 @@
 //!
 //! [Documentation](http://docs.developer.amazonservices.com/en_US/reports/Reports_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use chrono::{DateTime, Utc};
 use client::{Client, Method};
+use futures::Future;
 mod types;
-pub use self::types::{ReportInfo, ReportProcessingStatus, ReportRequestInfo};
+pub use self::types::{
+  ReportInfo, ReportProcessingStatus, ReportRequestInfo, ReportSchedule, Schedule,
+};
 use result::{MwsError, MwsResult};
 use std::io::{self, Write};
 
-static PATH: &'static str = "/";
-static VERSION: &'static str = "2009-01-01";
+pub(crate) const PATH: &'static str = "/";
+pub(crate) const VERSION: &'static str = "2009-01-01";
 
 /// Parameters for `GetReportList`
 #[allow(non_snake_case)]
@@ -47,16 +51,37 @@ response_envelope_type!(
   "GetReportListByNextTokenResult"
 );
 
+next_token_response!(
+  GetReportListResponse,
+  ReportInfo: ReportInfo,
+  GetReportListByNextToken,
+  GetReportListByNextTokenAsync, HasNext
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportList",
+  GetReportListParameters => GetReportListEnvelope<GetReportListResponse>,
+  ByNextToken: GetReportListByNextTokenParameters => GetReportListByNextTokenEnvelope
+);
+
 /// Returns a list of reports that were created in the previous 90 days.
 #[allow(non_snake_case)]
 pub fn GetReportList(
   client: &Client,
   params: GetReportListParameters,
 ) -> MwsResult<GetReportListResponse> {
-  client
-    .request_xml(Method::Post, PATH, VERSION, "GetReportList", params)
-    .map(|e: GetReportListEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportList`](fn.GetReportList.html).
+#[allow(non_snake_case)]
+pub fn GetReportListAsync(
+  client: &AsyncClient,
+  params: GetReportListParameters,
+) -> MwsFuture<GetReportListResponse> {
+  client.execute(params)
 }
 
 /// Returns a list of reports using the NextToken, which was supplied by a previous request to either
@@ -66,17 +91,20 @@ pub fn GetReportListByNextToken(
   client: &Client,
   next_token: String,
 ) -> MwsResult<GetReportListResponse> {
-  let params = vec![("NextToken".to_string(), next_token)];
-  client
-    .request_xml(
-      Method::Post,
-      PATH,
-      VERSION,
-      "GetReportListByNextToken",
-      params,
-    )
-    .map(|e: GetReportListByNextTokenEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(GetReportListByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`GetReportListByNextToken`](fn.GetReportListByNextToken.html).
+#[allow(non_snake_case)]
+pub fn GetReportListByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<GetReportListResponse> {
+  client.execute(GetReportListByNextTokenParameters {
+    NextToken: next_token,
+  })
 }
 
 /// Returns the contents of a report and the Content-MD5 header for the returned report body.
@@ -89,15 +117,32 @@ pub fn GetReport<W: Write>(
   let params = vec![("ReportId".to_string(), report_id)];
   let mut resp = client.request(Method::Post, PATH, VERSION, "GetReport", params)?;
   let content_md5 = resp
-    .headers()
-    .get_raw("Content-MD5")
-    .ok_or_else(|| MwsError::ContentMD5HeaderMissing)
-    .and_then(|data| ::std::str::from_utf8(&data[0]).map_err(Into::into))?
+    .header("Content-MD5")
+    .ok_or_else(|| MwsError::ContentMD5HeaderMissing)?
     .to_owned();
   let size = io::copy(&mut resp, out)?;
   Ok((size, content_md5))
 }
 
+/// Asynchronous version of [`GetReport`](fn.GetReport.html).
+///
+/// Resolves to the report body and the Content-MD5 header.
+#[allow(non_snake_case)]
+pub fn GetReportAsync(client: &AsyncClient, report_id: String) -> MwsFuture<(Vec<u8>, String)> {
+  let params = vec![("ReportId".to_string(), report_id)];
+  Box::new(
+    client
+      .request(Method::Post, PATH, VERSION, "GetReport", params)
+      .and_then(|resp| -> MwsResult<_> {
+        let content_md5 = resp
+          .header("Content-MD5")
+          .ok_or_else(|| MwsError::ContentMD5HeaderMissing)?
+          .to_owned();
+        Ok((resp.into_bytes()?, content_md5))
+      }),
+  )
+}
+
 /// Parameters for `GetReportRequestList`
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -134,16 +179,37 @@ response_envelope_type!(
   "GetReportRequestListByNextTokenResult"
 );
 
+next_token_response!(
+  GetReportRequestListResponse,
+  ReportRequestInfo: ReportRequestInfo,
+  GetReportRequestListByNextToken,
+  GetReportRequestListByNextTokenAsync, HasNext
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportRequestList",
+  GetReportRequestListParameters => GetReportRequestListEnvelope<GetReportRequestListResponse>,
+  ByNextToken: GetReportRequestListByNextTokenParameters => GetReportRequestListByNextTokenEnvelope
+);
+
 /// Returns a list of report requests that you can use to get the ReportRequestId for a report.
 #[allow(non_snake_case)]
 pub fn GetReportRequestList(
   client: &Client,
   params: GetReportRequestListParameters,
 ) -> MwsResult<GetReportRequestListResponse> {
-  client
-    .request_xml(Method::Post, PATH, VERSION, "GetReportRequestList", params)
-    .map(|e: GetReportRequestListEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportRequestList`](fn.GetReportRequestList.html).
+#[allow(non_snake_case)]
+pub fn GetReportRequestListAsync(
+  client: &AsyncClient,
+  params: GetReportRequestListParameters,
+) -> MwsFuture<GetReportRequestListResponse> {
+  client.execute(params)
 }
 
 /// Returns a list of reports using the NextToken, which was supplied by a previous request to either
@@ -153,17 +219,20 @@ pub fn GetReportRequestListByNextToken(
   client: &Client,
   next_token: String,
 ) -> MwsResult<GetReportRequestListResponse> {
-  let params = vec![("NextToken".to_string(), next_token)];
-  client
-    .request_xml(
-      Method::Post,
-      PATH,
-      VERSION,
-      "GetReportRequestListByNextToken",
-      params,
-    )
-    .map(|e: GetReportRequestListByNextTokenEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(GetReportRequestListByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`GetReportRequestListByNextToken`](fn.GetReportRequestListByNextToken.html).
+#[allow(non_snake_case)]
+pub fn GetReportRequestListByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<GetReportRequestListResponse> {
+  client.execute(GetReportRequestListByNextTokenParameters {
+    NextToken: next_token,
+  })
 }
 
 /// Parameters for `RequestReport`
@@ -190,15 +259,415 @@ response_envelope_type!(
   "RequestReportResult"
 );
 
+mws_operation!(
+  PATH,
+  VERSION,
+  "RequestReport",
+  RequestReportParameters => RequestReportEnvelope<RequestReportResponse>,
+  idempotent: false
+);
+
 #[allow(non_snake_case)]
 pub fn RequestReport(
   client: &Client,
   params: RequestReportParameters,
 ) -> MwsResult<RequestReportResponse> {
-  client
-    .request_xml(Method::Post, PATH, VERSION, "RequestReport", params)
-    .map(|e: RequestReportEnvelope| e.into_inner())
-    .map_err(|err| err.into())
+  client.execute(params)
+}
+
+/// Asynchronous version of [`RequestReport`](fn.RequestReport.html).
+#[allow(non_snake_case)]
+pub fn RequestReportAsync(
+  client: &AsyncClient,
+  params: RequestReportParameters,
+) -> MwsFuture<RequestReportResponse> {
+  client.execute(params)
+}
+
+/// The response of the `Get...Count` operations
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, FromXmlStream)]
+pub struct CountResponse {
+  pub Count: i32,
+}
+
+/// Parameters for `GetReportCount`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetReportCountParameters {
+  #[mws_param(list_item_type_name = "Type")]
+  pub ReportTypeList: Option<Vec<String>>,
+  pub Acknowledged: Option<bool>,
+  pub AvailableFromDate: Option<DateTime<Utc>>,
+  pub AvailableToDate: Option<DateTime<Utc>>,
+}
+
+response_envelope_type!(
+  GetReportCountEnvelope<CountResponse>,
+  "GetReportCountResponse",
+  "GetReportCountResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportCount",
+  GetReportCountParameters => GetReportCountEnvelope<CountResponse>,
+  idempotent: true
+);
+
+/// Returns a count of the reports, created in the previous 90 days, with a status of `_DONE_`
+/// and that are available for download.
+#[allow(non_snake_case)]
+pub fn GetReportCount(
+  client: &Client,
+  params: GetReportCountParameters,
+) -> MwsResult<CountResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportCount`](fn.GetReportCount.html).
+#[allow(non_snake_case)]
+pub fn GetReportCountAsync(
+  client: &AsyncClient,
+  params: GetReportCountParameters,
+) -> MwsFuture<CountResponse> {
+  client.execute(params)
+}
+
+/// Parameters for `GetReportRequestCount`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetReportRequestCountParameters {
+  #[mws_param(list_item_type_name = "Type")]
+  pub ReportTypeList: Option<Vec<String>>,
+  #[mws_param(list_item_type_name = "Status")]
+  pub ReportProcessingStatusList: Option<Vec<ReportProcessingStatus>>,
+  pub RequestedFromDate: Option<DateTime<Utc>>,
+  pub RequestedToDate: Option<DateTime<Utc>>,
+}
+
+response_envelope_type!(
+  GetReportRequestCountEnvelope<CountResponse>,
+  "GetReportRequestCountResponse",
+  "GetReportRequestCountResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportRequestCount",
+  GetReportRequestCountParameters => GetReportRequestCountEnvelope<CountResponse>,
+  idempotent: true
+);
+
+/// Returns a count of report requests that have been submitted to Amazon MWS for processing.
+#[allow(non_snake_case)]
+pub fn GetReportRequestCount(
+  client: &Client,
+  params: GetReportRequestCountParameters,
+) -> MwsResult<CountResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportRequestCount`](fn.GetReportRequestCount.html).
+#[allow(non_snake_case)]
+pub fn GetReportRequestCountAsync(
+  client: &AsyncClient,
+  params: GetReportRequestCountParameters,
+) -> MwsFuture<CountResponse> {
+  client.execute(params)
+}
+
+/// Parameters for `CancelReportRequests`
+///
+/// Without parameters all report requests which are `_SUBMITTED_` are cancelled.
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct CancelReportRequestsParameters {
+  #[mws_param(list_item_type_name = "Id")]
+  pub ReportRequestIdList: Option<Vec<String>>,
+  #[mws_param(list_item_type_name = "Type")]
+  pub ReportTypeList: Option<Vec<String>>,
+  #[mws_param(list_item_type_name = "Status")]
+  pub ReportProcessingStatusList: Option<Vec<ReportProcessingStatus>>,
+  pub RequestedFromDate: Option<DateTime<Utc>>,
+  pub RequestedToDate: Option<DateTime<Utc>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, FromXmlStream)]
+pub struct CancelReportRequestsResponse {
+  /// The number of cancelled report requests.
+  pub Count: i32,
+  #[from_xml_stream(no_list_wrapper)]
+  pub ReportRequestInfo: Vec<ReportRequestInfo>,
+}
+
+response_envelope_type!(
+  CancelReportRequestsEnvelope<CancelReportRequestsResponse>,
+  "CancelReportRequestsResponse",
+  "CancelReportRequestsResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "CancelReportRequests",
+  CancelReportRequestsParameters => CancelReportRequestsEnvelope<CancelReportRequestsResponse>,
+  idempotent: false
+);
+
+/// Cancels one or more report requests.
+#[allow(non_snake_case)]
+pub fn CancelReportRequests(
+  client: &Client,
+  params: CancelReportRequestsParameters,
+) -> MwsResult<CancelReportRequestsResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`CancelReportRequests`](fn.CancelReportRequests.html).
+#[allow(non_snake_case)]
+pub fn CancelReportRequestsAsync(
+  client: &AsyncClient,
+  params: CancelReportRequestsParameters,
+) -> MwsFuture<CancelReportRequestsResponse> {
+  client.execute(params)
+}
+
+/// Parameters for `UpdateReportAcknowledgements`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct UpdateReportAcknowledgementsParameters {
+  /// Up to 100 report ids.
+  #[mws_param(list_item_type_name = "Id")]
+  pub ReportIdList: Vec<String>,
+  pub Acknowledged: Option<bool>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct UpdateReportAcknowledgementsResponse {
+  /// The number of updated reports.
+  pub Count: i32,
+  #[from_xml_stream(no_list_wrapper)]
+  pub ReportInfo: Vec<ReportInfo>,
+}
+
+response_envelope_type!(
+  UpdateReportAcknowledgementsEnvelope<UpdateReportAcknowledgementsResponse>,
+  "UpdateReportAcknowledgementsResponse",
+  "UpdateReportAcknowledgementsResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "UpdateReportAcknowledgements",
+  UpdateReportAcknowledgementsParameters
+    => UpdateReportAcknowledgementsEnvelope<UpdateReportAcknowledgementsResponse>,
+  idempotent: false
+);
+
+/// Updates the acknowledged status of one or more reports, e.g. to skip processed reports
+/// with `GetReportListParameters.Acknowledged`.
+#[allow(non_snake_case)]
+pub fn UpdateReportAcknowledgements(
+  client: &Client,
+  params: UpdateReportAcknowledgementsParameters,
+) -> MwsResult<UpdateReportAcknowledgementsResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`UpdateReportAcknowledgements`](fn.UpdateReportAcknowledgements.html).
+#[allow(non_snake_case)]
+pub fn UpdateReportAcknowledgementsAsync(
+  client: &AsyncClient,
+  params: UpdateReportAcknowledgementsParameters,
+) -> MwsFuture<UpdateReportAcknowledgementsResponse> {
+  client.execute(params)
+}
+
+/// Parameters for `ManageReportSchedule`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct ManageReportScheduleParameters {
+  pub ReportType: String,
+  pub Schedule: Schedule,
+  /// The date when the next report should run, defaults to now.
+  pub ScheduleDate: Option<DateTime<Utc>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ManageReportScheduleResponse {
+  /// The number of created, updated or deleted schedules.
+  pub Count: i32,
+  #[from_xml_stream(no_list_wrapper)]
+  pub ReportSchedule: Vec<ReportSchedule>,
+}
+
+response_envelope_type!(
+  ManageReportScheduleEnvelope<ManageReportScheduleResponse>,
+  "ManageReportScheduleResponse",
+  "ManageReportScheduleResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "ManageReportSchedule",
+  ManageReportScheduleParameters => ManageReportScheduleEnvelope<ManageReportScheduleResponse>,
+  idempotent: false
+);
+
+/// Creates, updates, or deletes a report request schedule for a specified report type.
+///
+/// Use `Schedule::_NEVER_` to delete the schedule of the report type.
+#[allow(non_snake_case)]
+pub fn ManageReportSchedule(
+  client: &Client,
+  params: ManageReportScheduleParameters,
+) -> MwsResult<ManageReportScheduleResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`ManageReportSchedule`](fn.ManageReportSchedule.html).
+#[allow(non_snake_case)]
+pub fn ManageReportScheduleAsync(
+  client: &AsyncClient,
+  params: ManageReportScheduleParameters,
+) -> MwsFuture<ManageReportScheduleResponse> {
+  client.execute(params)
+}
+
+/// Parameters for `GetReportScheduleList`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetReportScheduleListParameters {
+  #[mws_param(list_item_type_name = "Type")]
+  pub ReportTypeList: Option<Vec<String>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct GetReportScheduleListResponse {
+  #[from_xml_stream(no_list_wrapper)]
+  pub ReportSchedule: Vec<ReportSchedule>,
+  pub NextToken: Option<String>,
+  pub HasNext: bool,
+}
+
+response_envelope_type!(
+  GetReportScheduleListEnvelope<GetReportScheduleListResponse>,
+  "GetReportScheduleListResponse",
+  "GetReportScheduleListResult"
+);
+
+response_envelope_type!(
+  GetReportScheduleListByNextTokenEnvelope<GetReportScheduleListResponse>,
+  "GetReportScheduleListByNextTokenResponse",
+  "GetReportScheduleListByNextTokenResult"
+);
+
+next_token_response!(
+  GetReportScheduleListResponse,
+  ReportSchedule: ReportSchedule,
+  GetReportScheduleListByNextToken,
+  GetReportScheduleListByNextTokenAsync, HasNext
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportScheduleList",
+  GetReportScheduleListParameters => GetReportScheduleListEnvelope<GetReportScheduleListResponse>,
+  ByNextToken:
+    GetReportScheduleListByNextTokenParameters => GetReportScheduleListByNextTokenEnvelope
+);
+
+/// Returns a list of order report requests that are scheduled to be submitted to Amazon MWS
+/// for processing.
+#[allow(non_snake_case)]
+pub fn GetReportScheduleList(
+  client: &Client,
+  params: GetReportScheduleListParameters,
+) -> MwsResult<GetReportScheduleListResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportScheduleList`](fn.GetReportScheduleList.html).
+#[allow(non_snake_case)]
+pub fn GetReportScheduleListAsync(
+  client: &AsyncClient,
+  params: GetReportScheduleListParameters,
+) -> MwsFuture<GetReportScheduleListResponse> {
+  client.execute(params)
+}
+
+/// Returns a list of report schedules using the NextToken, which was supplied by a previous
+/// request to either GetReportScheduleListByNextToken or GetReportScheduleList, where the value
+/// of HasNext was true in the previous call.
+#[allow(non_snake_case)]
+pub fn GetReportScheduleListByNextToken(
+  client: &Client,
+  next_token: String,
+) -> MwsResult<GetReportScheduleListResponse> {
+  client.execute(GetReportScheduleListByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`GetReportScheduleListByNextToken`](fn.GetReportScheduleListByNextToken.html).
+#[allow(non_snake_case)]
+pub fn GetReportScheduleListByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<GetReportScheduleListResponse> {
+  client.execute(GetReportScheduleListByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Parameters for `GetReportScheduleCount`
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Serialize, SerializeMwsParams)]
+pub struct GetReportScheduleCountParameters {
+  #[mws_param(list_item_type_name = "Type")]
+  pub ReportTypeList: Option<Vec<String>>,
+}
+
+response_envelope_type!(
+  GetReportScheduleCountEnvelope<CountResponse>,
+  "GetReportScheduleCountResponse",
+  "GetReportScheduleCountResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "GetReportScheduleCount",
+  GetReportScheduleCountParameters => GetReportScheduleCountEnvelope<CountResponse>,
+  idempotent: true
+);
+
+/// Returns a count of order report requests that are scheduled to be submitted to Amazon MWS.
+#[allow(non_snake_case)]
+pub fn GetReportScheduleCount(
+  client: &Client,
+  params: GetReportScheduleCountParameters,
+) -> MwsResult<CountResponse> {
+  client.execute(params)
+}
+
+/// Asynchronous version of [`GetReportScheduleCount`](fn.GetReportScheduleCount.html).
+#[allow(non_snake_case)]
+pub fn GetReportScheduleCountAsync(
+  client: &AsyncClient,
+  params: GetReportScheduleCountParameters,
+) -> MwsFuture<CountResponse> {
+  client.execute(params)
 }
 
 #[cfg(test)]
@@ -211,7 +680,7 @@ mod tests {
   #[ignore]
   fn test_get_report_list() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("reports_get_report_list");
     let mut params = GetReportListParameters::default();
     params.ReportTypeList = Some(vec!["_GET_V2_SETTLEMENT_REPORT_DATA_FLAT_FILE_".to_owned()]);
     let res = GetReportList(&c, params).expect("GetReportList");
@@ -222,7 +691,7 @@ mod tests {
   // #[ignore]
   // fn test_settlement_report() {
   //   dotenv().ok();
-  //   let c = get_test_client();
+  //   let c = get_test_client("reports_settlement_report");
   //   let res = GetFlatFileSettlementReport(&c, "3915548544017177".to_string())
   //     .expect("GetFlatFileSettlementReport");
   //   println!("{:?}", res);
@@ -232,7 +701,7 @@ mod tests {
   #[ignore]
   fn test_get_report_request_list() {
     dotenv().ok();
-    let c = get_test_client();
+    let c = get_test_client("reports_get_report_request_list");
     let mut params = GetReportRequestListParameters::default();
     params.ReportTypeList = Some(vec!["_GET_AFN_INVENTORY_DATA_".to_owned()]);
     let res = GetReportRequestList(&c, params).expect("GetReportRequestList");
@@ -243,4 +712,119 @@ mod tests {
       GetReportRequestListByNextToken(&c, next_token).expect("GetReportRequestListByNextToken");
     println!("{:?}", res);
   }
+
+  #[test]
+  fn test_update_report_acknowledgements_parameters() {
+    use SerializeMwsParams;
+    let pairs = UpdateReportAcknowledgementsParameters {
+      ReportIdList: vec!["6297036773".to_string(), "6297036774".to_string()],
+      Acknowledged: Some(true),
+    }
+    .into_mws_params();
+    assert_eq!(
+      pairs,
+      vec![
+        ("ReportIdList.Id.1".to_string(), "6297036773".to_string()),
+        ("ReportIdList.Id.2".to_string(), "6297036774".to_string()),
+        ("Acknowledged".to_string(), "true".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_decode_update_report_acknowledgements_response() {
+    test_decode_envelope!(
+      UpdateReportAcknowledgementsEnvelope,
+      r#"
+        <UpdateReportAcknowledgementsResponse xmlns="http://mws.amazonaws.com/doc/2009-01-01/">
+          <UpdateReportAcknowledgementsResult>
+            <Count>1</Count>
+            <ReportInfo>
+              <ReportId>6297036773</ReportId>
+              <ReportType>_GET_ORDERS_DATA_</ReportType>
+              <ReportRequestId>2278662938</ReportRequestId>
+              <AvailableDate>2009-02-20T02:10:35+00:00</AvailableDate>
+              <Acknowledged>true</Acknowledged>
+              <AcknowledgedDate>2009-02-20T11:44:45+00:00</AcknowledgedDate>
+            </ReportInfo>
+          </UpdateReportAcknowledgementsResult>
+          <ResponseMetadata>
+            <RequestId>efc8a6b2-9b42-4f0d-8c5c-b2f9d7f4f9a1</RequestId>
+          </ResponseMetadata>
+        </UpdateReportAcknowledgementsResponse>
+      "#,
+      UpdateReportAcknowledgementsResponse {
+        Count: 1,
+        ReportInfo: vec![ReportInfo {
+          ReportType: "_GET_ORDERS_DATA_".to_string(),
+          Acknowledged: true,
+          AcknowledgedDate: Some("2009-02-20T11:44:45Z".parse().unwrap()),
+          ReportId: "6297036773".to_string(),
+          AvailableDate: Some("2009-02-20T02:10:35Z".parse().unwrap()),
+          ReportRequestId: "2278662938".to_string(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn test_manage_report_schedule_parameters() {
+    use SerializeMwsParams;
+    let pairs = ManageReportScheduleParameters {
+      ReportType: "_GET_ORDERS_DATA_".to_string(),
+      Schedule: Schedule::_8_HOURS_,
+      ScheduleDate: None,
+    }
+    .into_mws_params();
+    assert_eq!(
+      pairs,
+      vec![
+        ("ReportType".to_string(), "_GET_ORDERS_DATA_".to_string()),
+        ("Schedule".to_string(), "_8_HOURS_".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_decode_get_report_schedule_list_response() {
+    test_decode_envelope!(
+      GetReportScheduleListEnvelope,
+      r#"
+        <GetReportScheduleListResponse xmlns="http://mws.amazonaws.com/doc/2009-01-01/">
+          <GetReportScheduleListResult>
+            <NextToken>2YgYW55IGNhcm5hbCBwbGVhc3VyZS4=</NextToken>
+            <HasNext>true</HasNext>
+            <ReportSchedule>
+              <ReportType>_GET_ORDERS_DATA_</ReportType>
+              <Schedule>_8_HOURS_</Schedule>
+              <ScheduledDate>2009-02-20T02:10:42+00:00</ScheduledDate>
+            </ReportSchedule>
+            <ReportSchedule>
+              <ReportType>_GET_FLAT_FILE_OPEN_LISTINGS_DATA_</ReportType>
+              <Schedule>_1_DAY_</Schedule>
+            </ReportSchedule>
+          </GetReportScheduleListResult>
+          <ResponseMetadata>
+            <RequestId>675bc07d-7f5e-4bc7-a7b1-c2ce4b7dc8a3</RequestId>
+          </ResponseMetadata>
+        </GetReportScheduleListResponse>
+      "#,
+      GetReportScheduleListResponse {
+        ReportSchedule: vec![
+          ReportSchedule {
+            ReportType: "_GET_ORDERS_DATA_".to_string(),
+            Schedule: Schedule::_8_HOURS_,
+            ScheduledDate: Some("2009-02-20T02:10:42Z".parse().unwrap()),
+          },
+          ReportSchedule {
+            ReportType: "_GET_FLAT_FILE_OPEN_LISTINGS_DATA_".to_string(),
+            Schedule: Schedule::_1_DAY_,
+            ScheduledDate: None,
+          },
+        ],
+        NextToken: Some("2YgYW55IGNhcm5hbCBwbGVhc3VyZS4=".to_string()),
+        HasNext: true,
+      }
+    );
+  }
 }
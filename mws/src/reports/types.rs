@@ -35,3 +35,35 @@ pub struct ReportRequestInfo {
   pub StartedProcessingDate: Option<DateTime<Utc>>,
   pub CompletedDate: Option<DateTime<Utc>>,
 }
+
+str_enum! {
+  /// How often a report is requested by a report schedule
+  ///
+  /// `_NEVER_` deletes a schedule.
+  pub enum Schedule {
+    _15_MINUTES_,
+    _30_MINUTES_,
+    _1_HOUR_,
+    _2_HOURS_,
+    _4_HOURS_,
+    _8_HOURS_,
+    _12_HOURS_,
+    _1_DAY_,
+    _2_DAYS_,
+    _72_HOURS_,
+    _1_WEEK_,
+    _14_DAYS_,
+    _15_DAYS_,
+    _30_DAYS_,
+    _NEVER_,
+  }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ReportSchedule {
+  pub ReportType: String,
+  pub Schedule: Schedule,
+  /// The date when the next report is scheduled to run.
+  pub ScheduledDate: Option<DateTime<Utc>>,
+}
@@ -1,4 +1,5 @@
 pub use client::ErrorResponse as MwsErrorResponse;
+use client::MwsErrorCode;
 use types::GenericXmlResponseParseError;
 
 #[derive(Fail, Debug)]
@@ -7,6 +8,12 @@ pub enum MwsError {
   Io(#[cause] ::std::io::Error),
   #[fail(display = "http error: {}", _0)]
   Http(#[cause] ::reqwest::Error),
+  #[fail(display = "http error: {}", message)]
+  HttpRedacted {
+    message: String,
+    status: Option<::reqwest::StatusCode>,
+    redirect: bool,
+  },
   #[fail(display = "xml reader error: {}", _0)]
   XmlReader(#[cause] ::xml::reader::Error),
   #[fail(display = "xml writer error: {}", _0)]
@@ -34,6 +41,20 @@ pub enum MwsError {
   InvalidPath(String),
   #[fail(display = "Content-MD5 header missing")]
   ContentMD5HeaderMissing,
+  #[fail(
+    display = "request throttled: '{}', retry after {:?}",
+    action, retry_after
+  )]
+  Throttled {
+    action: String,
+    retry_after: ::std::time::Duration,
+  },
+  #[fail(display = "missing credentials: {}", _0)]
+  MissingCredentials(String),
+  #[fail(display = "unknown region: '{}'", _0)]
+  UnknownRegion(String),
+  #[fail(display = "dry run: the request was not sent")]
+  DryRun,
   #[fail(display = "{}", _0)]
   Msg(String),
 }
@@ -42,13 +63,31 @@ impl MwsError {
   /// The common response to a 500 or 503 service error is
   /// to try the request again. Such service errors are
   /// usually only temporary and will resolve themselves.
+  ///
+  /// Error codes take precedence over the HTTP status,
+  /// e.g. a `NonRetriableInternalError` is never retried.
   pub fn should_try_again(&self) -> bool {
     match *self {
       MwsError::ErrorResponse(ref res) => {
+        let codes = self.error_codes();
+        if codes.iter().any(MwsErrorCode::is_temporary) {
+          return true
+        }
+
+        if codes
+          .iter()
+          .any(|code| code.is_auth_error() || code.is_invalid_input())
+          || codes.contains(&MwsErrorCode::NonRetriableInternalError)
+          || codes.contains(&MwsErrorCode::DependencyFatalException)
+        {
+          return false
+        }
+
         let code = res.status.as_u16();
         code >= 500 && code < 600
       }
       MwsError::Io(_) => true,
+      MwsError::Throttled { .. } => true,
       MwsError::Http(ref err) => {
         if err.is_redirect() {
           return false
@@ -62,9 +101,53 @@ impl MwsError {
 
         return true
       }
+      MwsError::HttpRedacted {
+        ref status,
+        redirect,
+        ..
+      } => !redirect && !status.map(|s| s.is_client_error()).unwrap_or(false),
+      _ => false,
+    }
+  }
+
+  /// Returns the error codes of an `ErrorResponse`.
+  pub fn error_codes(&self) -> Vec<MwsErrorCode> {
+    match *self {
+      MwsError::ErrorResponse(ref res) => res
+        .info
+        .as_ref()
+        .map(|info| info.errors.iter().map(|e| e.error_code()).collect())
+        .unwrap_or_default(),
+      _ => vec![],
+    }
+  }
+
+  /// The request was throttled by MWS or by the client.
+  pub fn is_throttled(&self) -> bool {
+    match *self {
+      MwsError::Throttled { .. } => true,
+      _ => self.error_codes().iter().any(MwsErrorCode::is_throttled),
+    }
+  }
+
+  /// The request was rejected because of the credentials or the signature.
+  pub fn is_auth_error(&self) -> bool {
+    match *self {
+      MwsError::ErrorResponse(ref res) => {
+        let status = res.status.as_u16();
+        status == 401 || status == 403 || self.error_codes().iter().any(MwsErrorCode::is_auth_error)
+      }
       _ => false,
     }
   }
+
+  /// The request was rejected because of invalid parameters.
+  pub fn is_invalid_input(&self) -> bool {
+    self
+      .error_codes()
+      .iter()
+      .any(MwsErrorCode::is_invalid_input)
+  }
 }
 
 macro_rules! impl_from {
@@ -0,0 +1,240 @@
+//! Automatic retry of failed requests
+//!
+//! `Client` retries a request when [`MwsError::should_try_again`](../result/enum.MwsError.html#method.should_try_again)
+//! returns `true`, waiting with exponential backoff between attempts.
+//! The request is signed again before every attempt so the `Timestamp` stays fresh.
+
+use rand;
+use result::MwsError;
+use std::cmp;
+use std::time::Duration;
+
+/// Operations which are not safe to send twice.
+///
+/// These operations are retried only if the previous attempt was throttled,
+/// because a throttled request is rejected before it is processed.
+/// Typed operations declare this with [`MwsOperation::IDEMPOTENT`](../operation/trait.MwsOperation.html#associatedconstant.IDEMPOTENT) instead.
+static NON_IDEMPOTENT_ACTIONS: &'static [&'static str] = &[
+  // Feeds
+  "SubmitFeed",
+  "CancelFeedSubmissions",
+  // Reports
+  "RequestReport",
+  "CancelReportRequests",
+  "ManageReportSchedule",
+  "UpdateReportAcknowledgements",
+  // Fulfillment Inbound Shipment
+  "CreateInboundShipmentPlan",
+  "CreateInboundShipment",
+  "UpdateInboundShipment",
+  // Fulfillment Outbound Shipment
+  "CreateFulfillmentOrder",
+  "UpdateFulfillmentOrder",
+  "CancelFulfillmentOrder",
+  // Merchant Fulfillment
+  "CreateShipment",
+  "CancelShipment",
+  // Subscriptions
+  "RegisterDestination",
+  "DeregisterDestination",
+  "SendTestNotificationToDestination",
+  "CreateSubscription",
+  "DeleteSubscription",
+  "UpdateSubscription",
+];
+
+/// Returns `false` if `action` is not safe to send twice.
+pub fn is_idempotent(action: &str) -> bool {
+  !NON_IDEMPOTENT_ACTIONS.contains(&action)
+}
+
+/// Retry policy of `Client`
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+  /// Maximum number of attempts including the first one, `1` disables retries
+  pub max_attempts: u32,
+  /// Delay before the first retry, doubled on every following retry
+  pub base_delay: Duration,
+  /// Upper bound of the delay between two attempts
+  pub max_delay: Duration,
+  /// Fraction of the delay which is randomized, between `0.0` and `1.0`
+  pub jitter: f64,
+  /// Stop retrying once this much time has passed since the first attempt
+  pub max_elapsed: Option<Duration>,
+  /// Minimum delay after a `RequestThrottled` or `QuotaExceeded` error
+  pub throttled_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> RetryPolicy {
+    RetryPolicy::none()
+  }
+}
+
+impl RetryPolicy {
+  /// Never retry.
+  pub fn none() -> RetryPolicy {
+    RetryPolicy::new(1)
+  }
+
+  /// Retry up to `max_attempts` attempts with the default delays.
+  pub fn new(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+      max_attempts: max_attempts,
+      base_delay: Duration::from_secs(1),
+      max_delay: Duration::from_secs(60),
+      jitter: 0.5,
+      max_elapsed: Some(Duration::from_secs(300)),
+      throttled_delay: Duration::from_secs(10),
+    }
+  }
+
+  /// Returns the delay before the next attempt, or `None` if the request should not be retried.
+  ///
+  /// `idempotent` tells whether the request is safe to send twice, see `is_idempotent`.
+  /// `attempt` is the number of attempts made so far, `elapsed` is the time since the first attempt.
+  pub fn next_delay(
+    &self,
+    idempotent: bool,
+    err: &MwsError,
+    attempt: u32,
+    elapsed: Duration,
+  ) -> Option<Duration> {
+    if attempt >= self.max_attempts || !err.should_try_again() {
+      return None;
+    }
+
+    let throttled = err.is_throttled();
+    if !throttled && !idempotent {
+      return None;
+    }
+
+    let mut delay = self.backoff(attempt);
+    if throttled {
+      delay = cmp::max(delay, self.throttled_delay);
+    }
+    if let MwsError::Throttled { retry_after, .. } = *err {
+      delay = cmp::max(delay, retry_after);
+    }
+
+    if let Some(max_elapsed) = self.max_elapsed {
+      if elapsed + delay > max_elapsed {
+        return None;
+      }
+    }
+
+    Some(delay)
+  }
+
+  fn backoff(&self, attempt: u32) -> Duration {
+    let exp = cmp::min(attempt.saturating_sub(1), 31);
+    let delay = self
+      .base_delay
+      .checked_mul(1 << exp)
+      .map(|d| cmp::min(d, self.max_delay))
+      .unwrap_or(self.max_delay);
+    let jitter = self.jitter.max(0.0).min(1.0);
+    if jitter > 0.0 {
+      let factor = 1.0 - jitter * rand::random::<f64>();
+      let nanos = (delay.as_secs() as f64 * 1_000_000_000.0 + delay.subsec_nanos() as f64) * factor;
+      Duration::new(
+        (nanos / 1_000_000_000.0) as u64,
+        (nanos % 1_000_000_000.0) as u32,
+      )
+    } else {
+      delay
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use client::{ErrorResponse, ErrorResponseError, ErrorResponseInfo, StatusCode};
+
+  fn error_response(status: StatusCode, code: &str) -> MwsError {
+    MwsError::ErrorResponse(ErrorResponse {
+      status: status,
+      info: Some(ErrorResponseInfo {
+        errors: vec![ErrorResponseError {
+          error_type: "Sender".to_string(),
+          code: code.to_string(),
+          ..Default::default()
+        }],
+        request_id: "REQUEST".to_string(),
+      }),
+      raw: String::new(),
+    })
+  }
+
+  #[test]
+  fn test_next_delay() {
+    let policy = RetryPolicy {
+      jitter: 0.0,
+      ..RetryPolicy::new(4)
+    };
+    let err = error_response(StatusCode::InternalServerError, "InternalError");
+    let zero = Duration::from_secs(0);
+    assert_eq!(
+      policy.next_delay(true, &err, 1, zero),
+      Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+      policy.next_delay(true, &err, 3, zero),
+      Some(Duration::from_secs(4))
+    );
+    assert_eq!(policy.next_delay(true, &err, 4, zero), None);
+    assert_eq!(
+      policy.next_delay(true, &err, 1, Duration::from_secs(300)),
+      None
+    );
+
+    // not retryable
+    let err = error_response(StatusCode::BadRequest, "InvalidParameterValue");
+    assert_eq!(policy.next_delay(true, &err, 1, zero), None);
+
+    // not idempotent
+    let err = error_response(StatusCode::InternalServerError, "InternalError");
+    assert_eq!(policy.next_delay(false, &err, 1, zero), None);
+
+    // throttled requests are always retried
+    let err = error_response(StatusCode::ServiceUnavailable, "RequestThrottled");
+    assert_eq!(
+      policy.next_delay(false, &err, 1, zero),
+      Some(Duration::from_secs(10))
+    );
+  }
+
+  #[test]
+  fn test_is_idempotent() {
+    assert!(is_idempotent("ListOrders"));
+    assert!(is_idempotent("GetReportList"));
+    assert!(!is_idempotent("SubmitFeed"));
+    assert!(!is_idempotent("ManageReportSchedule"));
+    assert!(!is_idempotent("UpdateReportAcknowledgements"));
+    assert!(!is_idempotent("CancelReportRequests"));
+    assert!(!is_idempotent("CancelFulfillmentOrder"));
+    assert!(!is_idempotent("DeleteSubscription"));
+  }
+
+  #[test]
+  fn test_jitter() {
+    let policy = RetryPolicy::new(2);
+    let err = error_response(StatusCode::InternalServerError, "InternalError");
+    let delay = policy
+      .next_delay(true, &err, 1, Duration::from_secs(0))
+      .unwrap();
+    assert!(delay >= Duration::from_millis(500));
+    assert!(delay <= Duration::from_secs(1));
+  }
+
+  #[test]
+  fn test_none() {
+    let policy = RetryPolicy::default();
+    let err = error_response(StatusCode::InternalServerError, "InternalError");
+    assert_eq!(
+      policy.next_delay(true, &err, 1, Duration::from_secs(0)),
+      None
+    );
+  }
+}
@@ -0,0 +1,148 @@
+//! Secret redaction
+//!
+//! Credentials are wrapped in [`Secret`](struct.Secret.html), which never prints its value.
+//! Signed URLs carry the access key, the auth token and the signature in the query string,
+//! [`redact`](fn.redact.html) masks them in URLs and other text before they end up in
+//! errors or logs.
+
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+const REDACTED: &'static str = "REDACTED";
+
+/// Query parameters whose values are masked by `redact`
+pub static SENSITIVE_PARAMS: &'static [&'static str] =
+  &["AWSAccessKeyId", "MWSAuthToken", "Signature"];
+
+/// A credential which is masked in `Debug` output
+///
+/// ```
+/// use mws::secret::Secret;
+///
+/// let key = Secret::new("SECRET");
+/// assert_eq!(key.expose(), "SECRET");
+/// assert_eq!(format!("{:?}", key), "Secret(\"***\")");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+  pub fn new<S: Into<String>>(value: S) -> Secret {
+    Secret(value.into())
+  }
+
+  /// Returns the value, e.g. to sign a request.
+  pub fn expose(&self) -> &str {
+    &self.0
+  }
+}
+
+impl fmt::Debug for Secret {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.0.is_empty() {
+      f.write_str("Secret(\"\")")
+    } else {
+      f.write_str("Secret(\"***\")")
+    }
+  }
+}
+
+impl From<String> for Secret {
+  fn from(value: String) -> Secret {
+    Secret(value)
+  }
+}
+
+impl<'a> From<&'a str> for Secret {
+  fn from(value: &'a str) -> Secret {
+    Secret(value.to_string())
+  }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+  fn deserialize<D>(deserializer: D) -> Result<Secret, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    String::deserialize(deserializer).map(Secret)
+  }
+}
+
+/// Replaces the values of `SENSITIVE_PARAMS` in `text` with `REDACTED`.
+///
+/// `text` can be an URL, an url-encoded form or a message which contains either,
+/// e.g. the error message of a failed request.
+pub fn redact(text: &str) -> String {
+  let mut result = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some((start, name)) = find_param(rest) {
+    let value_start = start + name.len() + 1;
+    let value_len = rest[value_start..]
+      .find(|c: char| {
+        c == '&' || c == '#' || c == ')' || c == '"' || c == '\'' || c == '<' || c.is_whitespace()
+      })
+      .unwrap_or(rest.len() - value_start);
+    result.push_str(&rest[..value_start]);
+    if value_len > 0 {
+      result.push_str(REDACTED);
+    }
+    rest = &rest[value_start + value_len..];
+  }
+  result.push_str(rest);
+  result
+}
+
+/// Finds the first `Name=` of a sensitive parameter which starts a query pair.
+fn find_param(text: &str) -> Option<(usize, &'static str)> {
+  SENSITIVE_PARAMS
+    .iter()
+    .filter_map(|name| {
+      let pattern = format!("{}=", name);
+      text
+        .match_indices(&pattern as &str)
+        .map(|(i, _)| i)
+        .find(|&i| {
+          i == 0
+            || text[..i]
+              .chars()
+              .next_back()
+              .map(|c| c == '?' || c == '&' || c == ';' || c.is_whitespace())
+              .unwrap_or(false)
+        })
+        .map(|i| (i, *name))
+    })
+    .min_by_key(|&(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_redact() {
+    assert_eq!(
+      redact(
+        "https://mws.amazonservices.com/Orders/2013-09-01?AWSAccessKeyId=KEY&Action=ListOrders\
+         &MWSAuthToken=amzn.mws.TOKEN&SellerId=SELLER&Signature=abc%2Bdef%3D"
+      ),
+      "https://mws.amazonservices.com/Orders/2013-09-01?AWSAccessKeyId=REDACTED&Action=ListOrders\
+       &MWSAuthToken=REDACTED&SellerId=SELLER&Signature=REDACTED"
+    );
+    assert_eq!(
+      redact("error sending request for url (https://host/?Signature=abc): timed out"),
+      "error sending request for url (https://host/?Signature=REDACTED): timed out"
+    );
+    assert_eq!(
+      redact("<Detail>?XSignature=abc&AWSAccessKeyId=</Detail>"),
+      "<Detail>?XSignature=abc&AWSAccessKeyId=</Detail>"
+    );
+  }
+
+  #[test]
+  fn test_secret() {
+    let secret: Secret = "SECRET".into();
+    assert_eq!(secret.expose(), "SECRET");
+    assert!(!format!("{:?}", secret).contains("SECRET"));
+    assert_eq!(format!("{:?}", Secret::default()), "Secret(\"\")");
+  }
+}
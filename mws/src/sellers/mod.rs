@@ -0,0 +1,214 @@
+//! Amazon MWS Sellers API - Version 2011-07-01
+//!
+//! [Reference](http://docs.developer.amazonservices.com/en_US/sellers/Sellers_Overview.html)
+
+use async_client::{AsyncClient, MwsFuture};
+use client::Client;
+use constants::{AmazonMarketplace, MARKETPLACES};
+use pagination;
+mod types;
+pub use self::types::*;
+use result::MwsResult;
+use {SerializeMwsParams, SerializeMwsParamsContext};
+
+pub(crate) const PATH: &'static str = "/Sellers/2011-07-01";
+pub(crate) const VERSION: &'static str = "2011-07-01";
+
+get_service_status!(PATH, VERSION);
+
+/// Parameters for `ListMarketplaceParticipations`, the operation has no parameters
+/// besides the `SellerId` added by the client.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct ListMarketplaceParticipationsParameters;
+
+impl SerializeMwsParams for ListMarketplaceParticipationsParameters {
+  fn serialize_mws_params(
+    &self,
+    _ctx: &SerializeMwsParamsContext,
+    _pairs: &mut Vec<(String, String)>,
+  ) {
+  }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct ListMarketplaceParticipationsResponse {
+  pub ListParticipations: Vec<Participation>,
+  pub ListMarketplaces: Vec<Marketplace>,
+  pub NextToken: Option<String>,
+}
+
+impl ListMarketplaceParticipationsResponse {
+  /// Returns the marketplaces in `constants::MARKETPLACES` the seller participates in.
+  ///
+  /// Participations in other marketplaces, e.g. non-Amazon test marketplaces, are dropped.
+  pub fn known_marketplaces(&self) -> Vec<&'static AmazonMarketplace> {
+    MARKETPLACES
+      .iter()
+      .filter(|m| {
+        self
+          .ListParticipations
+          .iter()
+          .any(|p| p.MarketplaceId == m.id)
+      })
+      .collect()
+  }
+}
+
+response_envelope_type!(
+  ListMarketplaceParticipationsEnvelope<ListMarketplaceParticipationsResponse>,
+  "ListMarketplaceParticipationsResponse",
+  "ListMarketplaceParticipationsResult"
+);
+
+response_envelope_type!(
+  ListMarketplaceParticipationsByNextTokenEnvelope<ListMarketplaceParticipationsResponse>,
+  "ListMarketplaceParticipationsByNextTokenResponse",
+  "ListMarketplaceParticipationsByNextTokenResult"
+);
+
+mws_operation!(
+  PATH,
+  VERSION,
+  "ListMarketplaceParticipations",
+  ListMarketplaceParticipationsParameters => ListMarketplaceParticipationsEnvelope<ListMarketplaceParticipationsResponse>,
+  ByNextToken: ListMarketplaceParticipationsByNextTokenParameters => ListMarketplaceParticipationsByNextTokenEnvelope
+);
+
+next_token_response!(
+  ListMarketplaceParticipationsResponse,
+  ListParticipations: Participation,
+  ListMarketplaceParticipationsByNextToken,
+  ListMarketplaceParticipationsByNextTokenAsync
+);
+
+/// Returns the marketplaces the seller can sell in and the seller's participation in them.
+///
+/// [Documentation](http://docs.developer.amazonservices.com/en_US/sellers/Sellers_ListMarketplaceParticipations.html)
+#[allow(non_snake_case)]
+pub fn ListMarketplaceParticipations(
+  client: &Client,
+) -> MwsResult<ListMarketplaceParticipationsResponse> {
+  client.execute(ListMarketplaceParticipationsParameters)
+}
+
+/// Asynchronous version of [`ListMarketplaceParticipations`](fn.ListMarketplaceParticipations.html).
+#[allow(non_snake_case)]
+pub fn ListMarketplaceParticipationsAsync(
+  client: &AsyncClient,
+) -> MwsFuture<ListMarketplaceParticipationsResponse> {
+  client.execute(ListMarketplaceParticipationsParameters)
+}
+
+/// Returns the next page of marketplaces and participations using the NextToken parameter.
+#[allow(non_snake_case)]
+pub fn ListMarketplaceParticipationsByNextToken(
+  client: &Client,
+  next_token: String,
+) -> MwsResult<ListMarketplaceParticipationsResponse> {
+  client.execute(ListMarketplaceParticipationsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Asynchronous version of [`ListMarketplaceParticipationsByNextToken`](fn.ListMarketplaceParticipationsByNextToken.html).
+#[allow(non_snake_case)]
+pub fn ListMarketplaceParticipationsByNextTokenAsync(
+  client: &AsyncClient,
+  next_token: String,
+) -> MwsFuture<ListMarketplaceParticipationsResponse> {
+  client.execute(ListMarketplaceParticipationsByNextTokenParameters {
+    NextToken: next_token,
+  })
+}
+
+/// Returns the marketplaces in `constants::MARKETPLACES` the seller of `client` participates in,
+/// use it instead of `constants::get_region_marketplace_id_list` to skip marketplaces the seller
+/// is not enrolled in.
+///
+/// All pages of `ListMarketplaceParticipations` are requested.
+pub fn get_seller_marketplaces(client: &Client) -> MwsResult<Vec<&'static AmazonMarketplace>> {
+  let mut res = ListMarketplaceParticipationsResponse::default();
+  for page in pagination::pages(client, ListMarketplaceParticipations(client)) {
+    let mut page = page?;
+    res.ListParticipations.append(&mut page.ListParticipations);
+    res.ListMarketplaces.append(&mut page.ListMarketplaces);
+  }
+  Ok(res.known_marketplaces())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use constants::{MARKETPLACE_ID_CA, MARKETPLACE_ID_US};
+  use std::io::Cursor;
+  use xmlhelper::decode;
+  use xmlhelper::decode::FromXmlStream;
+
+  #[test]
+  fn test_decode_list_marketplace_participations_response() {
+    let mut s = decode::Stream::new(Cursor::new(
+      r#"
+      <ListMarketplaceParticipationsResponse xmlns="https://mws.amazonservices.com/Sellers/2011-07-01">
+        <ListMarketplaceParticipationsResult>
+          <NextToken>MRgZW55IGNhcm5hbCBwbGVhc3VyZS4=</NextToken>
+          <ListParticipations>
+            <Participation>
+              <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+              <SellerId>A135KKEKJAIBJ56</SellerId>
+              <HasSellerSuspendedListings>No</HasSellerSuspendedListings>
+            </Participation>
+            <Participation>
+              <MarketplaceId>A1MQXOICRS2Z7M</MarketplaceId>
+              <SellerId>A135KKEKJAIBJ56</SellerId>
+              <HasSellerSuspendedListings>No</HasSellerSuspendedListings>
+            </Participation>
+            <Participation>
+              <MarketplaceId>A2EUQ1WTGCTBG2</MarketplaceId>
+              <SellerId>A135KKEKJAIBJ56</SellerId>
+              <HasSellerSuspendedListings>Yes</HasSellerSuspendedListings>
+            </Participation>
+          </ListParticipations>
+          <ListMarketplaces>
+            <Marketplace>
+              <MarketplaceId>ATVPDKIKX0DER</MarketplaceId>
+              <Name>Amazon.com</Name>
+              <DefaultCountryCode>US</DefaultCountryCode>
+              <DefaultCurrencyCode>USD</DefaultCurrencyCode>
+              <DefaultLanguageCode>en_US</DefaultLanguageCode>
+              <DomainName>www.amazon.com</DomainName>
+            </Marketplace>
+          </ListMarketplaces>
+        </ListMarketplaceParticipationsResult>
+        <ResponseMetadata>
+          <RequestId>efeab958-74e2-45d4-9018-2323084413b6</RequestId>
+        </ResponseMetadata>
+      </ListMarketplaceParticipationsResponse>
+      "#,
+    ));
+    let res = ListMarketplaceParticipationsEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(res.ListParticipations.len(), 3);
+    assert!(!res.ListParticipations[0].has_suspended_listings());
+    assert!(res.ListParticipations[2].has_suspended_listings());
+    assert_eq!(
+      res.ListMarketplaces,
+      vec![Marketplace {
+        MarketplaceId: "ATVPDKIKX0DER".to_string(),
+        Name: "Amazon.com".to_string(),
+        DefaultCountryCode: "US".to_string(),
+        DefaultCurrencyCode: "USD".to_string(),
+        DefaultLanguageCode: "en_US".to_string(),
+        DomainName: "www.amazon.com".to_string(),
+      }]
+    );
+    assert_eq!(
+      res.NextToken,
+      Some("MRgZW55IGNhcm5hbCBwbGVhc3VyZS4=".to_string())
+    );
+
+    let ids: Vec<_> = res.known_marketplaces().iter().map(|m| m.id).collect();
+    assert_eq!(ids, vec![MARKETPLACE_ID_CA, MARKETPLACE_ID_US]);
+  }
+}
@@ -0,0 +1,32 @@
+/// A marketplace the seller can sell in
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct Participation {
+  pub MarketplaceId: String,
+  pub SellerId: String,
+  /// `Yes` if the seller has suspended listings in the marketplace.
+  pub HasSellerSuspendedListings: String,
+}
+
+impl Participation {
+  /// Returns `true` if the seller has suspended listings in the marketplace.
+  pub fn has_suspended_listings(&self) -> bool {
+    self.HasSellerSuspendedListings == "Yes"
+  }
+}
+
+/// Information about a marketplace
+#[allow(non_snake_case)]
+#[derive(Debug, Default, PartialEq, Serialize, FromXmlStream)]
+pub struct Marketplace {
+  pub MarketplaceId: String,
+  pub Name: String,
+  /// ISO 3166-1 alpha-2 country code, e.g. `US`.
+  pub DefaultCountryCode: String,
+  /// ISO 4217 currency code, e.g. `USD`.
+  pub DefaultCurrencyCode: String,
+  /// Language and country code, e.g. `en_US`.
+  pub DefaultLanguageCode: String,
+  /// e.g. `www.amazon.com`
+  pub DomainName: String,
+}
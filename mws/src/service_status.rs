@@ -0,0 +1,178 @@
+//! Operational status of the API sections
+//!
+//! Every section except Feeds and Reports has a `GetServiceStatus` operation, e.g.
+//! [`orders::GetServiceStatus`](../orders/fn.GetServiceStatus.html).
+//! [`poll_all`](fn.poll_all.html) asks all of them at once:
+//!
+//! ```ignore
+//! for section in service_status::poll_all(&client) {
+//!   match section.result {
+//!     Ok(ref res) => println!("{}: {:?}", section.name, res.Status),
+//!     Err(ref err) => println!("{}: {}", section.name, err),
+//!   }
+//! }
+//! ```
+//!
+//! [Reference](http://docs.developer.amazonservices.com/en_US/dev_guide/DG_ServiceStatus.html)
+
+use async_client::{AsyncClient, MwsFuture};
+use chrono::{DateTime, Utc};
+use client::{Client, Method};
+use futures::{future, Future};
+use result::{MwsError, MwsResult};
+
+str_enum! {
+  /// Status of a section
+  ///
+  /// - `GREEN`: the service is operating normally
+  /// - `GREEN_I`: the service is operating normally, `Messages` contains additional information
+  /// - `YELLOW`: the service is experiencing higher than normal error rates or degraded performance
+  /// - `RED`: the service is unavailable or experiencing extremely high error rates
+  pub enum ServiceStatus {
+    GREEN,
+    GREEN_I,
+    YELLOW,
+    RED,
+  }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Clone, Serialize, FromXmlStream)]
+pub struct ServiceStatusMessage {
+  pub Locale: String,
+  pub Text: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Default, Clone, Serialize, FromXmlStream)]
+pub struct GetServiceStatusResponse {
+  pub Status: ServiceStatus,
+  pub Timestamp: Option<DateTime<Utc>>,
+  pub MessageId: Option<String>,
+  pub Messages: Vec<ServiceStatusMessage>,
+}
+
+response_envelope_type!(
+  GetServiceStatusEnvelope<GetServiceStatusResponse>,
+  "GetServiceStatusResponse",
+  "GetServiceStatusResult"
+);
+
+/// Returns the status of the section at `path`.
+pub fn get_service_status(
+  client: &Client,
+  path: &str,
+  version: &str,
+) -> MwsResult<GetServiceStatusResponse> {
+  client
+    .request_xml(Method::Post, path, version, "GetServiceStatus", ())
+    .map(|e: GetServiceStatusEnvelope| e.into_inner())
+}
+
+/// Asynchronous version of [`get_service_status`](fn.get_service_status.html).
+pub fn get_service_status_async(
+  client: &AsyncClient,
+  path: &str,
+  version: &str,
+) -> MwsFuture<GetServiceStatusResponse> {
+  Box::new(
+    client
+      .request_xml(Method::Post, path, version, "GetServiceStatus", ())
+      .map(|e: GetServiceStatusEnvelope| e.into_inner()),
+  )
+}
+
+/// (name, path, version) of the sections with a `GetServiceStatus` operation
+#[cfg_attr(rustfmt, rustfmt_skip)]
+pub static SECTIONS: &'static [(&'static str, &'static str, &'static str)] = &[
+  ("Orders", ::orders::PATH, ::orders::VERSION),
+  ("Products", ::products::PATH, ::products::VERSION),
+  ("FulfillmentInventory", ::fulfillment_inventory::PATH, ::fulfillment_inventory::VERSION),
+  ("FulfillmentInboundShipment", ::fulfillment_inbound_shipment::PATH, ::fulfillment_inbound_shipment::VERSION),
+  ("FulfillmentOutboundShipment", ::fulfillment_outbound::PATH, ::fulfillment_outbound::VERSION),
+  ("MerchantFulfillment", ::merchant_fulfillment::PATH, ::merchant_fulfillment::VERSION),
+  ("Subscriptions", ::subscriptions::PATH, ::subscriptions::VERSION),
+  ("Sellers", ::sellers::PATH, ::sellers::VERSION),
+  ("Finances", ::finances::PATH, ::finances::VERSION),
+];
+
+/// The status of a section, see [`poll_all`](fn.poll_all.html)
+#[derive(Debug)]
+pub struct SectionStatus {
+  pub name: &'static str,
+  pub path: &'static str,
+  pub result: MwsResult<GetServiceStatusResponse>,
+}
+
+/// Returns the status of all sections in `SECTIONS`.
+///
+/// Errors are returned per section, e.g. `AccessDenied` for sections the seller has no access to.
+pub fn poll_all(client: &Client) -> Vec<SectionStatus> {
+  SECTIONS
+    .iter()
+    .map(|&(name, path, version)| SectionStatus {
+      name: name,
+      path: path,
+      result: get_service_status(client, path, version),
+    })
+    .collect()
+}
+
+/// Asynchronous version of [`poll_all`](fn.poll_all.html), the sections are requested concurrently.
+pub fn poll_all_async(client: &AsyncClient) -> MwsFuture<Vec<SectionStatus>> {
+  let futures: Vec<_> = SECTIONS
+    .iter()
+    .map(|&(name, path, version)| {
+      get_service_status_async(client, path, version).then(move |result| {
+        Ok::<_, MwsError>(SectionStatus {
+          name: name,
+          path: path,
+          result: result,
+        })
+      })
+    })
+    .collect();
+  Box::new(future::join_all(futures))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Cursor;
+  use xmlhelper::decode;
+  use xmlhelper::decode::FromXmlStream;
+
+  #[test]
+  fn test_decode() {
+    let mut s = decode::Stream::new(Cursor::new(
+      r#"<?xml version="1.0"?>
+    <GetServiceStatusResponse xmlns="https://mws.amazonservices.com/Orders/2013-09-01">
+      <GetServiceStatusResult>
+        <Status>GREEN_I</Status>
+        <Timestamp>2010-11-01T21:38:09.676Z</Timestamp>
+        <MessageId>173964729I</MessageId>
+        <Messages>
+          <Message>
+            <Locale>en_US</Locale>
+            <Text>We are experiencing high latency in UK because of heavy traffic.</Text>
+          </Message>
+        </Messages>
+      </GetServiceStatusResult>
+      <ResponseMetadata>
+        <RequestId>d80c6c7b-f7c7-4fa7-bdd7-854711cb3bcc</RequestId>
+      </ResponseMetadata>
+    </GetServiceStatusResponse>"#,
+    ));
+    let res = GetServiceStatusEnvelope::from_xml(&mut s)
+      .expect("decode")
+      .into_inner();
+    assert_eq!(res.Status, ServiceStatus::GREEN_I);
+    assert_eq!(
+      res.Timestamp,
+      Some("2010-11-01T21:38:09.676Z".parse().unwrap())
+    );
+    assert_eq!(res.MessageId, Some("173964729I".to_string()));
+    assert_eq!(res.Messages.len(), 1);
+    assert_eq!(res.Messages[0].Locale, "en_US");
+  }
+}
@@ -4,12 +4,15 @@
 //! http://docs.aws.amazon.com/general/latest/gr/signature-version-2.html
 //!
 
+use chrono::{DateTime, Utc};
 pub use reqwest::Method;
 use result::{MwsError, MwsResult};
+use secret::Secret;
 use std::cmp::{Ord, Ordering};
 use std::path::Path;
 use types::ToIso8601;
 use url::percent_encoding::{percent_encode, EncodeSet};
+use url::Url;
 
 /// URL encode the parameter name and values according to the following rules:
 /// - Do not URL encode any of the unreserved characters that RFC 3986 defines. These unreserved characters are A-Z, a-z, 0-9, hyphen ( - ), underscore ( _ ), period ( . ), and tilde ( ~ ).
@@ -55,6 +58,7 @@ impl Ord for Param {
 }
 
 pub struct SignedUrl<'a> {
+  pub scheme: &'a str,
   pub host: &'a str,
   pub method: Method,
   pub path: String,
@@ -71,7 +75,12 @@ impl<'a> Into<String> for SignedUrl<'a> {
 
 impl<'a> SignedUrl<'a> {
   pub fn get_url_without_query(&self) -> String {
-    format!("https://{host}{path}", host = &self.host, path = self.path,)
+    format!(
+      "{scheme}://{host}{path}",
+      scheme = &self.scheme,
+      host = &self.host,
+      path = self.path,
+    )
   }
 
   pub fn to_string(self) -> String {
@@ -81,7 +90,8 @@ impl<'a> SignedUrl<'a> {
       signature_encoded.push_str(part);
     }
     format!(
-      "https://{host}{path}?{qs}&Signature={signature}",
+      "{scheme}://{host}{path}?{qs}&Signature={signature}",
+      scheme = &self.scheme,
       host = &self.host,
       path = self.path,
       qs = self.query_string,
@@ -93,30 +103,55 @@ impl<'a> SignedUrl<'a> {
 /// Signature V2 generator
 #[derive(Debug, Clone)]
 pub struct SignatureV2 {
+  scheme: String,
   host: String,
   aws_access_key_id: String,
-  secret_key: String,
-  auth_token: Option<String>,
+  secret_key: Secret,
+  auth_token: Option<Secret>,
   pairs: Vec<Param>,
+  clock_offset: ::chrono::Duration,
+  expires_in: Option<::chrono::Duration>,
 }
 
 impl SignatureV2 {
   /// Constructs a new, empty generator
+  ///
+  /// `host` may start with a scheme, e.g. `http://127.0.0.1:8080`, the default is `https`.
   pub fn new(
     host: &str,
     aws_access_key_id: &str,
     secret_key: &str,
     auth_token: Option<&str>,
   ) -> SignatureV2 {
+    let (scheme, host) = match host.find("://") {
+      Some(pos) => (&host[..pos], &host[pos + 3..]),
+      None => ("https", host),
+    };
     SignatureV2 {
+      scheme: scheme.to_string(),
       host: host.into(),
       aws_access_key_id: aws_access_key_id.to_string(),
-      secret_key: secret_key.to_string(),
-      auth_token: auth_token.map(ToString::to_string),
+      secret_key: Secret::new(secret_key),
+      auth_token: auth_token.map(Secret::new),
       pairs: Vec::new(),
+      clock_offset: ::chrono::Duration::zero(),
+      expires_in: None,
     }
   }
 
+  /// Sets the difference between the clock of MWS and the local clock,
+  /// which is added to the `Timestamp` or `Expires` of the request.
+  pub fn set_clock_offset(&mut self, offset: ::chrono::Duration) -> &mut Self {
+    self.clock_offset = offset;
+    self
+  }
+
+  /// Stamps requests with `Expires`, `expires_in` from now, instead of `Timestamp`.
+  pub fn set_expires_in(&mut self, expires_in: Option<::chrono::Duration>) -> &mut Self {
+    self.expires_in = expires_in;
+    self
+  }
+
   /// Adds a key/value pair. Duplicated key is overridden.
   pub fn add<T: Into<String>>(&mut self, key: &str, value: T) -> &mut Self {
     SignatureV2::set_param(&mut self.pairs, key, value);
@@ -142,65 +177,48 @@ impl SignatureV2 {
     version: T,
     action: T,
   ) -> MwsResult<SignedUrl<'a>> {
-    use crypto::hmac::Hmac;
-    use crypto::mac::Mac;
-    use crypto::sha2::Sha256;
-
     let mut params = self.pairs.clone();
-    let mut qs = String::with_capacity(255);
 
     SignatureV2::set_param(&mut params, "AWSAccessKeyId", &self.aws_access_key_id);
     if let Some(auth_token) = self.auth_token.as_ref() {
-      SignatureV2::set_param(&mut params, "MWSAuthToken", auth_token);
+      SignatureV2::set_param(&mut params, "MWSAuthToken", auth_token.expose());
     }
     SignatureV2::set_param(&mut params, "SignatureMethod", "HmacSHA256");
     SignatureV2::set_param(&mut params, "SignatureVersion", "2");
     SignatureV2::set_param(&mut params, "Version", version.as_ref());
     SignatureV2::set_param(&mut params, "Action", action.as_ref());
 
-    if !params.iter().any(|pair| pair.0 == "Timestamp") {
-      SignatureV2::set_param(&mut params, "Timestamp", ::chrono::Utc::now().to_iso8601());
+    if !params
+      .iter()
+      .any(|pair| pair.0 == "Timestamp" || pair.0 == "Expires")
+    {
+      let now = Utc::now() + self.clock_offset;
+      match self.expires_in {
+        Some(expires_in) => {
+          SignatureV2::set_param(&mut params, "Expires", (now + expires_in).to_iso8601())
+        }
+        None => SignatureV2::set_param(&mut params, "Timestamp", now.to_iso8601()),
+      }
     }
 
-    let mut pairs = vec![];
     params.sort();
-    for Param(key, value) in params {
-      if qs.len() > 0 {
-        qs.push_str("&");
-      }
-
-      for part in percent_encode(key.as_bytes(), ParameterEncodeSet) {
-        qs.push_str(part);
-      }
-
-      qs.push_str("=");
-
-      for part in percent_encode(value.as_bytes(), ParameterEncodeSet) {
-        qs.push_str(part);
-      }
-
-      pairs.push((key, value));
-    }
+    let qs = canonical_query(&params);
+    let pairs = params
+      .into_iter()
+      .map(|Param(key, value)| (key, value))
+      .collect();
 
     let path_str = path.as_ref().to_str().ok_or_else(|| {
       let display = path.as_ref().display();
       MwsError::InvalidPath(format!("{}", display))
     })?;
-    let signature = {
-      let canonical_qs = format!(
-        "{method}\n{host}\n{path}\n{qs}",
-        method = &method,
-        host = &self.host,
-        path = path_str,
-        qs = qs
-      );
-      // println!("string to sign: {}", canonical_qs);
-      let mut hmac = Hmac::new(Sha256::new(), self.secret_key.as_bytes());
-      hmac.input(canonical_qs.as_bytes());
-      base64::encode(&hmac.result().code())
-    };
+    let signature = sign(
+      self.secret_key.expose(),
+      &string_to_sign(&method, &self.host, path_str, &qs),
+    );
 
     Ok(SignedUrl {
+      scheme: &self.scheme,
       host: &self.host,
       method: method,
       path: path_str.to_string(),
@@ -211,6 +229,284 @@ impl SignatureV2 {
   }
 }
 
+fn percent_encode_str(value: &str, out: &mut String) {
+  for part in percent_encode(value.as_bytes(), ParameterEncodeSet) {
+    out.push_str(part);
+  }
+}
+
+/// Encodes sorted parameters as the canonical query string
+fn canonical_query(params: &[Param]) -> String {
+  let mut qs = String::with_capacity(255);
+  for &Param(ref key, ref value) in params {
+    if qs.len() > 0 {
+      qs.push_str("&");
+    }
+    percent_encode_str(key, &mut qs);
+    qs.push_str("=");
+    percent_encode_str(value, &mut qs);
+  }
+  qs
+}
+
+/// Builds the string to sign, the host is lowercased as it is case-insensitive
+fn string_to_sign(method: &Method, host: &str, path: &str, qs: &str) -> String {
+  format!(
+    "{method}\n{host}\n{path}\n{qs}",
+    method = method,
+    host = host.to_lowercase(),
+    path = path,
+    qs = qs
+  )
+}
+
+/// Signs `data` with HMAC-SHA256 and encodes the result with base64
+fn sign(secret_key: &str, data: &str) -> String {
+  use crypto::hmac::Hmac;
+  use crypto::mac::Mac;
+  use crypto::sha2::Sha256;
+
+  let mut hmac = Hmac::new(Sha256::new(), secret_key.as_bytes());
+  hmac.input(data.as_bytes());
+  base64::encode(&hmac.result().code())
+}
+
+/// Reasons a request fails verification
+#[derive(Fail, Debug, Clone, PartialEq)]
+pub enum VerifyError {
+  #[fail(display = "missing parameter: {}", _0)]
+  MissingParameter(String),
+  #[fail(
+    display = "unsupported signature: method '{}', version '{}'",
+    method, version
+  )]
+  UnsupportedSignature { method: String, version: String },
+  #[fail(display = "a request must not contain both Timestamp and Expires")]
+  TimestampAndExpires,
+  #[fail(display = "invalid {}: '{}'", name, value)]
+  InvalidDate { name: String, value: String },
+  #[fail(
+    display = "Timestamp {} is too far from the current time {}",
+    timestamp, now
+  )]
+  TimestampOutOfRange {
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+  },
+  #[fail(display = "request expired at {}, current time {}", expires, now)]
+  Expired {
+    expires: DateTime<Utc>,
+    now: DateTime<Utc>,
+  },
+  #[fail(display = "invalid url: {}", _0)]
+  InvalidUrl(String),
+  #[fail(
+    display = "signature does not match, string to sign: {:?}",
+    string_to_sign
+  )]
+  SignatureDoesNotMatch { string_to_sign: String },
+}
+
+/// Signature V2 verifier
+///
+/// Recomputes the signature of a received request with the same rules as
+/// [`SignatureV2`](struct.SignatureV2.html) and checks the `Timestamp` or `Expires` window.
+#[derive(Debug, Clone)]
+pub struct SignatureV2Verifier {
+  secret_key: Secret,
+  max_clock_skew: ::chrono::Duration,
+  now: Option<DateTime<Utc>>,
+}
+
+impl SignatureV2Verifier {
+  /// Constructs a verifier which accepts a `Timestamp` skew of 15 minutes, like MWS.
+  ///
+  /// A request with `Expires` is rejected once it has expired, without skew.
+  pub fn new(secret_key: &str) -> SignatureV2Verifier {
+    SignatureV2Verifier {
+      secret_key: Secret::new(secret_key),
+      max_clock_skew: ::chrono::Duration::minutes(15),
+      now: None,
+    }
+  }
+
+  /// Sets the accepted difference between `Timestamp` and the current time.
+  pub fn max_clock_skew(mut self, max_clock_skew: ::chrono::Duration) -> SignatureV2Verifier {
+    self.max_clock_skew = max_clock_skew;
+    self
+  }
+
+  /// Checks the `Timestamp` or `Expires` window against `now` instead of the current time,
+  /// e.g. to audit a logged URL.
+  pub fn at(mut self, now: DateTime<Utc>) -> SignatureV2Verifier {
+    self.now = Some(now);
+    self
+  }
+
+  /// Returns the canonical string to sign of a request.
+  ///
+  /// `params` are the received query or form parameters, `Signature` is ignored.
+  pub fn string_to_sign(
+    method: &Method,
+    host: &str,
+    path: &str,
+    params: &[(String, String)],
+  ) -> String {
+    let mut params: Vec<Param> = params
+      .iter()
+      .filter(|&&(ref k, _)| k != "Signature")
+      .map(|&(ref k, ref v)| Param(k.clone(), v.clone()))
+      .collect();
+    params.sort();
+    string_to_sign(method, host, path, &canonical_query(&params))
+  }
+
+  /// Verifies a received request.
+  pub fn verify(
+    &self,
+    method: &Method,
+    host: &str,
+    path: &str,
+    params: &[(String, String)],
+  ) -> Result<(), VerifyError> {
+    let get = |name: &str| {
+      params
+        .iter()
+        .find(|&&(ref k, _)| k == name)
+        .map(|&(_, ref v)| v.as_str())
+    };
+    let require =
+      |name: &str| get(name).ok_or_else(|| VerifyError::MissingParameter(name.to_string()));
+
+    for name in &["AWSAccessKeyId", "Action", "Version"] {
+      require(name)?;
+    }
+    let signature = require("Signature")?;
+
+    let signature_method = require("SignatureMethod")?;
+    let signature_version = require("SignatureVersion")?;
+    if signature_method != "HmacSHA256" || signature_version != "2" {
+      return Err(VerifyError::UnsupportedSignature {
+        method: signature_method.to_string(),
+        version: signature_version.to_string(),
+      });
+    }
+
+    let now = self.now.unwrap_or_else(Utc::now);
+    match (get("Timestamp"), get("Expires")) {
+      (Some(_), Some(_)) => return Err(VerifyError::TimestampAndExpires),
+      (Some(value), None) => {
+        let timestamp = parse_date("Timestamp", value)?;
+        if timestamp - now > self.max_clock_skew || now - timestamp > self.max_clock_skew {
+          return Err(VerifyError::TimestampOutOfRange {
+            timestamp: timestamp,
+            now: now,
+          });
+        }
+      }
+      (None, Some(value)) => {
+        let expires = parse_date("Expires", value)?;
+        if now > expires {
+          return Err(VerifyError::Expired {
+            expires: expires,
+            now: now,
+          });
+        }
+      }
+      (None, None) => return Err(VerifyError::MissingParameter("Timestamp".to_string())),
+    }
+
+    let string_to_sign = SignatureV2Verifier::string_to_sign(method, host, path, params);
+    let expected = sign(self.secret_key.expose(), &string_to_sign);
+    if !constant_time_eq(expected.as_bytes(), signature.as_bytes()) {
+      return Err(VerifyError::SignatureDoesNotMatch {
+        string_to_sign: string_to_sign,
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Verifies a signed url, e.g. one produced by `SignedUrl::to_string`.
+  pub fn verify_url(&self, method: &Method, url: &str) -> Result<(), VerifyError> {
+    let url = Url::parse(url).map_err(|err| VerifyError::InvalidUrl(err.to_string()))?;
+    let host = match (url.host_str(), url.port()) {
+      (Some(host), Some(port)) => format!("{}:{}", host, port),
+      (Some(host), None) => host.to_string(),
+      (None, _) => return Err(VerifyError::InvalidUrl("missing host".to_string())),
+    };
+    let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    self.verify(method, &host, url.path(), &params)
+  }
+}
+
+/// Describes the differences between two strings to sign, e.g. ours and the one
+/// expected by a server, one line per difference. Returns an empty list if they are equal.
+pub fn diff_string_to_sign(expected: &str, actual: &str) -> Vec<String> {
+  let split = |s: &str| -> Vec<String> {
+    let mut parts: Vec<String> = s.splitn(4, '\n').map(ToString::to_string).collect();
+    parts.resize(4, String::new());
+    parts
+  };
+  let expected = split(expected);
+  let actual = split(actual);
+  let mut diffs = vec![];
+
+  for (i, name) in ["method", "host", "path"].iter().enumerate() {
+    if expected[i] != actual[i] {
+      diffs.push(format!(
+        "{}: expected {:?}, found {:?}",
+        name, expected[i], actual[i]
+      ));
+    }
+  }
+
+  let pairs = |qs: &str| -> Vec<(String, String)> {
+    qs.split('&')
+      .filter(|pair| !pair.is_empty())
+      .map(|pair| match pair.find('=') {
+        Some(pos) => (pair[..pos].to_string(), pair[pos + 1..].to_string()),
+        None => (pair.to_string(), String::new()),
+      })
+      .collect()
+  };
+  let expected_pairs = pairs(&expected[3]);
+  let actual_pairs = pairs(&actual[3]);
+  for &(ref k, ref v) in &expected_pairs {
+    match actual_pairs.iter().find(|&&(ref ak, _)| ak == k) {
+      Some(&(_, ref av)) if av != v => {
+        diffs.push(format!("parameter {}: expected {:?}, found {:?}", k, v, av))
+      }
+      Some(_) => {}
+      None => diffs.push(format!("parameter {}: missing", k)),
+    }
+  }
+  for &(ref k, _) in &actual_pairs {
+    if !expected_pairs.iter().any(|&(ref ek, _)| ek == k) {
+      diffs.push(format!("parameter {}: unexpected", k));
+    }
+  }
+  if diffs.is_empty() && expected[3] != actual[3] {
+    diffs.push("query: parameters are in a different order".to_string());
+  }
+
+  diffs
+}
+
+fn parse_date(name: &str, value: &str) -> Result<DateTime<Utc>, VerifyError> {
+  value
+    .parse::<DateTime<Utc>>()
+    .map_err(|_| VerifyError::InvalidDate {
+      name: name.to_string(),
+      value: value.to_string(),
+    })
+}
+
+/// Compares two byte strings in a time which depends only on their lengths
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && ::crypto::util::fixed_time_eq(a, b)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -238,4 +534,228 @@ mod tests {
       "e7NJFMRLOOpRUp0IP42irtpKzq404KDbjZpKZ/OWRLI="
     );
   }
+
+  #[test]
+  fn test_scheme() {
+    let s = SignatureV2::new("http://127.0.0.1:8080", "3333", "0000", None);
+    let url = s
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url");
+    assert_eq!(url.host, "127.0.0.1:8080");
+    assert_eq!(
+      url.get_url_without_query(),
+      "http://127.0.0.1:8080/Orders/2013-09-01"
+    );
+    assert!(url
+      .to_string()
+      .starts_with("http://127.0.0.1:8080/Orders/2013-09-01?"));
+  }
+
+  fn signed_pairs(date: DateTime<Utc>) -> Vec<(String, String)> {
+    let mut s = SignatureV2::new("mws.amazonservices.ca", "3333", "0000", None);
+    let url = s
+      .add("Timestamp", date.to_iso8601())
+      .add("MarketplaceId", "5555")
+      .add("SellerId", "1111")
+      .generate_url(
+        Method::Post,
+        "/Products/2011-10-01",
+        "2011-10-01",
+        "GetMatchingProduct",
+      )
+      .expect("generate url");
+    let mut pairs = url.pairs.clone();
+    pairs.push(("Signature".to_string(), url.signature.clone()));
+    pairs
+  }
+
+  #[test]
+  fn test_clock_offset() {
+    let get = |url: &SignedUrl, name: &str| {
+      url
+        .pairs
+        .iter()
+        .find(|&&(ref k, _)| k == name)
+        .map(|&(_, ref v)| v.parse::<DateTime<Utc>>().expect("parse date"))
+    };
+
+    let mut s = SignatureV2::new("mws.amazonservices.com", "3333", "0000", None);
+    s.set_clock_offset(::chrono::Duration::hours(1));
+    let url = s
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url");
+    let skew = get(&url, "Timestamp").expect("Timestamp") - Utc::now();
+    assert!(skew > ::chrono::Duration::minutes(59) && skew <= ::chrono::Duration::hours(1));
+
+    s.set_clock_offset(::chrono::Duration::zero())
+      .set_expires_in(Some(::chrono::Duration::minutes(5)));
+    let url = s
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url");
+    assert_eq!(get(&url, "Timestamp"), None);
+    let expires = get(&url, "Expires").expect("Expires") - Utc::now();
+    assert!(expires > ::chrono::Duration::minutes(4) && expires <= ::chrono::Duration::minutes(5));
+    assert_eq!(
+      SignatureV2Verifier::new("0000").verify_url(&Method::Post, &url.to_string()),
+      Ok(())
+    );
+  }
+
+  #[test]
+  fn test_verify() {
+    let date: DateTime<Utc> = "2016-12-20T18:42:04Z".parse().expect("parse date");
+    let pairs = signed_pairs(date);
+    let verifier = SignatureV2Verifier::new("0000").at(date);
+    let path = "/Products/2011-10-01";
+    assert_eq!(
+      verifier.verify(&Method::Post, "mws.amazonservices.ca", path, &pairs),
+      Ok(())
+    );
+
+    match SignatureV2Verifier::new("1111").at(date).verify(
+      &Method::Post,
+      "mws.amazonservices.ca",
+      path,
+      &pairs,
+    ) {
+      Err(VerifyError::SignatureDoesNotMatch { string_to_sign }) => assert_eq!(
+        string_to_sign,
+        SignatureV2Verifier::string_to_sign(&Method::Post, "mws.amazonservices.ca", path, &pairs)
+      ),
+      res => panic!("unexpected result: {:?}", res),
+    }
+
+    assert!(verifier
+      .verify(&Method::Get, "mws.amazonservices.ca", path, &pairs)
+      .is_err());
+
+    let later = date + ::chrono::Duration::minutes(16);
+    match SignatureV2Verifier::new("0000").at(later).verify(
+      &Method::Post,
+      "mws.amazonservices.ca",
+      path,
+      &pairs,
+    ) {
+      Err(VerifyError::TimestampOutOfRange { .. }) => {}
+      res => panic!("unexpected result: {:?}", res),
+    }
+
+    let unsigned: Vec<_> = pairs
+      .iter()
+      .cloned()
+      .filter(|&(ref k, _)| k != "Signature")
+      .collect();
+    assert_eq!(
+      verifier.verify(&Method::Post, "mws.amazonservices.ca", path, &unsigned),
+      Err(VerifyError::MissingParameter("Signature".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_verify_expires() {
+    let expires: DateTime<Utc> = "2016-12-20T18:42:04Z".parse().expect("parse date");
+    let mut s = SignatureV2::new("mws.amazonservices.ca", "3333", "0000", None);
+    let url = s
+      .add("Expires", expires.to_iso8601())
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url")
+      .to_string();
+
+    let before = expires - ::chrono::Duration::seconds(1);
+    assert_eq!(
+      SignatureV2Verifier::new("0000")
+        .at(before)
+        .verify_url(&Method::Post, &url),
+      Ok(())
+    );
+
+    let after = expires + ::chrono::Duration::seconds(1);
+    match SignatureV2Verifier::new("0000")
+      .at(after)
+      .verify_url(&Method::Post, &url)
+    {
+      Err(VerifyError::Expired { .. }) => {}
+      res => panic!("unexpected result: {:?}", res),
+    }
+  }
+
+  #[test]
+  fn test_host_case() {
+    let date: DateTime<Utc> = "2016-12-20T18:42:04Z".parse().expect("parse date");
+    let mut s = SignatureV2::new("MWS.AmazonServices.ca", "3333", "0000", None);
+    let url = s
+      .add("Timestamp", date.to_iso8601())
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url");
+    let mut pairs = url.pairs.clone();
+    pairs.push(("Signature".to_string(), url.signature.clone()));
+
+    let verifier = SignatureV2Verifier::new("0000").at(date);
+    let path = "/Orders/2013-09-01";
+    for host in &["MWS.AmazonServices.ca", "mws.amazonservices.ca"] {
+      assert_eq!(verifier.verify(&Method::Post, host, path, &pairs), Ok(()));
+    }
+  }
+
+  #[test]
+  fn test_verify_url() {
+    let date: DateTime<Utc> = "2016-12-20T18:42:04Z".parse().expect("parse date");
+    let mut s = SignatureV2::new("http://127.0.0.1:8080", "3333", "0000", None);
+    let url = s
+      .add("Timestamp", date.to_iso8601())
+      .add("Marketplace Id", "a+b/c")
+      .generate_url(
+        Method::Post,
+        "/Orders/2013-09-01",
+        "2013-09-01",
+        "ListOrders",
+      )
+      .expect("generate url")
+      .to_string();
+    let verifier = SignatureV2Verifier::new("0000").at(date);
+    assert_eq!(verifier.verify_url(&Method::Post, &url), Ok(()));
+    assert!(verifier
+      .verify_url(&Method::Post, &url.replace("8080", "8081"))
+      .is_err());
+  }
+
+  #[test]
+  fn test_diff_string_to_sign() {
+    let expected = "POST\nmws.amazonservices.com\n/\nA=1&B=2&C=3";
+    assert!(diff_string_to_sign(expected, expected).is_empty());
+    assert_eq!(
+      diff_string_to_sign(expected, "GET\nmws.amazonservices.com\n/\nA=1&B=3&D=4"),
+      vec![
+        "method: expected \"POST\", found \"GET\"".to_string(),
+        "parameter B: expected \"2\", found \"3\"".to_string(),
+        "parameter C: missing".to_string(),
+        "parameter D: unexpected".to_string(),
+      ]
+    );
+  }
 }
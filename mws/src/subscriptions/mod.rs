@@ -2,15 +2,19 @@
 //!
 //! [Reference](http://docs.developer.amazonservices.com/en_US/subscriptions/Subscriptions_Overview.html)
 
+use async_client::{AsyncClient, MwsFuture};
 use client::{Client, Method};
+use futures::Future;
 use result::MwsResult;
 
 pub mod types;
 pub use self::types::*;
 pub mod notification;
 
-static PATH: &'static str = "/Subscriptions/2013-07-01";
-static VERSION: &'static str = "2013-07-01";
+pub(crate) const PATH: &'static str = "/Subscriptions/2013-07-01";
+pub(crate) const VERSION: &'static str = "2013-07-01";
+
+get_service_status!(PATH, VERSION);
 
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
@@ -30,6 +34,15 @@ pub fn RegisterDestination(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`RegisterDestination`](fn.RegisterDestination.html).
+#[allow(non_snake_case)]
+pub fn RegisterDestinationAsync(
+  client: &AsyncClient,
+  params: RegisterDestinationParameters,
+) -> MwsFuture<()> {
+  Box::new(client.request_xml::<_, ()>(Method::Post, PATH, VERSION, "RegisterDestination", params))
+}
+
 /// Removes an existing destination from the list of registered destinations.
 #[allow(non_snake_case)]
 pub fn DeregisterDestination(
@@ -41,6 +54,21 @@ pub fn DeregisterDestination(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`DeregisterDestination`](fn.DeregisterDestination.html).
+#[allow(non_snake_case)]
+pub fn DeregisterDestinationAsync(
+  client: &AsyncClient,
+  params: RegisterDestinationParameters,
+) -> MwsFuture<()> {
+  Box::new(client.request_xml::<_, ()>(
+    Method::Post,
+    PATH,
+    VERSION,
+    "DeregisterDestination",
+    params,
+  ))
+}
+
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
 pub struct ListRegisteredDestinationsResponse {
@@ -71,6 +99,25 @@ pub fn ListRegisteredDestinations(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListRegisteredDestinations`](fn.ListRegisteredDestinations.html).
+#[allow(non_snake_case)]
+pub fn ListRegisteredDestinationsAsync(
+  client: &AsyncClient,
+  marketplace_id: String,
+) -> MwsFuture<ListRegisteredDestinationsResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListRegisteredDestinations",
+        vec![("MarketplaceId".to_string(), marketplace_id)],
+      )
+      .map(|e: ListRegisteredDestinationsResponseEnvelope| e.into_inner()),
+  )
+}
+
 /// Sends a test notification to an existing destination.
 #[allow(non_snake_case)]
 pub fn SendTestNotificationToDestination(
@@ -88,6 +135,21 @@ pub fn SendTestNotificationToDestination(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`SendTestNotificationToDestination`](fn.SendTestNotificationToDestination.html).
+#[allow(non_snake_case)]
+pub fn SendTestNotificationToDestinationAsync(
+  client: &AsyncClient,
+  params: RegisterDestinationParameters,
+) -> MwsFuture<()> {
+  Box::new(client.request_xml::<_, ()>(
+    Method::Post,
+    PATH,
+    VERSION,
+    "SendTestNotificationToDestination",
+    params,
+  ))
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
 pub struct CreateSubscriptionParameters {
@@ -103,6 +165,15 @@ pub fn CreateSubscription(client: &Client, params: CreateSubscriptionParameters)
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`CreateSubscription`](fn.CreateSubscription.html).
+#[allow(non_snake_case)]
+pub fn CreateSubscriptionAsync(
+  client: &AsyncClient,
+  params: CreateSubscriptionParameters,
+) -> MwsFuture<()> {
+  Box::new(client.request_xml::<_, ()>(Method::Post, PATH, VERSION, "CreateSubscription", params))
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Serialize, SerializeMwsParams)]
 pub struct GetSubscriptionParameters {
@@ -135,6 +206,19 @@ pub fn GetSubscription(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`GetSubscription`](fn.GetSubscription.html).
+#[allow(non_snake_case)]
+pub fn GetSubscriptionAsync(
+  client: &AsyncClient,
+  params: GetSubscriptionParameters,
+) -> MwsFuture<GetSubscriptionResponse> {
+  Box::new(
+    client
+      .request_xml(Method::Post, PATH, VERSION, "GetSubscription", params)
+      .map(|e: GetSubscriptionResponseEnvelope| e.into_inner()),
+  )
+}
+
 #[derive(FromXmlStream, Default, Debug)]
 #[allow(non_snake_case)]
 pub struct ListSubscriptionsResponse {
@@ -165,6 +249,25 @@ pub fn ListSubscriptions(
     .map_err(|err| err.into())
 }
 
+/// Asynchronous version of [`ListSubscriptions`](fn.ListSubscriptions.html).
+#[allow(non_snake_case)]
+pub fn ListSubscriptionsAsync(
+  client: &AsyncClient,
+  marketplace_id: String,
+) -> MwsFuture<ListSubscriptionsResponse> {
+  Box::new(
+    client
+      .request_xml(
+        Method::Post,
+        PATH,
+        VERSION,
+        "ListSubscriptions",
+        vec![("MarketplaceId".to_string(), marketplace_id)],
+      )
+      .map(|e: ListSubscriptionsResponseEnvelope| e.into_inner()),
+  )
+}
+
 /// Updates the subscription for the specified notification type and destination.
 #[allow(non_snake_case)]
 pub fn UpdateSubscription(client: &Client, params: CreateSubscriptionParameters) -> MwsResult<()> {
@@ -172,3 +275,12 @@ pub fn UpdateSubscription(client: &Client, params: CreateSubscriptionParameters)
     .request_xml(Method::Post, PATH, VERSION, "UpdateSubscription", params)
     .map_err(|err| err.into())
 }
+
+/// Asynchronous version of [`UpdateSubscription`](fn.UpdateSubscription.html).
+#[allow(non_snake_case)]
+pub fn UpdateSubscriptionAsync(
+  client: &AsyncClient,
+  params: CreateSubscriptionParameters,
+) -> MwsFuture<()> {
+  Box::new(client.request_xml::<_, ()>(Method::Post, PATH, VERSION, "UpdateSubscription", params))
+}
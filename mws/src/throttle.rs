@@ -0,0 +1,442 @@
+//! Client-side request throttling
+//!
+//! Amazon MWS throttles every operation with a maximum request quota and a restore rate.
+//! `Throttler` keeps a token bucket per endpoint, seller and operation, pre-populated with the
+//! [documented limits](http://docs.developer.amazonservices.com/en_US/dev_guide/DG_Throttling.html),
+//! so requests can be delayed or rejected before they are sent.
+//!
+//! A `...ByNextToken` operation shares the quota and the bucket of the operation it continues,
+//! e.g. `ListOrdersByNextToken` takes from the `ListOrders` bucket.
+//!
+//! A `Throttler` is `Sync` and can be shared between clients with an `Arc`.
+
+use result::{MwsError, MwsResult};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Throttling limits of an operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+  /// Maximum number of requests that can be submitted at once
+  pub max_request_quota: u32,
+  /// Time it takes to restore one request
+  pub restore_period: Duration,
+}
+
+impl Quota {
+  pub fn new(max_request_quota: u32, restore_period: Duration) -> Quota {
+    Quota {
+      max_request_quota: max_request_quota,
+      restore_period: restore_period,
+    }
+  }
+}
+
+/// What to do when a request exceeds its quota
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleMode {
+  /// Block the calling thread until the request can be sent
+  Wait,
+  /// Fail with `MwsError::Throttled`
+  Reject,
+}
+
+/// (path, action, max request quota, restore period in milliseconds)
+///
+/// `...ByNextToken` actions are not listed, see `throttle_class`.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+static DEFAULT_QUOTAS: &'static [(&'static str, &'static str, u32, u64)] = &[
+  // Feeds
+  ("/", "SubmitFeed", 15, 120_000),
+  ("/", "GetFeedSubmissionList", 10, 45_000),
+  ("/", "GetFeedSubmissionCount", 10, 45_000),
+  ("/", "CancelFeedSubmissions", 10, 45_000),
+  ("/", "GetFeedSubmissionResult", 15, 60_000),
+  // Reports
+  ("/", "RequestReport", 15, 60_000),
+  ("/", "GetReportRequestList", 10, 45_000),
+  ("/", "GetReportRequestCount", 10, 45_000),
+  ("/", "CancelReportRequests", 10, 45_000),
+  ("/", "GetReportList", 10, 60_000),
+  ("/", "GetReportCount", 10, 45_000),
+  ("/", "GetReport", 15, 60_000),
+  ("/", "ManageReportSchedule", 10, 45_000),
+  ("/", "GetReportScheduleList", 10, 45_000),
+  ("/", "GetReportScheduleCount", 10, 45_000),
+  ("/", "UpdateReportAcknowledgements", 10, 45_000),
+  // Orders
+  ("/Orders/2013-09-01", "ListOrders", 6, 60_000),
+  ("/Orders/2013-09-01", "GetOrder", 6, 60_000),
+  ("/Orders/2013-09-01", "ListOrderItems", 30, 2_000),
+  ("/Orders/2013-09-01", "GetServiceStatus", 2, 300_000),
+  // Fulfillment Inventory
+  ("/FulfillmentInventory/2010-10-01", "ListInventorySupply", 30, 500),
+  ("/FulfillmentInventory/2010-10-01", "GetServiceStatus", 2, 300_000),
+  // Fulfillment Inbound Shipment
+  ("/FulfillmentInboundShipment/2010-10-01", "ListInboundShipments", 30, 500),
+  ("/FulfillmentInboundShipment/2010-10-01", "ListInboundShipmentItems", 30, 500),
+  ("/FulfillmentInboundShipment/2010-10-01", "GetServiceStatus", 2, 300_000),
+  // Fulfillment Outbound Shipment
+  ("/FulfillmentOutboundShipment/2010-10-01", "GetFulfillmentPreview", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "CreateFulfillmentOrder", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "ListAllFulfillmentOrders", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "GetFulfillmentOrder", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "CancelFulfillmentOrder", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "GetPackageTrackingDetails", 30, 500),
+  ("/FulfillmentOutboundShipment/2010-10-01", "GetServiceStatus", 2, 300_000),
+  // Merchant Fulfillment
+  ("/MerchantFulfillment/2015-06-01", "GetEligibleShippingServices", 10, 200),
+  ("/MerchantFulfillment/2015-06-01", "CreateShipment", 10, 200),
+  ("/MerchantFulfillment/2015-06-01", "GetShipment", 10, 200),
+  ("/MerchantFulfillment/2015-06-01", "CancelShipment", 10, 200),
+  ("/MerchantFulfillment/2015-06-01", "GetServiceStatus", 2, 300_000),
+  // Products
+  ("/Products/2011-10-01", "ListMatchingProducts", 20, 5_000),
+  ("/Products/2011-10-01", "GetMatchingProduct", 20, 500),
+  ("/Products/2011-10-01", "GetMatchingProductForId", 20, 200),
+  ("/Products/2011-10-01", "GetCompetitivePricingForSKU", 20, 100),
+  ("/Products/2011-10-01", "GetCompetitivePricingForASIN", 20, 100),
+  ("/Products/2011-10-01", "GetLowestOfferListingsForSKU", 20, 100),
+  ("/Products/2011-10-01", "GetLowestOfferListingsForASIN", 20, 100),
+  ("/Products/2011-10-01", "GetLowestPricedOffersForSKU", 10, 200),
+  ("/Products/2011-10-01", "GetLowestPricedOffersForASIN", 10, 200),
+  ("/Products/2011-10-01", "GetMyFeesEstimate", 20, 100),
+  ("/Products/2011-10-01", "GetMyPriceForSKU", 20, 100),
+  ("/Products/2011-10-01", "GetMyPriceForASIN", 20, 100),
+  ("/Products/2011-10-01", "GetProductCategoriesForSKU", 20, 5_000),
+  ("/Products/2011-10-01", "GetProductCategoriesForASIN", 20, 5_000),
+  ("/Products/2011-10-01", "GetServiceStatus", 2, 300_000),
+  // Subscriptions
+  ("/Subscriptions/2013-07-01", "RegisterDestination", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "DeregisterDestination", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "ListRegisteredDestinations", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "SendTestNotificationToDestination", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "CreateSubscription", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "GetSubscription", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "DeleteSubscription", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "ListSubscriptions", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "UpdateSubscription", 25, 1_000),
+  ("/Subscriptions/2013-07-01", "GetServiceStatus", 2, 300_000),
+  // Finances
+  ("/Finances/2015-05-01", "ListFinancialEventGroups", 30, 2_000),
+  ("/Finances/2015-05-01", "ListFinancialEvents", 30, 2_000),
+  ("/Finances/2015-05-01", "GetServiceStatus", 2, 300_000),
+  // Sellers
+  ("/Sellers/2011-07-01", "ListMarketplaceParticipations", 15, 60_000),
+  ("/Sellers/2011-07-01", "GetServiceStatus", 2, 300_000),
+];
+
+#[derive(Debug)]
+struct Bucket {
+  tokens: f64,
+  updated_at: Instant,
+}
+
+impl Bucket {
+  fn refill(&mut self, quota: &Quota, now: Instant) {
+    let elapsed = duration_to_secs(now.duration_since(self.updated_at));
+    let period = duration_to_secs(quota.restore_period);
+    let max = quota.max_request_quota as f64;
+    if period > 0.0 {
+      self.tokens = (self.tokens + elapsed / period).min(max);
+    } else {
+      self.tokens = max;
+    }
+    self.updated_at = now;
+  }
+}
+
+/// Token bucket table keyed by endpoint, seller, section path and action
+///
+/// MWS counts the requests of a seller separately in every region, so clients of the same seller
+/// which send to different endpoints do not share buckets.
+#[derive(Debug)]
+pub struct Throttler {
+  mode: ThrottleMode,
+  quotas: RwLock<HashMap<(String, String), Quota>>,
+  buckets: Mutex<HashMap<(String, String, String, String), Bucket>>,
+}
+
+impl Default for Throttler {
+  fn default() -> Throttler {
+    Throttler::new(ThrottleMode::Wait)
+  }
+}
+
+impl Throttler {
+  /// Constructs a throttler with the documented quotas.
+  pub fn new(mode: ThrottleMode) -> Throttler {
+    let quotas = DEFAULT_QUOTAS
+      .iter()
+      .map(|&(path, action, max, restore_ms)| {
+        (
+          (path.to_string(), action.to_string()),
+          Quota::new(max, Duration::from_millis(restore_ms)),
+        )
+      })
+      .collect();
+    Throttler {
+      mode: mode,
+      quotas: RwLock::new(quotas),
+      buckets: Mutex::new(HashMap::new()),
+    }
+  }
+
+  pub fn mode(&self) -> ThrottleMode {
+    self.mode
+  }
+
+  /// Overrides the quota of an operation.
+  ///
+  /// Setting the quota of `action` also sets the quota of `actionByNextToken`.
+  pub fn set_quota(&self, path: &str, action: &str, quota: Quota) {
+    let action = throttle_class(action);
+    self
+      .quotas
+      .write()
+      .unwrap()
+      .insert((path.to_string(), action.to_string()), quota);
+    let mut buckets = self.buckets.lock().unwrap();
+    buckets.retain(|&(_, _, ref p, ref a), _| p != path || a != action);
+  }
+
+  /// Returns the quota of an operation, `None` if the operation is not throttled.
+  pub fn quota(&self, path: &str, action: &str) -> Option<Quota> {
+    self
+      .quotas
+      .read()
+      .unwrap()
+      .get(&(path.to_string(), throttle_class(action).to_string()))
+      .cloned()
+  }
+
+  /// Returns the number of requests which can be sent immediately.
+  pub fn remaining(
+    &self,
+    endpoint: &str,
+    seller_id: &str,
+    path: &str,
+    action: &str,
+  ) -> Option<u32> {
+    let quota = self.quota(path, action)?;
+    let now = Instant::now();
+    let mut buckets = self.buckets.lock().unwrap();
+    let remaining = match buckets.get_mut(&bucket_key(endpoint, seller_id, path, action)) {
+      Some(bucket) => {
+        bucket.refill(&quota, now);
+        bucket.tokens.floor() as u32
+      }
+      None => quota.max_request_quota,
+    };
+    Some(remaining)
+  }
+
+  /// Takes one request from the bucket.
+  ///
+  /// Returns the time to wait before the next request is available if the bucket is empty.
+  pub fn try_acquire(
+    &self,
+    endpoint: &str,
+    seller_id: &str,
+    path: &str,
+    action: &str,
+  ) -> Result<(), Duration> {
+    let quota = match self.quota(path, action) {
+      Some(quota) => quota,
+      None => return Ok(()),
+    };
+    let now = Instant::now();
+    let mut buckets = self.buckets.lock().unwrap();
+    let bucket = buckets
+      .entry(bucket_key(endpoint, seller_id, path, action))
+      .or_insert_with(|| Bucket {
+        tokens: quota.max_request_quota as f64,
+        updated_at: now,
+      });
+    bucket.refill(&quota, now);
+    if bucket.tokens >= 1.0 {
+      bucket.tokens -= 1.0;
+      Ok(())
+    } else {
+      let wait = (1.0 - bucket.tokens) * duration_to_secs(quota.restore_period);
+      Err(secs_to_duration(wait))
+    }
+  }
+
+  /// Takes one request from the bucket, waiting or failing according to the mode.
+  pub fn acquire(
+    &self,
+    endpoint: &str,
+    seller_id: &str,
+    path: &str,
+    action: &str,
+  ) -> MwsResult<()> {
+    loop {
+      match self.try_acquire(endpoint, seller_id, path, action) {
+        Ok(()) => return Ok(()),
+        Err(wait) => match self.mode {
+          ThrottleMode::Wait => thread::sleep(wait),
+          ThrottleMode::Reject => {
+            return Err(MwsError::Throttled {
+              action: action.to_string(),
+              retry_after: wait,
+            })
+          }
+        },
+      }
+    }
+  }
+}
+
+/// Returns the action whose quota `action` counts against.
+fn throttle_class(action: &str) -> &str {
+  const NEXT_TOKEN_SUFFIX: &'static str = "ByNextToken";
+  if action.len() > NEXT_TOKEN_SUFFIX.len() && action.ends_with(NEXT_TOKEN_SUFFIX) {
+    &action[..action.len() - NEXT_TOKEN_SUFFIX.len()]
+  } else {
+    action
+  }
+}
+
+fn bucket_key(
+  endpoint: &str,
+  seller_id: &str,
+  path: &str,
+  action: &str,
+) -> (String, String, String, String) {
+  (
+    endpoint.to_string(),
+    seller_id.to_string(),
+    path.to_string(),
+    throttle_class(action).to_string(),
+  )
+}
+
+fn duration_to_secs(d: Duration) -> f64 {
+  d.as_secs() as f64 + d.subsec_nanos() as f64 / 1_000_000_000.0
+}
+
+fn secs_to_duration(secs: f64) -> Duration {
+  let nanos = (secs * 1_000_000_000.0).ceil() as u64;
+  Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_quotas() {
+    let throttler = Throttler::default();
+    assert_eq!(
+      throttler.quota("/Orders/2013-09-01", "ListOrders"),
+      Some(Quota::new(6, Duration::from_secs(60)))
+    );
+    assert_eq!(
+      throttler.quota("/", "GetReport"),
+      Some(Quota::new(15, Duration::from_secs(60)))
+    );
+    assert_eq!(throttler.quota("/Orders/2013-09-01", "Unknown"), None);
+  }
+
+  #[test]
+  fn test_next_token_shares_quota() {
+    let throttler = Throttler::new(ThrottleMode::Reject);
+    assert_eq!(
+      throttler.quota("/Orders/2013-09-01", "ListOrdersByNextToken"),
+      throttler.quota("/Orders/2013-09-01", "ListOrders")
+    );
+
+    throttler.set_quota("/Test", "Op", Quota::new(2, Duration::from_secs(3600)));
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "OpByNextToken")
+      .unwrap();
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "A", "/Test", "Op"),
+      Some(0)
+    );
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "A", "/Test", "OpByNextToken"),
+      Some(0)
+    );
+    assert!(throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "OpByNextToken")
+      .is_err());
+  }
+
+  #[test]
+  fn test_reject() {
+    let throttler = Throttler::new(ThrottleMode::Reject);
+    throttler.set_quota("/Test", "Op", Quota::new(2, Duration::from_secs(3600)));
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "A", "/Test", "Op"),
+      Some(2)
+    );
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "A", "/Test", "Op"),
+      Some(0)
+    );
+    match throttler.acquire("mws.amazonservices.com", "A", "/Test", "Op") {
+      Err(MwsError::Throttled {
+        ref action,
+        retry_after,
+      }) => {
+        assert_eq!(action, "Op");
+        assert!(retry_after > Duration::from_secs(3500));
+      }
+      other => panic!("unexpected result: {:?}", other),
+    }
+
+    // buckets are per seller
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "B", "/Test", "Op"),
+      Some(2)
+    );
+    throttler
+      .acquire("mws.amazonservices.com", "B", "/Test", "Op")
+      .unwrap();
+
+    // and per endpoint, a seller has separate quotas in every region
+    assert_eq!(
+      throttler.remaining("mws-eu.amazonservices.com", "A", "/Test", "Op"),
+      Some(2)
+    );
+    throttler
+      .acquire("mws-eu.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+
+    // operations without quota are not throttled
+    assert_eq!(
+      throttler.remaining("mws.amazonservices.com", "A", "/Test", "Other"),
+      None
+    );
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Other")
+      .unwrap();
+  }
+
+  #[test]
+  fn test_wait() {
+    let throttler = Throttler::new(ThrottleMode::Wait);
+    throttler.set_quota("/Test", "Op", Quota::new(1, Duration::from_millis(50)));
+    let started = Instant::now();
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+    throttler
+      .acquire("mws.amazonservices.com", "A", "/Test", "Op")
+      .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(40));
+  }
+}
@@ -0,0 +1,245 @@
+//! Pluggable HTTP transport
+//!
+//! `Client` signs every request and hands it to a `HttpTransport` as a `HttpRequest`.
+//! The default transport is `reqwest::Client`; implement the trait to use a fake in
+//! unit tests, record traffic, or route requests through your own HTTP stack.
+
+use async_client::MwsFuture;
+use futures::{Async, Future, Poll, Stream as FutureStream};
+use reqwest;
+use reqwest::header::Headers;
+use reqwest::unstable::async as reqwest_async;
+pub use reqwest::{Method, StatusCode};
+use result::{MwsError, MwsResult};
+use secret::redact;
+use std::fmt;
+use std::io::{self, Cursor, Read};
+use url::form_urlencoded;
+
+/// A signed request
+///
+/// `Debug` and `Display` redact the credentials and the signature, see `secret::redact`.
+#[derive(Clone)]
+pub struct HttpRequest {
+  pub method: Method,
+  pub url: String,
+  pub headers: Vec<(String, String)>,
+  pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+  /// Returns the first header value with the given name, ignoring case.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    find_header(&self.headers, name)
+  }
+
+  /// Returns the first signed parameter with the given name,
+  /// from the query string or from the url-encoded form body.
+  pub fn parameter(&self, name: &str) -> Option<String> {
+    let query = self.url.splitn(2, '?').nth(1).unwrap_or("");
+    let is_form = self
+      .header("Content-Type")
+      .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+      .unwrap_or(false);
+    let form = match self.body {
+      Some(ref body) if is_form => body.as_slice(),
+      _ => &[],
+    };
+    form_urlencoded::parse(query.as_bytes())
+      .chain(form_urlencoded::parse(form))
+      .find(|&(ref k, _)| k == name)
+      .map(|(_, v)| v.into_owned())
+  }
+
+  /// Returns the signed MD5 hash of the body, if the request uploads a body.
+  pub fn content_md5(&self) -> Option<String> {
+    self.parameter("ContentMD5Value")
+  }
+
+  fn raw_headers(&self) -> Headers {
+    let mut headers = Headers::new();
+    for &(ref k, ref v) in &self.headers {
+      headers.set_raw(k.clone(), v.clone());
+    }
+    headers
+  }
+}
+
+impl fmt::Display for HttpRequest {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} {}", self.method, redact(&self.url))
+  }
+}
+
+impl fmt::Debug for HttpRequest {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let body = self
+      .body
+      .as_ref()
+      .map(|body| match ::std::str::from_utf8(body) {
+        Ok(text) => redact(text),
+        Err(_) => format!("<{} bytes>", body.len()),
+      });
+    f.debug_struct("HttpRequest")
+      .field("method", &self.method)
+      .field("url", &redact(&self.url))
+      .field("headers", &self.headers)
+      .field("body", &body)
+      .finish()
+  }
+}
+
+/// A response returned by a transport
+pub struct HttpResponse {
+  pub status: StatusCode,
+  pub headers: Vec<(String, String)>,
+  pub body: Box<dyn Read + Send>,
+}
+
+impl HttpResponse {
+  /// Constructs a response with an in-memory body.
+  pub fn new<B: Into<Vec<u8>>>(
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    body: B,
+  ) -> HttpResponse {
+    HttpResponse {
+      status: status,
+      headers: headers,
+      body: Box::new(Cursor::new(body.into())),
+    }
+  }
+
+  /// Returns the first header value with the given name, ignoring case.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    find_header(&self.headers, name)
+  }
+
+  /// Reads the remaining body into a `Vec<u8>`.
+  pub fn into_bytes(mut self) -> MwsResult<Vec<u8>> {
+    let mut body = vec![];
+    self.body.read_to_end(&mut body)?;
+    Ok(body)
+  }
+}
+
+impl Read for HttpResponse {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.body.read(buf)
+  }
+}
+
+impl fmt::Debug for HttpResponse {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("HttpResponse")
+      .field("status", &self.status)
+      .field("headers", &self.headers)
+      .finish()
+  }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+  headers
+    .iter()
+    .find(|&&(ref k, _)| k.eq_ignore_ascii_case(name))
+    .map(|&(_, ref v)| v.as_ref())
+}
+
+fn collect_headers(headers: &Headers) -> Vec<(String, String)> {
+  headers
+    .iter()
+    .map(|view| (view.name().to_string(), view.value_string()))
+    .collect()
+}
+
+/// Converts a reqwest error, redacting the signed URL quoted in its message.
+fn http_error(err: reqwest::Error) -> MwsError {
+  if err.url().is_none() {
+    return MwsError::Http(err);
+  }
+  MwsError::HttpRedacted {
+    message: redact(&err.to_string()),
+    status: err.status(),
+    redirect: err.is_redirect(),
+  }
+}
+
+/// Sends signed requests for `Client`
+pub trait HttpTransport: Send + Sync {
+  fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse>;
+}
+
+impl HttpTransport for reqwest::Client {
+  fn send(&self, request: HttpRequest) -> MwsResult<HttpResponse> {
+    let headers = request.raw_headers();
+    let mut builder = self.request(request.method, &request.url);
+    builder.headers(headers);
+    if let Some(body) = request.body {
+      builder.body(body);
+    }
+    let resp = builder.send().map_err(http_error)?;
+    Ok(HttpResponse {
+      status: resp.status().clone(),
+      headers: collect_headers(resp.headers()),
+      body: Box::new(resp),
+    })
+  }
+}
+
+/// Sends signed requests for `AsyncClient`
+///
+/// The returned response should carry a fully buffered body.
+pub trait AsyncHttpTransport {
+  fn send(&self, request: HttpRequest) -> MwsFuture<HttpResponse>;
+}
+
+impl AsyncHttpTransport for reqwest_async::Client {
+  fn send(&self, request: HttpRequest) -> MwsFuture<HttpResponse> {
+    let headers = request.raw_headers();
+    let mut builder = self.request(request.method, &request.url);
+    builder.headers(headers);
+    if let Some(body) = request.body {
+      builder.body(body);
+    }
+    Box::new(
+      builder
+        .send()
+        .map_err(http_error)
+        .and_then(|resp| ReadBody {
+          resp: Some(resp),
+          body: vec![],
+        })
+        .map(|(resp, body)| {
+          HttpResponse::new(resp.status().clone(), collect_headers(resp.headers()), body)
+        }),
+    )
+  }
+}
+
+/// Future that reads the whole body of an async reqwest response
+struct ReadBody {
+  resp: Option<reqwest_async::Response>,
+  body: Vec<u8>,
+}
+
+impl Future for ReadBody {
+  type Item = (reqwest_async::Response, Vec<u8>);
+  type Error = MwsError;
+
+  fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+    loop {
+      let chunk = {
+        let resp = self.resp.as_mut().expect("cannot poll ReadBody twice");
+        try_ready!(resp.body_mut().poll().map_err(http_error))
+      };
+      match chunk {
+        Some(chunk) => self.body.extend_from_slice(&chunk),
+        None => {
+          let resp = self.resp.take().expect("cannot poll ReadBody twice");
+          let body = ::std::mem::replace(&mut self.body, vec![]);
+          return Ok(Async::Ready((resp, body)));
+        }
+      }
+    }
+  }
+}